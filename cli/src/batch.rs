@@ -0,0 +1,197 @@
+use std::io::Write;
+use anyhow::Result;
+use super_calculator_core::{Calculation, Environment, Script, Settings};
+
+pub struct Batch {
+    decimal: bool,
+    environment: Environment,
+    settings: Settings,
+}
+
+impl Batch {
+    pub fn new(decimal: bool, settings: Settings) -> Self {
+        Self {
+            decimal,
+            environment: Environment::default(),
+            settings,
+        }
+    }
+
+    /// Prints one result per line and returns whether every line succeeded.
+    pub fn run<I>(self, lines: I) -> Result<bool>
+    where
+        I: Iterator<Item = std::io::Result<String>>,
+    {
+        self.run_with(lines, &mut std::io::stdout().lock(), &mut std::io::stderr().lock())
+    }
+
+    /// Prints the script annotated with results and returns whether every
+    /// statement succeeded.
+    pub fn run_script(self, text: &str) -> Result<bool> {
+        self.run_script_with(text, &mut std::io::stdout().lock())
+    }
+
+    fn run_script_with<W: Write>(mut self, text: &str, out: &mut W) -> Result<bool> {
+        let script = Script::run(text, &mut self.environment, &self.settings);
+        write!(out, "{}", script.annotate(self.decimal))?;
+        Ok(script.is_success())
+    }
+
+    fn run_with<I, W, E>(mut self, lines: I, out: &mut W, err: &mut E) -> Result<bool>
+    where
+        I: Iterator<Item = std::io::Result<String>>,
+        W: Write,
+        E: Write,
+    {
+        let mut success = true;
+        for line in lines {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let results = Calculation::calculate_statements(
+                line, &mut self.environment, &self.settings);
+            for (statement, result) in results {
+                match result {
+                    Ok(calculation) => {
+                        let value = match self.decimal {
+                            true => calculation.grouped_decimal(self.settings.locale),
+                            false => calculation.grouped_result(self.settings.locale),
+                        };
+                        // Approximations are marked; exact results stay
+                        // bare so that they can be piped on.
+                        match calculation.approximate {
+                            true => writeln!(out, "{} {}", calculation.relation(), value)?,
+                            false => writeln!(out, "{}", value)?,
+                        }
+                    },
+                    Err(e) => {
+                        writeln!(err, "エラー: {} ({})", e, statement)?;
+                        success = false;
+                    },
+                }
+            }
+        }
+        Ok(success)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super_calculator_core::{AngleUnit, BitWidth, Locale, Notation};
+
+    fn run(decimal: bool, settings: Settings, lines: &[&str]) -> (bool, String, String) {
+        let mut out = Vec::new();
+        let mut err = Vec::new();
+        let lines = lines.iter().map(|line| Ok(line.to_string()));
+        let success = Batch::new(decimal, settings)
+            .run_with(lines, &mut out, &mut err)
+            .unwrap();
+        (success, String::from_utf8(out).unwrap(), String::from_utf8(err).unwrap())
+    }
+
+    #[test]
+    fn run_prints_one_result_per_line() {
+        let (success, out, err) = run(false, Settings::default(), &["1/2 + 3/4", "", "x = 2", "ans * x"]);
+        assert!(success);
+        assert_eq!("5 / 4\n2\n4\n", out);
+        assert_eq!("", err);
+    }
+
+    #[test]
+    fn run_prints_decimal() {
+        let (success, out, _) = run(true, Settings::default(), &["1/3"]);
+        assert!(success);
+        assert_eq!("0.3333333333\n", out);
+    }
+
+    #[test]
+    fn run_marks_approximate_results() {
+        let (success, out, _) = run(false, Settings::default(), &["pi + 1", "π / 2", "sqrt(2) + 1"]);
+        assert!(success);
+        assert_eq!("≈ 4.1415926536\nπ / 2\n≈ 2.4142135624\n", out);
+    }
+
+    #[test]
+    fn run_reports_errors() {
+        let (success, out, err) = run(false, Settings::default(), &["1 / 0", "1 + 2"]);
+        assert!(!success);
+        assert_eq!("3\n", out);
+        assert_eq!("エラー: 0で割ることはできません (1 / 0)\n", err);
+    }
+
+    #[test]
+    fn run_prints_each_statement() {
+        let (success, out, err) = run(false, Settings::default(), &["a = 3; b = 4; a*a + b*b", "a; 1 / 0; b"]);
+        assert!(!success);
+        assert_eq!("3\n4\n25\n3\n", out);
+        assert_eq!("エラー: 2番目の文: 0で割ることはできません (1 / 0)\n", err);
+    }
+
+    #[test]
+    fn run_uses_angle_unit() {
+        let settings = Settings { angle_unit: AngleUnit::Degree, ..Settings::default() };
+        let (success, out, _) = run(false, settings, &["sin(30)", "acos(0)"]);
+        assert!(success);
+        assert_eq!("1 / 2\n90\n", out);
+    }
+
+    #[test]
+    fn run_uses_bit_width() {
+        let settings = Settings { bit_width: BitWidth::parse("u8").unwrap(), ..Settings::default() };
+        let (success, out, _) = run(false, settings, &["~0", "-1 in hex"]);
+        assert!(success);
+        assert_eq!("255\n0xFF\n", out);
+    }
+
+    #[test]
+    fn run_in_programmer_mode() {
+        let settings = Settings {
+            bit_width: BitWidth::parse("u8").unwrap(),
+            programmer: true,
+            ..Settings::default()
+        };
+        let (success, out, _) = run(false, settings, &["250 + 10", "7 / 2", "0 - 1 in hex"]);
+        assert!(success);
+        assert_eq!("4\n3\n0xFF\n", out);
+    }
+
+    #[test]
+    fn run_uses_notation() {
+        let settings = Settings { notation: Notation::Si, ..Settings::default() };
+        let (success, out, _) = run(false, settings, &["1 / 4700", "3.3k * 2"]);
+        assert!(success);
+        assert_eq!("≈ 212.7659574µ\n6.6k\n", out);
+    }
+
+    #[test]
+    fn run_groups_digits_for_locale() {
+        let settings = Settings { locale: Some(Locale::English), ..Settings::default() };
+        let (success, out, _) = run(false, settings, &["1_000 * 1_000", "0xFFFF"]);
+        assert!(success);
+        assert_eq!("1,000,000\n65,535\n", out);
+    }
+
+    #[test]
+    fn run_groups_decimal_for_locale() {
+        let settings = Settings { locale: Some(Locale::German), ..Settings::default() };
+        let (success, out, _) = run(true, settings, &["1234567 / 2", "1e6 / 3"]);
+        assert!(success);
+        assert_eq!("617.283,5\n333.333,3333333333\n", out);
+    }
+
+    #[test]
+    fn run_script_prints_annotated_script() {
+        let mut out = Vec::new();
+        let success = Batch::new(false, Settings::default())
+            .run_script_with("# 合計\nx = 2\nx * 3\ny\n", &mut out)
+            .unwrap();
+        assert!(!success);
+        assert_eq!(
+            "# 合計\nx = 2  # → 2\nx * 3  # → 6\ny  # エラー 4:1: 定義されていない変数です\n",
+            String::from_utf8(out).unwrap(),
+        );
+    }
+}
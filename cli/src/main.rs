@@ -0,0 +1,135 @@
+mod batch;
+mod repl;
+
+use std::io::{IsTerminal, Read};
+use std::process::ExitCode;
+use batch::Batch;
+use super_calculator_core::{AngleUnit, BitWidth, Locale, Notation, Settings};
+
+const USAGE: &str = "\
+使い方: super_calculator [オプション] [式...]
+
+式を指定すると、それぞれを計算して結果を1行ずつ出力します。
+近似値には ≈ を付けます。
+式を指定しない場合、端末からは対話モードで起動し、
+それ以外では標準入力の各行を計算します。
+
+オプション:
+  -d, --decimal          結果を小数で出力します
+  -p, --precision <桁数>  近似値を f64 ではなく指定した有効桁数で計算します
+  -a, --angle <単位>      三角関数の角度の単位を deg, rad, grad から選びます (既定は rad)
+  -w, --width <型>        ビット演算の型を i8, i16, i32, i64, u8, u16, u32, u64 から選びます
+                         (既定は i32)
+  -m, --machine <型>      プログラマーモードで計算します。整数を指定した型として扱い、
+                         桁あふれは折り返し、割り算は切り捨てます
+  -n, --notation <表記>   結果を std (通常), eng (指数が3の倍数), si (k や µ などの接頭辞),
+                         ja (3億2000万 のような万進), kanji (漢数字) のいずれかで出力します
+                         (既定は std)
+  -l, --locale <言語>     結果の桁を ja, en (1,234,567), de (1.234.567), fr (1 234 567),
+                         en-IN (12,34,567) などの言語の習慣に従って区切ります
+  -s, --script <ファイル>  .calc スクリプトを実行し、各行に結果を書き添えて出力します
+                         (- を指定すると標準入力から読み込みます)
+  -h, --help             この説明を表示します
+  --                     以降の引数はすべて式として扱います (-- -5+2 など)";
+
+fn read_script(path: &str) -> anyhow::Result<String> {
+    if path == "-" {
+        let mut text = String::new();
+        std::io::stdin().read_to_string(&mut text)?;
+        return Ok(text);
+    }
+    std::fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("{}を読み込めません: {}", path, e))
+}
+
+fn main() -> ExitCode {
+    let mut decimal = false;
+    let mut settings = Settings::default();
+    let mut script = None;
+    let mut expressions = Vec::new();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return ExitCode::SUCCESS;
+            },
+            "-d" | "--decimal" => decimal = true,
+            "-p" | "--precision" => match args.next().and_then(|digits| digits.parse().ok()) {
+                Some(digits) if (1..=10000).contains(&digits) => settings.precision = Some(digits),
+                _ => {
+                    eprintln!("--precision には1から10000までの桁数を指定してください");
+                    return ExitCode::from(2);
+                },
+            },
+            "-a" | "--angle" => match args.next().as_deref().and_then(AngleUnit::parse) {
+                Some(unit) => settings.angle_unit = unit,
+                None => {
+                    eprintln!("--angle には deg, rad, grad のいずれかを指定してください");
+                    return ExitCode::from(2);
+                },
+            },
+            "-w" | "--width" => match args.next().as_deref().and_then(BitWidth::parse) {
+                Some(width) => settings.bit_width = width,
+                None => {
+                    eprintln!("--width には i8, i16, i32, i64, u8, u16, u32, u64 のいずれかを指定してください");
+                    return ExitCode::from(2);
+                },
+            },
+            "-m" | "--machine" => match args.next().as_deref().and_then(BitWidth::parse) {
+                Some(width) => {
+                    settings.bit_width = width;
+                    settings.programmer = true;
+                },
+                None => {
+                    eprintln!("--machine には i8, i16, i32, i64, u8, u16, u32, u64 のいずれかを指定してください");
+                    return ExitCode::from(2);
+                },
+            },
+            "-n" | "--notation" => match args.next().as_deref().and_then(Notation::parse) {
+                Some(notation) => settings.notation = notation,
+                None => {
+                    eprintln!("--notation には std, eng, si, ja, kanji のいずれかを指定してください");
+                    return ExitCode::from(2);
+                },
+            },
+            "-l" | "--locale" => match args.next().as_deref().and_then(Locale::parse) {
+                Some(locale) => settings.locale = Some(locale),
+                None => {
+                    eprintln!("--locale には ja, en, de, fr, en-IN などの言語を指定してください");
+                    return ExitCode::from(2);
+                },
+            },
+            "-s" | "--script" => match args.next() {
+                Some(path) => script = Some(path),
+                None => {
+                    eprintln!("--script にはファイルを指定してください");
+                    return ExitCode::from(2);
+                },
+            },
+            "--" => {
+                expressions.extend(args.by_ref());
+                break;
+            },
+            _ => expressions.push(arg),
+        }
+    }
+
+    let result = if let Some(path) = script {
+        read_script(&path).and_then(|text| Batch::new(decimal, settings).run_script(&text))
+    } else if !expressions.is_empty() {
+        Batch::new(decimal, settings).run(expressions.into_iter().map(Ok))
+    } else if std::io::stdin().is_terminal() {
+        repl::run(settings)
+    } else {
+        Batch::new(decimal, settings).run(std::io::stdin().lines())
+    };
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(1),
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::from(2)
+        },
+    }
+}
@@ -0,0 +1,58 @@
+use std::path::PathBuf;
+use anyhow::Result;
+use rustyline::DefaultEditor;
+use rustyline::error::ReadlineError;
+use super_calculator_core::{Calculation, Environment, Settings};
+
+const PROMPT: &str = "> ";
+const HISTORY_FILE: &str = ".super_calculator_history";
+const HISTORY_FILE_VARIABLE: &str = "SUPER_CALCULATOR_HISTORY";
+const EXIT_COMMANDS: [&str; 2] = ["exit", "quit"];
+
+fn history_path() -> Option<PathBuf> {
+    std::env::var_os(HISTORY_FILE_VARIABLE)
+        .map(PathBuf::from)
+        .or_else(|| {
+            std::env::var_os("HOME")
+                .or_else(|| std::env::var_os("USERPROFILE"))
+                .map(|home| PathBuf::from(home).join(HISTORY_FILE))
+        })
+}
+
+pub fn run(settings: Settings) -> Result<bool> {
+    let mut editor = DefaultEditor::new()?;
+    let history_path = history_path();
+    if let Some(path) = &history_path {
+        // The history file does not exist on the first run.
+        let _ = editor.load_history(path);
+    }
+
+    let mut environment = Environment::default();
+    loop {
+        let line = match editor.readline(PROMPT) {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(e) => return Err(e.into()),
+        };
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        editor.add_history_entry(line)?;
+        if EXIT_COMMANDS.contains(&line) {
+            break;
+        }
+        for (_, result) in Calculation::calculate_statements(line, &mut environment, &settings) {
+            match result {
+                Ok(calculation) => println!("{}", calculation.to_grouped_string(settings.locale)),
+                Err(e) => eprintln!("エラー: {}", e),
+            }
+        }
+    }
+
+    if let Some(path) = &history_path {
+        editor.save_history(path)?;
+    }
+    Ok(true)
+}
@@ -0,0 +1,31 @@
+use std::process::{Command, Output};
+
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_super_calculator"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn double_dash_ends_options() {
+    let output = run(&["--", "-5+2", "-1 in hex"]);
+    assert!(output.status.success());
+    assert_eq!("-3\n0xFFFFFFFF\n", stdout(&output));
+
+    let output = run(&["-d", "--", "-1/4"]);
+    assert!(output.status.success());
+    assert_eq!("-0.25\n", stdout(&output));
+}
+
+#[test]
+fn options_after_double_dash_are_expressions() {
+    let output = run(&["--", "--help"]);
+    assert_eq!(Some(1), output.status.code());
+    assert_eq!("", stdout(&output));
+}
+
@@ -0,0 +1,48 @@
+use super::num::Num;
+use super::settings::BitWidth;
+use super::error::ErrorKind;
+
+// Operands are first wrapped to the bit width, so that 0xFF is -1 as i8.
+fn operand(num: &Num, width: BitWidth) -> Result<i128, ErrorKind> {
+    match num.to_bigint() {
+        Some(num) => Ok(width.wrap_integer(&num)),
+        None => Err(ErrorKind::NonIntegerBitwise),
+    }
+}
+
+fn result(num: i128, width: BitWidth) -> Result<Num, ErrorKind> {
+    Ok(Num::from_i128(width.wrap(num)))
+}
+
+pub fn and(x: &Num, y: &Num, width: BitWidth) -> Result<Num, ErrorKind> {
+    result(operand(x, width)? & operand(y, width)?, width)
+}
+
+pub fn or(x: &Num, y: &Num, width: BitWidth) -> Result<Num, ErrorKind> {
+    result(operand(x, width)? | operand(y, width)?, width)
+}
+
+pub fn xor(x: &Num, y: &Num, width: BitWidth) -> Result<Num, ErrorKind> {
+    result(operand(x, width)? ^ operand(y, width)?, width)
+}
+
+pub fn not(x: &Num, width: BitWidth) -> Result<Num, ErrorKind> {
+    result(!operand(x, width)?, width)
+}
+
+fn shift_amount(amount: &Num, width: BitWidth) -> Result<u32, ErrorKind> {
+    match amount.to_i128() {
+        Some(amount) if (0..width.bits as i128).contains(&amount) => Ok(amount as u32),
+        _ => Err(ErrorKind::InvalidShift),
+    }
+}
+
+pub fn shift_left(x: &Num, amount: &Num, width: BitWidth) -> Result<Num, ErrorKind> {
+    result(operand(x, width)? << shift_amount(amount, width)?, width)
+}
+
+// Arithmetic for signed types and logical for unsigned ones, since the
+// wrapped operand of an unsigned type is never negative.
+pub fn shift_right(x: &Num, amount: &Num, width: BitWidth) -> Result<Num, ErrorKind> {
+    result(operand(x, width)? >> shift_amount(amount, width)?, width)
+}
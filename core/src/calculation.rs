@@ -0,0 +1,1427 @@
+use num::BigInt;
+use super::expression::Expression;
+use super::term::Term;
+use super::num::Num;
+use super::fraction::Fraction;
+use super::environment::Environment;
+use super::settings::{Locale, Notation, Settings};
+use super::error::{Span, TokenError};
+use super::decimal::{self, Decimal};
+use super::radix::Radix;
+use super::machine;
+use super::notation;
+use super::{PRECISION, MAX_PRECISION};
+use super::error::ErrorKind;
+
+const STATEMENT_SEPARATOR: char = ';';
+// Extra digits carried through arbitrary-precision evaluation to absorb
+// rounding in intermediate steps.
+const GUARD_DIGITS: u32 = 20;
+// Zeros after the decimal point that are looked through at most for the
+// first significant digit of an approximation.
+const MAX_LEADING_ZEROS: u32 = 1000;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Calculation {
+    pub expression: String,
+    pub result: String,
+    pub decimal: String,
+    /// `result` in standard notation, which reads back as the same value.
+    pub value: String,
+    /// Whether `result` is rounded rather than exact.
+    pub approximate: bool,
+}
+
+impl Calculation {
+    /// `→` for exact results, `≈` for approximations.
+    pub fn relation(&self) -> &'static str {
+        if self.approximate { "≈" } else { "→" }
+    }
+
+    /// `result` for display, with its digits grouped as in `locale`.
+    pub fn grouped_result(&self, locale: Option<Locale>) -> String {
+        match locale {
+            Some(locale) => locale.group(&self.result),
+            None => self.result.clone(),
+        }
+    }
+
+    /// `decimal` with its digits grouped as in `locale`.
+    pub fn grouped_decimal(&self, locale: Option<Locale>) -> String {
+        match locale {
+            Some(locale) => locale.group(&self.decimal),
+            None => self.decimal.clone(),
+        }
+    }
+
+    /// Like `to_string`, with the digits of the result grouped.
+    pub fn to_grouped_string(&self, locale: Option<Locale>) -> String {
+        format!("{} {} {}", self.expression, self.relation(), self.grouped_result(locale))
+    }
+}
+
+impl std::fmt::Display for Calculation {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} {} {}", self.expression, self.relation(), self.result)
+    }
+}
+
+impl Calculation {
+    pub fn calculate(input: &str, environment: &mut Environment, settings: &Settings) -> Result<Self, String> {
+        Self::calculate_statement(input, environment, settings)
+            .map(|(calculation, _)| calculation)
+            .map_err(String::from)
+    }
+
+    /// Calculates `;`-separated statements in order and returns each
+    /// statement with its result. Calculation stops at the first failing
+    /// statement, whose error tells which statement it was.
+    pub fn calculate_statements(
+        input: &str,
+        environment: &mut Environment,
+        settings: &Settings,
+    ) -> Vec<(String, Result<Self, String>)> {
+        if !input.contains(STATEMENT_SEPARATOR) {
+            return vec![(input.to_string(), Self::calculate(input, environment, settings))];
+        }
+        let statements = Self::split_statements(input)
+            .into_iter()
+            .map(|(statement, _)| statement.trim());
+        let mut results = Vec::new();
+        for (index, statement) in statements.enumerate() {
+            let result = Self::calculate(statement, environment, settings)
+                .map_err(|e| format!("{}番目の文: {}", index + 1, e));
+            let failed = result.is_err();
+            results.push((statement.to_string(), result));
+            if failed {
+                break;
+            }
+        }
+        results
+    }
+
+    /// The non-empty `;`-separated statements of `input`, each with its
+    /// offset in UTF-16 units for shifting the spans of its errors.
+    pub(crate) fn split_statements(input: &str) -> Vec<(&str, usize)> {
+        let mut offset = 0;
+        let mut statements = Vec::new();
+        for statement in input.split(STATEMENT_SEPARATOR) {
+            if !statement.trim().is_empty() {
+                statements.push((statement, offset));
+            }
+            offset += statement.encode_utf16().count() + STATEMENT_SEPARATOR.len_utf16();
+        }
+        statements
+    }
+
+    pub(crate) fn calculate_statement(
+        input: &str,
+        environment: &mut Environment,
+        settings: &Settings,
+    ) -> Result<(Self, Num), ErrorKind> {
+        Self::calculate_statement_with_span(input, environment, settings)
+            .map_err(|(message, _)| message)
+    }
+
+    /// Like `calculate_statement`, but errors point at the part of `input`
+    /// that failed: the assignment target, the offending token, or the
+    /// whole expression when no single token raised the error.
+    pub(crate) fn calculate_statement_with_span(
+        input: &str,
+        environment: &mut Environment,
+        settings: &Settings,
+    ) -> Result<(Self, Num), (ErrorKind, Span)> {
+        let (name, start) = match input.find('=') {
+            Some(position) => (Some(Self::assignment_target(input, position)?), position + 1),
+            None => (None, 0),
+        };
+        let offset = input[..start].encode_utf16().count();
+        let expression_span = Span::from_byte_range(input, start, input.len());
+        let expression = Expression::parse_with_span(&input[start..])
+            .map_err(|(message, span)| (message, span.shift(offset)))?;
+        let (mut calculation, result) =
+            Self::calculate_expression(&expression, environment, settings)
+                .map_err(|error| {
+                    let span = error.span.map_or(expression_span, |span| span.shift(offset));
+                    (error.kind, span)
+                })?;
+        if let Some(name) = name {
+            environment.set(name, result.clone())
+                .map_err(|message| (message, expression_span))?;
+            calculation.expression = format!("{} = {}", name, calculation.expression);
+        }
+        Ok((calculation, result))
+    }
+
+    fn assignment_target(input: &str, position: usize) -> Result<&str, (ErrorKind, Span)> {
+        let name = input[..position].trim();
+        let span = Span::from_byte_range(input, 0, position);
+        if !Expression::is_identifier(name) {
+            return Err((ErrorKind::InvalidAssignmentTarget, span));
+        }
+        Environment::check_assignable(name)
+            .map_err(|message| (message, span))?;
+        Ok(name)
+    }
+
+    pub(crate) fn calculate_expression(
+        expression: &Expression,
+        environment: &mut Environment,
+        settings: &Settings,
+    ) -> Result<(Self, Num), TokenError> {
+        if let Some((argument, digits)) = expression.numeric_arguments()? {
+            return Self::calculate_numeric(expression, &argument, digits.as_ref(), environment, settings);
+        }
+        if let Some((argument, radix)) = expression.radix_conversion()? {
+            return Self::calculate_conversion(expression, &argument, radix, environment, settings);
+        }
+        let result = match (Self::calculate_num(expression, environment, settings), settings.precision) {
+            (Err(TokenError { kind: ErrorKind::RealOverflow, .. }), Some(digits)) => {
+                return Self::calculate_beyond_f64(expression, expression, environment, settings, digits);
+            },
+            (result, _) => result?,
+        };
+        if settings.notation != Notation::Standard {
+            return Self::calculate_notation(expression, result, environment, settings);
+        }
+        let decimal = match settings.precision {
+            Some(digits) if !result.is_exact() =>
+                Self::calculate_precise(expression, &result, environment, settings, digits)?,
+            _ => result.to_decimal(settings.decimal_digits)?,
+        };
+        // Approximations are shown at the configured precision.
+        let result_text = if result.is_exact() { result.to_string() } else { decimal.clone() };
+        let calculation = Self {
+            expression: expression.to_string(),
+            result: result_text.clone(),
+            value: result_text,
+            decimal,
+            approximate: !result.is_exact(),
+        };
+        environment.set_ans(result.clone());
+        Ok((calculation, result))
+    }
+
+    fn calculate_num(
+        expression: &Expression,
+        environment: &Environment,
+        settings: &Settings,
+    ) -> Result<Num, TokenError> {
+        let num = match expression.calculate_with(environment, settings)? {
+            Term::Num(num) => num,
+            _ => return Err(ErrorKind::SyntaxError.into()),
+        };
+        // Values that are never operated on, like a lone `200` as i8, wrap too.
+        match settings.programmer {
+            true => Ok(machine::wrap(num, settings.bit_width)?),
+            false => Ok(num),
+        }
+    }
+
+    // `N(x, digits)`: x to the given significant digits, or to the
+    // precision setting when omitted.
+    fn calculate_numeric(
+        expression: &Expression,
+        argument: &Expression,
+        digits: Option<&Expression>,
+        environment: &mut Environment,
+        settings: &Settings,
+    ) -> Result<(Self, Num), TokenError> {
+        let digits = match digits {
+            Some(digits) => match Self::calculate_num(digits, environment, settings)? {
+                Num::I32(digits) if (1..=MAX_PRECISION as i32).contains(&digits) => digits as u32,
+                _ => return Err(ErrorKind::InvalidPrecision.into()),
+            },
+            None => settings.precision.unwrap_or(PRECISION),
+        };
+        let result = match Self::calculate_num(argument, environment, settings) {
+            Err(TokenError { kind: ErrorKind::RealOverflow, .. }) => {
+                let settings = Settings { notation: Notation::Standard, ..settings.clone() };
+                return Self::calculate_beyond_f64(expression, argument, environment, &settings, digits);
+            },
+            result => result?,
+        };
+        let decimal = Self::calculate_precise(argument, &result, environment, settings, digits)?;
+        let calculation = Self {
+            expression: expression.to_string(),
+            result: decimal.clone(),
+            value: decimal.clone(),
+            decimal,
+            approximate: !decimal::is_exact_to(&result, digits),
+        };
+        environment.set_ans(result.clone());
+        Ok((calculation, result))
+    }
+
+    // Results in another notation, to the precision setting or to as many
+    // significant digits as decimals normally get. Integers are written in
+    // full in Japanese units, where every digit has a place.
+    fn calculate_notation(
+        expression: &Expression,
+        result: Num,
+        environment: &mut Environment,
+        settings: &Settings,
+    ) -> Result<(Self, Num), TokenError> {
+        let digits = settings.precision.unwrap_or(settings.decimal_digits);
+        let integer = match settings.notation {
+            Notation::Myriad | Notation::Kanji => result.to_bigint(),
+            _ => None,
+        };
+        let decimal = match &integer {
+            Some(integer) => integer.to_string(),
+            None => Self::calculate_precise(expression, &result, environment, settings, digits)?,
+        };
+        let calculation = Self {
+            expression: expression.to_string(),
+            result: notation::format(&decimal, settings.notation)?,
+            value: if result.is_exact() { result.to_string() } else { decimal.clone() },
+            decimal,
+            approximate: integer.is_none() && !decimal::is_exact_to(&result, digits),
+        };
+        environment.set_ans(result.clone());
+        Ok((calculation, result))
+    }
+
+    // `x in hex` or `to_hex(x)`: the integer x written in another base.
+    fn calculate_conversion(
+        expression: &Expression,
+        argument: &Expression,
+        radix: Radix,
+        environment: &mut Environment,
+        settings: &Settings,
+    ) -> Result<(Self, Num), TokenError> {
+        let result = Self::calculate_num(argument, environment, settings)?;
+        if !result.is_integer() {
+            return Err(ErrorKind::NonIntegerConversion.into());
+        }
+        let num = result.to_i128().ok_or(ErrorKind::WideOverflow)?;
+        let calculation = Self {
+            expression: expression.to_string(),
+            result: radix.format(num, settings.bit_width),
+            value: num.to_string(),
+            decimal: num.to_string(),
+            approximate: false,
+        };
+        environment.set_ans(result.clone());
+        Ok((calculation, result))
+    }
+
+    // Results beyond the range of f64, like `exp(1000)`, when a precision
+    // is given. The value evaluated with `Decimal` stands in for the result
+    // as `ans`.
+    fn calculate_beyond_f64(
+        expression: &Expression,
+        argument: &Expression,
+        environment: &mut Environment,
+        settings: &Settings,
+        digits: u32,
+    ) -> Result<(Self, Num), TokenError> {
+        let value = Self::calculate_decimal(argument, environment, settings, digits)?;
+        let decimal = value.to_significant(digits);
+        let calculation = Self {
+            expression: expression.to_string(),
+            result: notation::format(&decimal, settings.notation)?,
+            value: decimal.clone(),
+            decimal,
+            approximate: true,
+        };
+        let result = Fraction::new_result(value.to_rational());
+        environment.set_ans(result.clone());
+        Ok((calculation, result))
+    }
+
+    // Exact rationals are converted directly, with more digits after the
+    // decimal point for small values like `1e-400` so that all `digits` are
+    // significant. Everything else is calculated again from the expression.
+    fn calculate_precise(
+        expression: &Expression,
+        result: &Num,
+        environment: &Environment,
+        settings: &Settings,
+        digits: u32,
+    ) -> Result<String, TokenError> {
+        let value = match result.to_rational() {
+            Some(value) => {
+                let length = |num: &BigInt| num.magnitude().to_string().len() as u32;
+                let zeros = length(value.denom()).saturating_sub(length(value.numer()));
+                Decimal::from_num(result, digits + GUARD_DIGITS + zeros)?
+            },
+            None => Self::calculate_decimal(expression, environment, settings, digits)?,
+        };
+        Ok(value.to_significant(digits))
+    }
+
+    // Approximations are evaluated with `Decimal` alone, never through f64,
+    // so that values beyond its range like `exp(-1000)` keep all `digits`.
+    // Digits after the decimal point are added while leading zeros leave
+    // too few significant ones; values with more than `MAX_LEADING_ZEROS`
+    // are zero.
+    fn calculate_decimal(
+        expression: &Expression,
+        environment: &Environment,
+        settings: &Settings,
+        digits: u32,
+    ) -> Result<Decimal, TokenError> {
+        let max_scale = digits + GUARD_DIGITS + MAX_LEADING_ZEROS;
+        let mut scale = digits + GUARD_DIGITS;
+        loop {
+            let value = expression.calculate_precise(environment, settings, scale)?;
+            let needed = digits + GUARD_DIGITS + value.leading_zeros().unwrap_or(scale);
+            if needed <= scale || scale == max_scale {
+                return Ok(value);
+            }
+            scale = needed.min(max_scale);
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn from_input(input: &str) -> Result<Self, String> {
+        Self::calculate(input, &mut Environment::default(), &Settings::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::{AngleUnit, BitWidth};
+
+    fn calculate_and_format(input: &str) -> Result<String, String> {
+        Calculation::from_input(input).map(|calculation| calculation.to_string())
+    }
+
+    fn calculate_with(settings: &Settings, input: &str) -> Result<String, String> {
+        Calculation::calculate(input, &mut Environment::default(), settings)
+            .map(|calculation| calculation.to_string())
+    }
+
+    fn machine_type(width: &str, programmer: bool) -> Settings {
+        Settings { bit_width: BitWidth::parse(width).unwrap(), programmer, ..Settings::default() }
+    }
+
+    #[test]
+    fn parse_normal() {
+        let input = "1 + 2";
+        let expected = "1 + 2 → 3";
+        let actual = calculate_and_format(input).unwrap();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn calculate_keeps_result_separately() {
+        let input = "1/2 + 3/4";
+        let expected = Calculation {
+            expression: "1 / 2 + 3 / 4".to_string(),
+            result: "5 / 4".to_string(),
+            decimal: "1.25".to_string(),
+            value: "5 / 4".to_string(),
+            approximate: false,
+        };
+        let actual = Calculation::from_input(input).unwrap();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn calculate_decimal_rounded() {
+        let actual = Calculation::from_input("-2/3").unwrap();
+        assert_eq!("-0.6666666667", actual.decimal);
+    }
+
+    #[test]
+    fn calculate_with_ans() {
+        let mut environment = Environment::default();
+        let settings = Settings::default();
+        Calculation::calculate("1/2", &mut environment, &settings).unwrap();
+        let actual = Calculation::calculate("ans * 4 + 1", &mut environment, &settings).unwrap();
+        assert_eq!("ans * 4 + 1 → 3", actual.to_string());
+    }
+
+    #[test]
+    fn calculate_assignment() {
+        let mut environment = Environment::default();
+        let settings = Settings::default();
+        let actual = Calculation::calculate("x = 1/2", &mut environment, &settings).unwrap();
+        assert_eq!("x = 1 / 2 → 1 / 2", actual.to_string());
+        let actual = Calculation::calculate("4x + ans", &mut environment, &settings).unwrap();
+        assert_eq!("4 * x + ans → 5 / 2", actual.to_string());
+    }
+
+    fn calculate_all(input: &str) -> Vec<(String, Result<String, String>)> {
+        Calculation::calculate_statements(input, &mut Environment::default(), &Settings::default())
+            .into_iter()
+            .map(|(statement, result)| (statement, result.map(|calculation| calculation.to_string())))
+            .collect()
+    }
+
+    #[test]
+    fn calculate_statements_in_order() {
+        let expected = vec![
+            ("a = 3".to_string(), Ok("a = 3 → 3".to_string())),
+            ("b = 4".to_string(), Ok("b = 4 → 4".to_string())),
+            ("a*a + b*b".to_string(), Ok("a * a + b * b → 25".to_string())),
+        ];
+        assert_eq!(expected, calculate_all("a = 3; b = 4; a*a + b*b;"));
+    }
+
+    #[test]
+    fn calculate_statements_stops_at_error() {
+        let expected = vec![
+            ("a = 3".to_string(), Ok("a = 3 → 3".to_string())),
+            ("a / 0".to_string(), Err("2番目の文: 0で割ることはできません".to_string())),
+        ];
+        assert_eq!(expected, calculate_all("a = 3; a / 0; a + 1"));
+    }
+
+    #[test]
+    fn calculate_single_statement_keeps_input() {
+        let expected = vec![(" 1 / 0".to_string(), Err("0で割ることはできません".to_string()))];
+        assert_eq!(expected, calculate_all(" 1 / 0"));
+    }
+
+    #[test]
+    fn calculate_constants_stay_symbolic() {
+        assert_eq!("2 * π → 2π", calculate_and_format("2π").unwrap());
+        assert_eq!("pi / 2 → π / 2", calculate_and_format("pi / 2").unwrap());
+        assert_eq!("3 * π / 4 - π → -π / 4", calculate_and_format("3π/4 - π").unwrap());
+        assert_eq!("2 * e + e → 3e", calculate_and_format("2e + e").unwrap());
+        assert_eq!("π / ( 2 * π ) → 1 / 2", calculate_and_format("π / (2π)").unwrap());
+        assert_eq!("π - π → 0", calculate_and_format("π - π").unwrap());
+    }
+
+    #[test]
+    fn calculate_constants_approximated_when_mixed() {
+        let actual = Calculation::from_input("π + 1").unwrap();
+        assert_eq!("4.1415926536", actual.result);
+        assert_eq!("4.1415926536", actual.decimal);
+        assert_eq!("π + 1 ≈ 4.1415926536", actual.to_string());
+        assert_eq!("1.5707963268", Calculation::from_input("π / 2").unwrap().decimal);
+        assert_eq!("7.3890560989", Calculation::from_input("e * e").unwrap().result);
+        let settings = Settings { decimal_digits: 3, ..Settings::default() };
+        let actual = Calculation::calculate("π * e", &mut Environment::default(), &settings).unwrap();
+        assert_eq!("8.54", actual.result);
+    }
+
+    #[test]
+    fn calculate_approximation_propagates() {
+        let actual = Calculation::from_input("(π + 1) * 0 + 2").unwrap();
+        assert!(actual.approximate);
+        assert_eq!("( π + 1 ) * 0 + 2 ≈ 2", actual.to_string());
+        assert!(!Calculation::from_input("2π * 3").unwrap().approximate);
+    }
+
+    #[test]
+    fn calculate_exact_square_roots() {
+        assert_eq!("√ ( 9 ) → 3", calculate_and_format("sqrt(9)").unwrap());
+        assert_eq!("√ ( 9 / 4 ) → 3 / 2", calculate_and_format("sqrt(9/4)").unwrap());
+        assert_eq!("√ 0 → 0", calculate_and_format("√0").unwrap());
+        assert_eq!("√ 8 → 2√2", calculate_and_format("√8").unwrap());
+        assert_eq!("√ ( 1 / 8 ) → √2 / 4", calculate_and_format("√(1/8)").unwrap());
+        assert_eq!("√ ( 12 ) / √ ( 3 ) → 2", calculate_and_format("sqrt(12)/sqrt(3)").unwrap());
+    }
+
+    #[test]
+    fn calculate_square_roots_beyond_int32() {
+        assert_eq!("√ ( 4294967296 ) → 65536", calculate_and_format("sqrt(4294967296)").unwrap());
+        assert_eq!("√ ( 1e20 ) → 10000000000", calculate_and_format("sqrt(1e20)").unwrap());
+        assert_eq!("√ ( 1e-30 ) → 1 / 1000000000000000", calculate_and_format("sqrt(1e-30)").unwrap());
+        assert_eq!("√ ( 3000000000 ) → 10000√30", calculate_and_format("sqrt(3000000000)").unwrap());
+        assert_eq!("√ ( 1 / 4294967296 ) → 1 / 65536", calculate_and_format("sqrt(1/4294967296)").unwrap());
+        assert_eq!("√ ( 4294967297 / 4 ) → √4294967297 / 2", calculate_and_format("sqrt(4294967297/4)").unwrap());
+    }
+
+    #[test]
+    fn calculate_radical_arithmetic() {
+        assert_eq!("2 * √ 2 → 2√2", calculate_and_format("2√2").unwrap());
+        assert_eq!("√ 2 + √ 8 → 3√2", calculate_and_format("√2 + √8").unwrap());
+        assert_eq!("√ 2 - √ 2 → 0", calculate_and_format("√2 - √2").unwrap());
+        assert_eq!("√ 6 * √ 10 → 2√15", calculate_and_format("√6 * √10").unwrap());
+        assert_eq!("1 / √ 2 → √2 / 2", calculate_and_format("1 / √2").unwrap());
+        assert_eq!("√ 6 / √ 2 → √3", calculate_and_format("√6 / √2").unwrap());
+        assert_eq!("- √ 3 * 2 → -2√3", calculate_and_format("-√3 * 2").unwrap());
+        assert_eq!("√ 2 + 0 → √2", calculate_and_format("√2 + 0").unwrap());
+        let actual = Calculation::from_input("√2 + 1").unwrap();
+        assert_eq!("√ 2 + 1 ≈ 2.4142135624", actual.to_string());
+        assert_eq!("1.7724538509", Calculation::from_input("√π").unwrap().result);
+    }
+
+    #[test]
+    fn calculate_sqrt_errors() {
+        for (input, expected) in [
+            ("sqrt(-4)", "負の数の平方根は計算できません"),
+            ("sqrt 4", "関数の後に括弧がありません"),
+            ("2 + sqrt", "関数の後に括弧がありません"),
+            ("sqrt = 2", "関数名には代入できません"),
+        ] {
+            match calculate_and_format(input) {
+                Ok(_) => panic!("should be error: {}", input),
+                Err(e) => assert_eq!(expected, e),
+            }
+        }
+    }
+
+    #[test]
+    fn calculate_trigonometric_special_angles() {
+        let degree = Settings { angle_unit: AngleUnit::Degree, ..Settings::default() };
+        let gradian = Settings { angle_unit: AngleUnit::Gradian, ..Settings::default() };
+        assert_eq!("sin ( 30° ) → 1 / 2", calculate_and_format("sin(30°)").unwrap());
+        assert_eq!("cos ( 45° ) → √2 / 2", calculate_and_format("cos(45°)").unwrap());
+        assert_eq!("tan ( 60° ) → √3", calculate_and_format("tan(60°)").unwrap());
+        assert_eq!("sin ( π / 6 ) → 1 / 2", calculate_and_format("sin(π/6)").unwrap());
+        assert_eq!("cos ( π ) → -1", calculate_and_format("cos(π)").unwrap());
+        assert_eq!("sin ( 0 ) → 0", calculate_and_format("sin(0)").unwrap());
+        assert_eq!("tan ( - π / 6 ) → -√3 / 3", calculate_and_format("tan(-π/6)").unwrap());
+        assert_eq!("sin ( 210 ) → -1 / 2", calculate_with(&degree, "sin(210)").unwrap());
+        assert_eq!("cos ( - 300 ) → 1 / 2", calculate_with(&degree, "cos(-300)").unwrap());
+        assert_eq!("sin ( 50 ) → √2 / 2", calculate_with(&gradian, "sin(50)").unwrap());
+        assert_eq!("2 * sin ( 30° ) → 1", calculate_with(&gradian, "2sin(30°)").unwrap());
+    }
+
+    #[test]
+    fn calculate_trigonometric_approximations() {
+        let degree = Settings { angle_unit: AngleUnit::Degree, ..Settings::default() };
+        assert_eq!("sin ( 1 ) ≈ 0.8414709848", calculate_and_format("sin(1)").unwrap());
+        assert_eq!("cos ( 10 ) ≈ 0.984807753", calculate_with(&degree, "cos(10)").unwrap());
+        assert_eq!("sin ( π + 1 ) ≈ -0.8414709848", calculate_and_format("sin(π + 1)").unwrap());
+    }
+
+    #[test]
+    fn calculate_inverse_trigonometric() {
+        let degree = Settings { angle_unit: AngleUnit::Degree, ..Settings::default() };
+        let gradian = Settings { angle_unit: AngleUnit::Gradian, ..Settings::default() };
+        assert_eq!("asin ( 1 / 2 ) → π / 6", calculate_and_format("asin(1/2)").unwrap());
+        assert_eq!("acos ( - 1 ) → π", calculate_and_format("acos(-1)").unwrap());
+        assert_eq!("atan ( 1 ) → 45", calculate_with(&degree, "atan(1)").unwrap());
+        assert_eq!("acos ( √ 2 / 2 ) → 45", calculate_with(&degree, "acos(√2/2)").unwrap());
+        assert_eq!("asin ( - √ 3 / 2 ) → -60", calculate_with(&degree, "asin(-√3/2)").unwrap());
+        assert_eq!("atan ( 1 / √ 3 ) → 100 / 3", calculate_with(&gradian, "atan(1/√3)").unwrap());
+        assert_eq!("asin ( 0 ) → 0", calculate_and_format("asin(0)").unwrap());
+        assert_eq!("atan ( 2 ) ≈ 63.4349488229", calculate_with(&degree, "atan(2)").unwrap());
+    }
+
+    #[test]
+    fn calculate_degree_literal_in_each_unit() {
+        let degree = Settings { angle_unit: AngleUnit::Degree, ..Settings::default() };
+        let gradian = Settings { angle_unit: AngleUnit::Gradian, ..Settings::default() };
+        assert_eq!("90° → π / 2", calculate_and_format("90°").unwrap());
+        assert_eq!("90° → 90", calculate_with(&degree, "90°").unwrap());
+        assert_eq!("90° → 100", calculate_with(&gradian, "90°").unwrap());
+    }
+
+    #[test]
+    fn calculate_trigonometric_errors() {
+        for (input, expected) in [
+            ("tan(90°)", "tanが定義されない角度です"),
+            ("asin(2)", "-1から1の範囲外の値です"),
+            ("acos(-3/2)", "-1から1の範囲外の値です"),
+            ("sin 30", "関数の後に括弧がありません"),
+            ("x°", "°は数値の直後に付けてください"),
+            ("sin = 1", "関数名には代入できません"),
+        ] {
+            match calculate_and_format(input) {
+                Ok(_) => panic!("should be error: {}", input),
+                Err(e) => assert_eq!(expected, e),
+            }
+        }
+    }
+
+    #[test]
+    fn calculate_exact_logarithms() {
+        assert_eq!("log2 ( 1024 ) → 10", calculate_and_format("log2(1024)").unwrap());
+        assert_eq!("log10 ( 1 / 1000 ) → -3", calculate_and_format("log10(1/1000)").unwrap());
+        assert_eq!("log ( 8 , 2 ) → 1 / 3", calculate_and_format("log(8, 2)").unwrap());
+        assert_eq!("log ( 4 , 8 ) → 3 / 2", calculate_and_format("log(4, 8)").unwrap());
+        assert_eq!("log ( 1 / 2 , 16 ) → -4", calculate_and_format("log(1/2, 16)").unwrap());
+        assert_eq!("log ( 9 / 4 , 8 / 27 ) → -3 / 2", calculate_and_format("log(9/4, 8/27)").unwrap());
+        assert_eq!("ln ( 1 ) → 0", calculate_and_format("ln(1)").unwrap());
+        assert_eq!("ln ( e ) → 1", calculate_and_format("ln(e)").unwrap());
+        assert_eq!("exp ( 0 ) → 1", calculate_and_format("exp(0)").unwrap());
+        assert_eq!("exp ( 1 ) → e", calculate_and_format("exp(1)").unwrap());
+        assert_eq!("2 * log2 ( 8 ) + 1 → 7", calculate_and_format("2log2(8) + 1").unwrap());
+    }
+
+    #[test]
+    fn calculate_exact_logarithms_beyond_int32() {
+        for (input, expected) in [
+            ("log2(4294967296)", "log2 ( 4294967296 ) → 32"),
+            ("log2(1099511627776)", "log2 ( 1099511627776 ) → 40"),
+            ("log(4, 4294967296)", "log ( 4 , 4294967296 ) → 16"),
+            ("log10(1e30)", "log10 ( 1e30 ) → 30"),
+            ("log10(1e-400)", "log10 ( 1e-400 ) → -400"),
+            ("log(1e6, 1e-9)", "log ( 1e6 , 1e-9 ) → -3 / 2"),
+            ("log2(1/4294967296)", "log2 ( 1 / 4294967296 ) → -32"),
+            ("log(2147483648, 4294967296)", "log ( 2147483648 , 4294967296 ) → 32 / 31"),
+        ] {
+            assert_eq!(expected, calculate_and_format(input).unwrap(), "{}", input);
+        }
+        assert!(Calculation::from_input("log2(4294967297)").unwrap().approximate);
+    }
+
+    #[test]
+    fn calculate_approximate_logarithms() {
+        assert_eq!("log2 ( 10 ) ≈ 3.3219280949", calculate_and_format("log2(10)").unwrap());
+        assert_eq!("ln ( 2 ) ≈ 0.6931471806", calculate_and_format("ln(2)").unwrap());
+        assert_eq!("log ( 3 , 10 ) ≈ 2.0959032743", calculate_and_format("log(3, 10)").unwrap());
+        assert_eq!("exp ( 2 ) ≈ 7.3890560989", calculate_and_format("exp(2)").unwrap());
+        assert_eq!("log10 ( π ) ≈ 0.4971498727", calculate_and_format("log10(π)").unwrap());
+    }
+
+    #[test]
+    fn calculate_logarithm_errors() {
+        for (input, expected) in [
+            ("ln(0)", "真数は正の数でなければなりません"),
+            ("log10(-10)", "真数は正の数でなければなりません"),
+            ("log(1, 5)", "底は1以外の正の数でなければなりません"),
+            ("log(-2, 4)", "底は1以外の正の数でなければなりません"),
+            ("log(100)", "関数の引数の数が正しくありません"),
+            ("ln(2, 3)", "関数の引数の数が正しくありません"),
+            ("1, 2", "カンマは関数の引数の区切りにだけ使えます"),
+            ("log((2, 8))", "カンマは関数の引数の区切りにだけ使えます"),
+            ("exp(1000)", "計算結果が実数の範囲を超えました"),
+        ] {
+            match calculate_and_format(input) {
+                Ok(_) => panic!("should be error: {}", input),
+                Err(e) => assert_eq!(expected, e),
+            }
+        }
+    }
+
+    const PI_100: &str = "3.1415926535897932384626433832795028841971693993751\
+        05820974944592307816406286208998628034825342117068";
+    const SQRT_2_200: &str = "1.4142135623730950488016887242096980785696718753769\
+        480731766797379907324784621070388503875343276415727350138462309122970249248\
+        360558507372126441214970999358314132226659275055927557999505011527820605715";
+
+    #[test]
+    fn calculate_numeric_to_many_digits() {
+        let actual = Calculation::from_input("N(π, 100)").unwrap();
+        assert_eq!(PI_100, actual.result);
+        assert_eq!(format!("N ( π , 100 ) ≈ {}", PI_100), actual.to_string());
+        assert_eq!(SQRT_2_200, Calculation::from_input("N(sqrt(2), 200)").unwrap().result);
+        assert_eq!(
+            "0.8414709848078965066525023216302989996226",
+            Calculation::from_input("N(sin(1), 40)").unwrap().result,
+        );
+        assert_eq!(
+            "0.00000000000000000000000000000000000000000003720075976020835963",
+            Calculation::from_input("N(exp(-100), 20)").unwrap().decimal,
+        );
+    }
+
+    #[test]
+    fn calculate_numeric_beyond_f64() {
+        let actual = Calculation::from_input("N(exp(1000), 20)").unwrap();
+        assert_eq!(format!("19700711140170469939{}", "0".repeat(415)), actual.result);
+        assert!(actual.approximate);
+        assert_eq!(
+            format!("0.{}50759588975494567653", "0".repeat(434)),
+            Calculation::from_input("N(exp(-1000), 20)").unwrap().result,
+        );
+        let actual = Calculation::from_input("N(exp(1000), 100)").unwrap();
+        assert!(actual.result.starts_with("19700711140170469938888793522433231253169379853238"));
+        assert_eq!(435, actual.result.len());
+        let settings = Settings { precision: Some(10), ..Settings::default() };
+        let mut environment = Environment::default();
+        let actual = Calculation::calculate("exp(1000)", &mut environment, &settings).unwrap();
+        assert_eq!(format!("exp ( 1000 ) ≈ 1970071114{}", "0".repeat(425)), actual.to_string());
+        let actual = Calculation::calculate("ans / exp(999)", &mut environment, &settings).unwrap();
+        assert_eq!("2.718281828", actual.result);
+    }
+
+    #[test]
+    fn calculate_numeric_exact_results() {
+        assert_eq!("N ( 1 / 4 ) → 0.25", calculate_and_format("N(1/4)").unwrap());
+        assert_eq!("N ( 2 / 3 , 3 ) ≈ 0.667", calculate_and_format("N(2/3, 3)").unwrap());
+        assert_eq!("N ( 123456 , 3 ) ≈ 123000", calculate_and_format("N(123456, 3)").unwrap());
+        assert_eq!("N ( 0 ) → 0", calculate_and_format("N(0)").unwrap());
+        assert_eq!("N ( 6.02e23 , 30 ) → 602000000000000000000000", calculate_and_format("N(6.02e23, 30)").unwrap());
+        assert_eq!("N ( cos ( 60° ) , 5 ) → 0.5", calculate_and_format("N(cos(60°), 5)").unwrap());
+        let actual = Calculation::from_input("N(√2 / 2, 20)").unwrap();
+        assert_eq!("0.7071067811865475244", actual.result);
+    }
+
+    #[test]
+    fn calculate_numeric_sets_ans() {
+        let mut environment = Environment::default();
+        let settings = Settings::default();
+        Calculation::calculate("N(1/3, 5)", &mut environment, &settings).unwrap();
+        let actual = Calculation::calculate("ans * 3", &mut environment, &settings).unwrap();
+        assert_eq!("ans * 3 → 1", actual.to_string());
+    }
+
+    #[test]
+    fn calculate_with_precision_setting() {
+        let settings = Settings { precision: Some(30), ..Settings::default() };
+        let calculate = |input| Calculation::calculate(input, &mut Environment::default(), &settings).unwrap();
+        assert_eq!("π + 1 ≈ 4.14159265358979323846264338328", calculate("π + 1").to_string());
+        assert_eq!("2.71828182845904523536028747135", calculate("N(e)").result);
+        assert_eq!("1 / 3", calculate("1 / 3").result);
+        assert_eq!("0.3333333333", calculate("1 / 3").decimal);
+        let settings = Settings { precision: Some(20), angle_unit: AngleUnit::Degree, ..settings };
+        let actual = Calculation::calculate("sin(10)", &mut Environment::default(), &settings).unwrap();
+        assert_eq!("0.17364817766693034885", actual.result);
+    }
+
+    #[test]
+    fn calculate_numeric_keeps_literals_exact() {
+        assert_eq!(
+            "3.14159265368979323846264338328",
+            Calculation::from_input("N(1e-10 + π, 30)").unwrap().result,
+        );
+        assert_eq!(
+            "0.000000000000000000000000000001",
+            Calculation::from_input("N(sin(1e-30), 30)").unwrap().result,
+        );
+        let actual = Calculation::from_input("N(3e-400, 5)").unwrap();
+        assert_eq!(format!("0.{}3", "0".repeat(399)), actual.result);
+        assert!(!actual.approximate);
+    }
+
+    #[test]
+    fn calculate_numeric_errors() {
+        for (input, expected) in [
+            ("N(π, 0)", "桁数は1から10000までの整数で指定してください"),
+            ("N(π, 1/2)", "桁数は1から10000までの整数で指定してください"),
+            ("N(π, 10001)", "桁数は1から10000までの整数で指定してください"),
+            ("1 + N(2)", "Nは式全体にだけ使えます"),
+            ("N(1, 2, 3)", "関数の引数の数が正しくありません"),
+            ("N(1 / 0)", "0で割ることはできません"),
+        ] {
+            match calculate_and_format(input) {
+                Ok(_) => panic!("should be error: {}", input),
+                Err(e) => assert_eq!(expected, e),
+            }
+        }
+    }
+
+    #[test]
+    fn calculate_radix_literals() {
+        assert_eq!("31 → 31", calculate_and_format("0x1F").unwrap());
+        assert_eq!("10 + 15 → 25", calculate_and_format("0b1010 + 0o17").unwrap());
+        assert_eq!("255 → 255", calculate_and_format("0XfF").unwrap());
+        assert_eq!("10 * 10 → 100", calculate_and_format("0xA*0xa").unwrap());
+    }
+
+    #[test]
+    fn calculate_radix_conversions() {
+        assert_eq!("255 in hex → 0xFF", calculate_and_format("255 in hex").unwrap());
+        assert_eq!("to_bin ( 10 ) → 0b1010", calculate_and_format("to_bin(10)").unwrap());
+        assert_eq!("8 * 8 in oct → 0o100", calculate_and_format("8 * 8 in oct").unwrap());
+        assert_eq!("31 in dec → 31", calculate_and_format("0x1F in dec").unwrap());
+        assert_eq!("- 255 in hex → 0xFFFFFF01", calculate_and_format("-255 in hex").unwrap());
+        let actual = Calculation::from_input("to_hex(4096)").unwrap();
+        assert_eq!("0x1000", actual.result);
+        assert_eq!("4096", actual.decimal);
+    }
+
+    #[test]
+    fn calculate_radix_conversion_sets_ans() {
+        let mut environment = Environment::default();
+        let settings = Settings::default();
+        Calculation::calculate("255 in bin", &mut environment, &settings).unwrap();
+        assert_eq!(Some(255), environment.ans_integer());
+        let actual = Calculation::calculate("ans + 1", &mut environment, &settings).unwrap();
+        assert_eq!("ans + 1 → 256", actual.to_string());
+    }
+
+    #[test]
+    fn calculate_radix_errors() {
+        for (input, expected) in [
+            ("0x", "数値に変換できません"),
+            ("0b102", "数値に変換できません"),
+            ("0o8", "数値に変換できません"),
+            ("1 / 2 in hex", "整数以外は基数変換できません"),
+            ("1e40 in hex", "128ビットの範囲を超える整数です"),
+            ("to_hex(π)", "整数以外は基数変換できません"),
+            ("255 in base", "変換先はhex, dec, oct, binのいずれかです"),
+            ("255 in 16", "変換先はhex, dec, oct, binのいずれかです"),
+            ("(255 in hex) + 1", "基数変換は式全体にだけ使えます"),
+            ("1 + to_bin(2)", "基数変換は式全体にだけ使えます"),
+            ("in hex", "演算子の前に値がありません"),
+            ("in = 1", "予約語には代入できません"),
+        ] {
+            match calculate_and_format(input) {
+                Ok(_) => panic!("should be error: {}", input),
+                Err(e) => assert_eq!(expected, e, "{}", input),
+            }
+        }
+    }
+
+    #[test]
+    fn calculate_bitwise_operators() {
+        assert_eq!("12 & 10 → 8", calculate_and_format("12 & 10").unwrap());
+        assert_eq!("12 | 10 → 14", calculate_and_format("12 | 10").unwrap());
+        assert_eq!("12 xor 10 → 6", calculate_and_format("12 xor 10").unwrap());
+        assert_eq!("~ 0 → -1", calculate_and_format("~0").unwrap());
+        assert_eq!("1 << 4 → 16", calculate_and_format("1<<4").unwrap());
+        assert_eq!("- 16 >> 2 → -4", calculate_and_format("-16 >> 2").unwrap());
+        assert_eq!("~ ( 5 & 3 ) → -2", calculate_and_format("~(5 & 3)").unwrap());
+    }
+
+    #[test]
+    fn calculate_bitwise_precedence() {
+        assert_eq!("1 | 6 & 3 → 3", calculate_and_format("1 | 6 & 3").unwrap());
+        assert_eq!("1 | 2 xor 3 → 1", calculate_and_format("1 | 2 xor 3").unwrap());
+        assert_eq!("1 << 2 + 1 → 8", calculate_and_format("1 << 2 + 1").unwrap());
+        assert_eq!("6 & 3 << 1 → 6", calculate_and_format("6 & 3 << 1").unwrap());
+        assert_eq!("~ 1 * 2 → -4", calculate_and_format("~1 * 2").unwrap());
+    }
+
+    #[test]
+    fn calculate_bitwise_in_each_width() {
+        assert_eq!("~ 0 → 255", calculate_with(&machine_type("u8", false), "~0").unwrap());
+        assert_eq!("1 << 7 → -128", calculate_with(&machine_type("i8", false), "1 << 7").unwrap());
+        assert_eq!("255 & 255 → -1", calculate_with(&machine_type("i8", false), "0xFF & 0xFF").unwrap());
+        assert_eq!("65535 >> 4 → 4095", calculate_with(&machine_type("u16", false), "0xFFFF >> 4").unwrap());
+        assert_eq!("- 1 >> 28 → 15", calculate_with(&machine_type("u32", false), "-1 >> 28").unwrap());
+        assert_eq!("~ 0 → 4294967295", calculate_with(&machine_type("u32", false), "~0").unwrap());
+        assert_eq!("~ 0 → 18446744073709551615", calculate_with(&machine_type("u64", false), "~0").unwrap());
+        assert_eq!("1 << 63 → -9223372036854775808", calculate_with(&machine_type("i64", false), "1 << 63").unwrap());
+        assert_eq!("- 1 in hex → 0xFF", calculate_with(&machine_type("i8", false), "-1 in hex").unwrap());
+        assert_eq!("- 1 in bin → 0b1111111111111111", calculate_with(&machine_type("i16", false), "-1 in bin").unwrap());
+        assert_eq!("- 300 in hex → -0x12C", calculate_with(&machine_type("i8", false), "-300 in hex").unwrap());
+    }
+
+    #[test]
+    fn calculate_wide_integers() {
+        assert_eq!("3735928559 → 3735928559", calculate_and_format("0xDEADBEEF").unwrap());
+        assert_eq!("3735928559 + 1 → 3735928560", calculate_and_format("0xDEADBEEF + 1").unwrap());
+        assert_eq!("4294967296 / 2 → 2147483648", calculate_and_format("0x100000000 / 2").unwrap());
+        assert_eq!("4294967296 / 4 → 1073741824", calculate_and_format("0x100000000 / 4").unwrap());
+        assert_eq!("3735928559 in hex → 0xDEADBEEF", calculate_and_format("0xDEADBEEF in hex").unwrap());
+        assert_eq!("3000000000 + 1 → 3000000001", calculate_and_format("3000000000 + 1").unwrap());
+    }
+
+    #[test]
+    fn calculate_wide_fractions_exactly() {
+        let actual = Calculation::from_input("0x100000001 / 2").unwrap();
+        assert_eq!("4294967297 / 2 → 4294967297 / 2", actual.to_string());
+        assert_eq!("2147483648.5", actual.decimal);
+        assert_eq!(
+            "4294967295 + 1 / 2 → 8589934591 / 2",
+            calculate_and_format("0xFFFFFFFF + 1/2").unwrap(),
+        );
+        assert_eq!("3000000000 / 7 * 7 → 3000000000", calculate_and_format("3000000000 / 7 * 7").unwrap());
+        assert_eq!(
+            "18446744073709551615 / 7 + 1 / 3 → 55340232221128654852 / 21",
+            calculate_and_format("0xFFFFFFFFFFFFFFFF / 7 + 1 / 3").unwrap(),
+        );
+        assert_eq!(
+            "18446744073709551615 * 2 → 36893488147419103230",
+            calculate_and_format("0xFFFFFFFFFFFFFFFF * 2").unwrap(),
+        );
+    }
+
+    #[test]
+    fn calculate_bitwise_errors() {
+        for (input, expected) in [
+            ("1 / 2 & 1", "整数以外はビット演算できません"),
+            ("~π", "整数以外はビット演算できません"),
+            ("1 << 32", "シフト量はビット幅未満の0以上の整数で指定してください"),
+            ("1 >> (-1)", "シフト量はビット幅未満の0以上の整数で指定してください"),
+            ("1 < 2", "対応していない文字です"),
+            ("5 ~ 3", "無効な演算子です"),
+            ("xor 1", "演算子の前に値がありません"),
+            ("xor = 1", "予約語には代入できません"),
+        ] {
+            match calculate_and_format(input) {
+                Ok(_) => panic!("should be error: {}", input),
+                Err(e) => assert_eq!(expected, e, "{}", input),
+            }
+        }
+    }
+
+    #[test]
+    fn calculate_programmer_wraps() {
+        assert_eq!("127 + 1 → -128", calculate_with(&machine_type("i8", true), "127 + 1").unwrap());
+        assert_eq!("0 - 1 → 255", calculate_with(&machine_type("u8", true), "0 - 1").unwrap());
+        assert_eq!("- 1 → 65535", calculate_with(&machine_type("u16", true), "-1").unwrap());
+        assert_eq!("200 → -56", calculate_with(&machine_type("i8", true), "200").unwrap());
+        assert_eq!(
+            "2147483647 + 1 → -2147483648",
+            calculate_with(&machine_type("i32", true), "2147483647 + 1").unwrap(),
+        );
+        assert_eq!(
+            "65536 * 65536 → 0",
+            calculate_with(&machine_type("u32", true), "65536 * 65536").unwrap(),
+        );
+        assert_eq!(
+            "18446744073709551615 * 18446744073709551615 → 1",
+            calculate_with(&machine_type("u64", true), "0xFFFFFFFFFFFFFFFF * 0xFFFFFFFFFFFFFFFF").unwrap(),
+        );
+        assert_eq!(
+            "( - 9223372036854775807 - 1 ) / ( - 1 ) → -9223372036854775808",
+            calculate_with(&machine_type("i64", true), "(-0x7FFFFFFFFFFFFFFF - 1) / (-1)").unwrap(),
+        );
+    }
+
+    #[test]
+    fn calculate_programmer_truncates_division() {
+        assert_eq!("7 / 2 → 3", calculate_with(&machine_type("i32", true), "7 / 2").unwrap());
+        assert_eq!("- 7 / 2 → -3", calculate_with(&machine_type("i32", true), "-7 / 2").unwrap());
+        assert_eq!("1 / 3 * 3 → 0", calculate_with(&machine_type("u8", true), "1 / 3 * 3").unwrap());
+        assert_eq!("- 128 / ( - 1 ) → -128", calculate_with(&machine_type("i8", true), "-128 / (-1)").unwrap());
+    }
+
+    #[test]
+    fn calculate_programmer_with_wide_operands() {
+        assert_eq!(
+            "4294967295 * 4294967295 → 1",
+            calculate_with(&machine_type("u32", true), "4294967295*4294967295").unwrap(),
+        );
+        assert_eq!(
+            "3037000500 * 3037000500 → -9223372036709301616",
+            calculate_with(&machine_type("i64", true), "3037000500*3037000500").unwrap(),
+        );
+    }
+
+    #[test]
+    fn calculate_programmer_errors() {
+        assert_eq!(Err("0で割ることはできません".to_string()), calculate_with(&machine_type("u8", true), "1 / 0"));
+        assert_eq!(Err("0で割ることはできません".to_string()), calculate_with(&machine_type("u8", true), "1 / 256"));
+        for input in ["pi", "1.5", "1.5 + 1", "-√2", "sqrt(2) * 0"] {
+            assert_eq!(
+                Err("整数以外はプログラマーモードで計算できません".to_string()),
+                calculate_with(&machine_type("i8", true), input),
+                "{}",
+                input,
+            );
+        }
+    }
+
+    #[test]
+    fn calculate_decimal_and_scientific_literals() {
+        assert_eq!("1.5 * 2 → 3", calculate_and_format("1.5 * 2").unwrap());
+        assert_eq!("0.1 + 0.2 → 3 / 10", calculate_and_format("0.1 + 0.2").unwrap());
+        assert_eq!("1.5E-3 → 3 / 2000", calculate_and_format("1.5E-3").unwrap());
+        assert_eq!("2e+3 / 4 → 500", calculate_and_format("2e+3 / 4").unwrap());
+        assert_eq!(
+            "6.02e23 → 602000000000000000000000",
+            calculate_and_format("6.02e23").unwrap(),
+        );
+        assert_eq!("6.02e23 / 2 → 301000000000000000000000", calculate_and_format("6.02e23 / 2").unwrap());
+        assert_eq!("1e-10 * 2 → 1 / 5000000000", calculate_and_format("1e-10 * 2").unwrap());
+        assert_eq!("2 * e → 2e", calculate_and_format("2e").unwrap());
+        assert_eq!("2 * e + e → 3e", calculate_and_format("2e + e").unwrap());
+    }
+
+    #[test]
+    fn calculate_literals_mixed_with_other_numbers() {
+        assert_eq!("1e10 * 1e10 → 100000000000000000000", calculate_and_format("1e10*1e10").unwrap());
+        assert_eq!(
+            "1e20 * 1e20 → 10000000000000000000000000000000000000000",
+            calculate_and_format("1e20*1e20").unwrap(),
+        );
+        assert_eq!("1e20 / 3 → 100000000000000000000 / 3", calculate_and_format("1e20/3").unwrap());
+        assert_eq!("1e19 / 3 → 10000000000000000000 / 3", calculate_and_format("1e19/3").unwrap());
+        assert_eq!("1 / 3 * 1e20 → 100000000000000000000 / 3", calculate_and_format("1/3*1e20").unwrap());
+        assert_eq!("1.5e-3 * 2147483647 → 6442450941 / 2000", calculate_and_format("1.5e-3*2147483647").unwrap());
+        let tiny = Calculation::from_input("1e-400").unwrap();
+        assert_eq!(format!("1 / 1{}", "0".repeat(400)), tiny.result);
+        assert_eq!("0", tiny.decimal);
+        assert!(!tiny.approximate);
+        assert_eq!("1e400 * 1e-400 → 1", calculate_and_format("1e400 * 1e-400").unwrap());
+        assert_eq!(
+            Err("桁数が多すぎて正確に計算できません".to_string()),
+            calculate_and_format("1e100000"),
+        );
+    }
+
+    #[test]
+    fn calculate_si_prefix_literals() {
+        assert_eq!("3k → 3000", calculate_and_format("3k").unwrap());
+        assert_eq!("2.2M / 2 → 1100000", calculate_and_format("2.2M / 2").unwrap());
+        assert_eq!("470n * 1000 → 47 / 100000", calculate_and_format("470n * 1000").unwrap());
+        assert_eq!("3u → 3 / 1000000", calculate_and_format("3u").unwrap());
+        assert_eq!("2µ * 5 → 1 / 100000", calculate_and_format("2µ * 5").unwrap());
+        assert_eq!("2 * min", Expression::parse("2min").unwrap().to_string());
+        assert_eq!("k", Expression::parse("k").unwrap().to_string());
+        assert_eq!("km = 5 → 5", calculate_and_format("km = 5").unwrap());
+    }
+
+    #[test]
+    fn calculate_si_prefix_letters_as_variables() {
+        let mut environment = Environment::default();
+        let settings = Settings::default();
+        for (input, expected) in [
+            ("n = 5", "n = 5 → 5"),
+            ("m = 3", "m = 3 → 3"),
+            ("k = 4", "k = 4 → 4"),
+            ("n * m + k", "n * m + k → 19"),
+            ("2k", "2k → 2000"),
+            ("2 * k", "2 * k → 8"),
+        ] {
+            let actual = Calculation::calculate(input, &mut environment, &settings).unwrap();
+            assert_eq!(expected, actual.to_string());
+        }
+    }
+
+    #[test]
+    fn calculate_in_engineering_notation() {
+        let engineering = Settings { notation: Notation::Engineering, ..Settings::default() };
+        let si = Settings { notation: Notation::Si, ..Settings::default() };
+        assert_eq!("470n * 1000 → 470e-6", calculate_with(&engineering, "470n * 1000").unwrap());
+        assert_eq!("470n * 1000 → 470µ", calculate_with(&si, "470n * 1000").unwrap());
+        assert_eq!("12345 → 12.345k", calculate_with(&si, "12345").unwrap());
+        assert_eq!("1 / 3 ≈ 333.3333333m", calculate_with(&si, "1 / 3").unwrap());
+        assert_eq!("- 1500 → -1.5e3", calculate_with(&engineering, "-1500").unwrap());
+        let actual = Calculation::calculate(
+            "2.2k",
+            &mut Environment::default(),
+            &Settings { notation: Notation::Si, ..Settings::default() },
+        ).unwrap();
+        assert_eq!("2.2k", actual.result);
+        assert_eq!("2200", actual.decimal);
+    }
+
+    #[test]
+    fn calculate_japanese_unit_literals() {
+        assert_eq!("3億2000万 → 320000000", calculate_and_format("3億2000万").unwrap());
+        assert_eq!("1.5万 + 1 → 15001", calculate_and_format("1.5万 + 1").unwrap());
+        assert_eq!("1兆 / 1億 → 10000", calculate_and_format("1兆 / 1億").unwrap());
+        assert_eq!("2万5 → 20005", calculate_and_format("2万5").unwrap());
+        assert_eq!("3万 * x", Expression::parse("3万x").unwrap().to_string());
+        for (input, expected) in [
+            ("3万2億", "対応していない文字です"),
+            ("万", "対応していない文字です"),
+        ] {
+            match calculate_and_format(input) {
+                Ok(_) => panic!("should be error: {}", input),
+                Err(e) => assert_eq!(expected, e, "{}", input),
+            }
+        }
+    }
+
+    #[test]
+    fn calculate_digit_separators() {
+        assert_eq!("1,000,000 + 1 → 1000001", calculate_and_format("1,000,000 + 1").unwrap());
+        assert_eq!("1_000_000 / 4 → 250000", calculate_and_format("1_000_000 / 4").unwrap());
+        assert_eq!("1，000 * 2 → 2000", calculate_and_format("1，000 * 2").unwrap());
+        assert_eq!("1,234.5 → 2469 / 2", calculate_and_format("1,234.5").unwrap());
+        assert_eq!("1,200万 → 12000000", calculate_and_format("1,200万").unwrap());
+        assert_eq!("( 1,000 ) → 1000", calculate_and_format("(1,000)").unwrap());
+        assert_eq!("log ( 10 , 100 ) → 2", calculate_and_format("log(10,100)").unwrap());
+        assert_eq!("log ( 10 , 1_000 ) → 3", calculate_and_format("log(10, 1_000)").unwrap());
+        assert_eq!("1 , 2", Expression::parse("1,2").unwrap().to_string());
+        assert_eq!("1,000 , 2", Expression::parse("1,000,2").unwrap().to_string());
+        assert_eq!("1 , 2000", Expression::parse("1,2000").unwrap().to_string());
+    }
+
+    #[test]
+    fn grouped_result_follows_locale() {
+        let actual = Calculation::from_input("1234567 / 2").unwrap();
+        assert_eq!("1234567 / 2", actual.grouped_result(None));
+        assert_eq!("1,234,567 / 2", actual.grouped_result(Some(Locale::English)));
+        assert_eq!("617.283,5", actual.grouped_decimal(Some(Locale::German)));
+        assert_eq!("617283.5", actual.grouped_decimal(None));
+        let actual = Calculation::from_input("1234567.5").unwrap();
+        assert_eq!("1234567.5 → 2,469,135 / 2", actual.to_grouped_string(Some(Locale::English)));
+        assert_eq!(Some(Locale::English), Locale::parse("ja-JP"));
+        assert_eq!(Some(Locale::German), Locale::parse("de"));
+        assert_eq!(Some(Locale::Indian), Locale::parse("en-IN"));
+        assert_eq!(None, Locale::parse("xx"));
+    }
+
+    #[test]
+    fn calculate_in_japanese_notation() {
+        let myriad = Settings { notation: Notation::Myriad, ..Settings::default() };
+        let kanji = Settings { notation: Notation::Kanji, ..Settings::default() };
+        assert_eq!(
+            "32000 * 10000 → 3億2000万",
+            calculate_with(&myriad, "32000 * 10000").unwrap(),
+        );
+        assert_eq!(
+            "18446744073709551615 → 1844京6744兆737億955万1615",
+            calculate_with(&myriad, "0xFFFFFFFFFFFFFFFF").unwrap(),
+        );
+        assert_eq!("1 / 4 → 0.25", calculate_with(&myriad, "1 / 4").unwrap());
+        assert_eq!("300 + 20 → 三百二十", calculate_with(&kanji, "300 + 20").unwrap());
+        assert_eq!("3億2000万 → 三億二千万", calculate_with(&kanji, "3億2000万").unwrap());
+    }
+
+    #[test]
+    fn calculate_japanese_units_up_to_goku() {
+        let myriad = Settings { notation: Notation::Myriad, ..Settings::default() };
+        let kanji = Settings { notation: Notation::Kanji, ..Settings::default() };
+        assert_eq!("1e24 → 一秭", calculate_with(&kanji, "1e24").unwrap());
+        assert_eq!("1e24 → 1秭", calculate_with(&myriad, "1e24").unwrap());
+        assert_eq!("2穣 → 2穣", calculate_with(&myriad, "2穣").unwrap());
+        assert_eq!("9999e48 → 九千九百九十九極", calculate_with(&kanji, "9999e48").unwrap());
+        let expected = Err("極より大きな数は万進表記や漢数字で表せません".to_string());
+        assert_eq!(expected, calculate_with(&kanji, "1e52"));
+        assert_eq!(expected, calculate_with(&myriad, "-1e52"));
+        assert_eq!("1e52 → 10e51", calculate_with(&Settings { notation: Notation::Si, ..myriad }, "1e52").unwrap());
+    }
+
+    #[test]
+    fn calculate_assignment_to_constant() {
+        let expected = "定数には代入できません";
+        match calculate_and_format("e = 2") {
+            Ok(_) => panic!("should be error"),
+            Err(e) => assert_eq!(expected, e),
+        }
+    }
+
+    #[test]
+    fn calculate_assignment_invalid_target() {
+        let expected = "代入先が変数名ではありません";
+        match calculate_and_format("1 = 2") {
+            Ok(_) => panic!("should be error"),
+            Err(e) => assert_eq!(expected, e),
+        }
+    }
+
+    #[test]
+    fn calculate_assignment_to_ans() {
+        let expected = "ansには代入できません";
+        match calculate_and_format("ans = 2") {
+            Ok(_) => panic!("should be error"),
+            Err(e) => assert_eq!(expected, e),
+        }
+    }
+
+    #[test]
+    fn calculate_ans_without_result() {
+        let expected = "ansはまだ計算結果を持っていません";
+        match calculate_and_format("ans + 1") {
+            Ok(_) => panic!("should be error"),
+            Err(e) => assert_eq!(expected, e),
+        }
+    }
+
+    #[test]
+    fn parse_undefined_variable() {
+        let expected = "定義されていない変数です";
+        match calculate_and_format("x + 1") {
+            Ok(_) => panic!("should be error"),
+            Err(e) => assert_eq!(expected, e),
+        }
+    }
+
+    #[test]
+    fn parse_invalid_operator() {
+        let input = "1 $ 2";
+        let expected = "対応していない文字です";
+        match calculate_and_format(input) {
+            Ok(_) => panic!("should be error"),
+            Err(e) => assert_eq!(expected, e),
+        }
+    }
+
+    #[test]
+    fn parse_missing_operator() {
+        let input = "1 2";
+        let expected = "数値が連続しています";
+        match calculate_and_format(input) {
+            Ok(_) => panic!("should be error"),
+            Err(e) => assert_eq!(expected, e),
+        }
+    }
+
+    #[test]
+    fn parse_missing_right_operand() {
+        let input = "1 + ";
+        let expected = "構文解析に失敗しました";
+        match calculate_and_format(input) {
+            Ok(_) => panic!("should be error"),
+            Err(e) => assert_eq!(expected, e),
+        }
+    }
+
+    #[test]
+    fn parse_many_spaces() {
+        let input = "  1  +  2  ";
+        let expected = "1 + 2 → 3";
+        let actual = calculate_and_format(input).unwrap();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn parse_paren_normal() {
+        let input = "2 - (1 + 2) * 3";
+        let expected = "2 - ( 1 + 2 ) * 3 → -7";
+        let actual = calculate_and_format(input).unwrap();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn parse_paren_unary() {
+        let input = "-(-2 + 1) * 3";
+        let expected = "- ( - 2 + 1 ) * 3 → 3";
+        let actual = calculate_and_format(input).unwrap();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn parse_paren_num_to_paren() {
+        let input = "1 + 2(2 * 3)";
+        let expected = "1 + 2 * ( 2 * 3 ) → 13";
+        let actual = calculate_and_format(input).unwrap();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn parse_paren_paren_to_num() {
+        let input = "(1 + 2)2 * 3";
+        let expected = "( 1 + 2 ) * 2 * 3 → 18";
+        let actual = calculate_and_format(input).unwrap();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn parse_paren_missing_right_paren() {
+        let input = "1 + (2 * 3";
+        let expected = "括弧が閉じられていません";
+        match calculate_and_format(input) {
+            Ok(_) => panic!("should be error"),
+            Err(e) => assert_eq!(expected, e),
+        }
+    }
+
+    #[test]
+    fn parse_paren_missing_left_paren() {
+        let input = "1 + 2) * 3";
+        let expected = "括弧の対応が取れていません";
+        match calculate_and_format(input) {
+            Ok(_) => panic!("should be error"),
+            Err(e) => assert_eq!(expected, e),
+        }
+    }
+
+    #[test]
+    fn parse_paren_missing_element() {
+        let input = "1 + ()";
+        let expected = "括弧の中に要素がありませんでした";
+        match calculate_and_format(input) {
+            Ok(_) => panic!("should be error"),
+            Err(e) => assert_eq!(expected, e),
+        }
+    }
+
+    #[test]
+    fn parse_fraction_normal() {
+        let input = "1/2 + 3/4";
+        let expected = "1 / 2 + 3 / 4 → 5 / 4";
+        let actual = calculate_and_format(input).unwrap();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn parse_fraction_denominator_minus() {
+        let input = "1/2 + 3/(-4)";
+        let expected = "1 / 2 + 3 / ( - 4 ) → -1 / 4";
+        let actual = calculate_and_format(input).unwrap();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_add_normal() {
+        let input = "1 + 2";
+        let expected = "1 + 2 → 3";
+        let actual = calculate_and_format(input).unwrap();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_add_beyond_int32() {
+        let input = "2147483647 + 1";
+        let expected = "2147483647 + 1 → 2147483648";
+        let actual = calculate_and_format(input).unwrap();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_sub_normal() {
+        let input = "1 - 2";
+        let expected = "1 - 2 → -1";
+        let actual = calculate_and_format(input).unwrap();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_sub_beyond_int32() {
+        let input = "-2147483647 - 2";
+        let expected = "- 2147483647 - 2 → -2147483649";
+        let actual = calculate_and_format(input).unwrap();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_mul_normal() {
+        let input = "2 * 3";
+        let expected = "2 * 3 → 6";
+        let actual = calculate_and_format(input).unwrap();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_mul_beyond_int32() {
+        let input = "2147483647 * 2";
+        let expected = "2147483647 * 2 → 4294967294";
+        let actual = calculate_and_format(input).unwrap();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_div_normal() {
+        let input = "6 / 3";
+        let expected = "6 / 3 → 2";
+        let actual = calculate_and_format(input).unwrap();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_div_beyond_int32() {
+        let input = "(-2147483647 - 1) / (-1)";
+        let expected = "( - 2147483647 - 1 ) / ( - 1 ) → 2147483648";
+        let actual = calculate_and_format(input).unwrap();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_minus_of_int32_min() {
+        let input = "-(-2147483647 - 1)";
+        let expected = "- ( - 2147483647 - 1 ) → 2147483648";
+        let actual = calculate_and_format(input).unwrap();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_back_within_int32() {
+        let mut environment = Environment::default();
+        let actual = Calculation::calculate_statement("2147483647 + 1 - 1", &mut environment, &Settings::default());
+        assert_eq!(Num::I32(2147483647), actual.unwrap().1);
+    }
+
+    #[test]
+    fn test_div_by_zero() {
+        let input = "1 / 0";
+        let expected = "0で割ることはできません";
+        match calculate_and_format(input) {
+            Ok(_) => panic!("should be error"),
+            Err(e) => assert_eq!(expected, e),
+        }
+    }
+
+    #[test]
+    fn test_plus_normal() {
+        let input = "+1";
+        let expected = "+ 1 → 1";
+        let actual = calculate_and_format(input).unwrap();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_minus_normal() {
+        let input = "-1";
+        let expected = "- 1 → -1";
+        let actual = calculate_and_format(input).unwrap();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_minus_beyond_int32() {
+        let input = "-2147483648";
+        let expected = "- 2147483648 → -2147483648";
+        let actual = calculate_and_format(input).unwrap();
+        assert_eq!(expected, actual);
+    }
+}
@@ -0,0 +1,370 @@
+use num::{BigInt, BigRational, Integer, One, Signed, Zero};
+use super::num::Num;
+use super::symbolic::Constant;
+use super::error::ErrorKind;
+
+/// Fixed-point number `value / 10^scale` for evaluating approximations to
+/// many digits. Values combined by an operation share the same scale.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Decimal {
+    value: BigInt,
+    scale: u32,
+}
+
+fn pow10(exponent: u32) -> BigInt {
+    num::pow(BigInt::from(10), exponent as usize)
+}
+
+impl Decimal {
+    fn new(value: BigInt, scale: u32) -> Self {
+        Self { value, scale }
+    }
+
+    fn one_value(&self) -> BigInt {
+        pow10(self.scale)
+    }
+
+    pub fn from_integer(num: i64, scale: u32) -> Self {
+        Self::new(BigInt::from(num) * pow10(scale), scale)
+    }
+
+    pub fn from_ratio(numerator: i64, denominator: i64, scale: u32) -> Self {
+        Self::new(BigInt::from(numerator) * pow10(scale) / denominator, scale)
+    }
+
+    pub fn from_num(num: &Num, scale: u32) -> Result<Self, ErrorKind> {
+        match num {
+            Num::Symbolic(symbolic) => {
+                Self::from_num(symbolic.coefficient(), scale)?
+                    .mul(&Self::constant(symbolic.constant(), scale)?)
+            },
+            Num::Radical(radical) => {
+                Self::from_num(radical.coefficient(), scale)?
+                    .mul(&Self::new(radical.radicand() * pow10(scale), scale).sqrt()?)
+            },
+            // Only as precise as the f64 it came from.
+            Num::Real(num) => Self::parse(&format!("{:.17e}", num), scale),
+            num => match num.to_rational() {
+                Some(value) => Ok(Self::new(value.numer() * pow10(scale) / value.denom(), scale)),
+                None => Err(ErrorKind::InvalidNumber),
+            },
+        }
+    }
+
+    // Parses `d.ddde±x` as written by `{:e}`.
+    fn parse(input: &str, scale: u32) -> Result<Self, ErrorKind> {
+        let invalid = || ErrorKind::InvalidNumber;
+        let (mantissa, exponent) = input.split_once('e').ok_or_else(invalid)?;
+        let exponent: i64 = exponent.parse().map_err(|_| invalid())?;
+        let fraction_digits = mantissa.split_once('.').map_or(0, |(_, fraction)| fraction.len());
+        let digits: BigInt = mantissa.replace('.', "").parse().map_err(|_| invalid())?;
+        let shift = scale as i64 + exponent - fraction_digits as i64;
+        let value = if shift >= 0 {
+            digits * pow10(shift as u32)
+        } else {
+            digits / pow10((-shift) as u32)
+        };
+        Ok(Self::new(value, scale))
+    }
+
+    pub fn constant(constant: Constant, scale: u32) -> Result<Self, ErrorKind> {
+        match constant {
+            Constant::Pi => Ok(Self::pi(scale)),
+            Constant::E => Self::from_integer(1, scale).exp(),
+        }
+    }
+
+    // Machin's formula: π = 16 atan(1/5) - 4 atan(1/239).
+    pub fn pi(scale: u32) -> Self {
+        let atan_inverse = |n: i64| {
+            let n_squared = BigInt::from(n * n);
+            let mut term = pow10(scale) / n;
+            let mut sum = term.clone();
+            let mut k = 1;
+            while !term.is_zero() {
+                term /= &n_squared;
+                let next = &term / (2 * k + 1);
+                if k % 2 == 1 { sum -= next } else { sum += next }
+                k += 1;
+            }
+            sum
+        };
+        Self::new(atan_inverse(5) * 16 - atan_inverse(239) * 4, scale)
+    }
+
+    pub fn scale(&self) -> u32 {
+        self.scale
+    }
+
+    pub fn is_one(&self) -> bool {
+        self.value == self.one_value()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.value.is_zero()
+    }
+
+    pub fn is_minus(&self) -> bool {
+        self.value.is_negative()
+    }
+
+    /// Zeros between the decimal point and the first significant digit,
+    /// or `None` for zero.
+    pub fn leading_zeros(&self) -> Option<u32> {
+        match self.is_zero() {
+            true => None,
+            false => Some(self.scale.saturating_sub(self.value.magnitude().to_string().len() as u32)),
+        }
+    }
+
+    /// The value as an exact rational number.
+    pub fn to_rational(&self) -> BigRational {
+        BigRational::new(self.value.clone(), self.one_value())
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        Self::new(&self.value + &other.value, self.scale)
+    }
+
+    pub fn sub(&self, other: &Self) -> Self {
+        Self::new(&self.value - &other.value, self.scale)
+    }
+
+    pub fn minus(&self) -> Self {
+        Self::new(-&self.value, self.scale)
+    }
+
+    pub fn mul(&self, other: &Self) -> Result<Self, ErrorKind> {
+        Ok(Self::new(&self.value * &other.value / self.one_value(), self.scale))
+    }
+
+    pub fn div(&self, other: &Self) -> Result<Self, ErrorKind> {
+        if other.is_zero() {
+            return Err(ErrorKind::DivisionByZero);
+        }
+        Ok(Self::new(&self.value * self.one_value() / &other.value, self.scale))
+    }
+
+    pub fn mul_integer(&self, num: i64) -> Self {
+        Self::new(&self.value * num, self.scale)
+    }
+
+    pub fn div_integer(&self, num: i64) -> Self {
+        Self::new(&self.value / num, self.scale)
+    }
+
+    pub fn sqrt(&self) -> Result<Self, ErrorKind> {
+        if self.is_minus() {
+            return Err(ErrorKind::NegativeSquareRoot);
+        }
+        Ok(Self::new((&self.value * self.one_value()).sqrt(), self.scale))
+    }
+
+    pub fn exp(&self) -> Result<Self, ErrorKind> {
+        if self.is_minus() {
+            return Self::from_integer(1, self.scale).div(&self.minus().exp()?);
+        }
+        // exp(x) = exp(x / 2^n)^(2^n), with a quickly converging series.
+        let half = self.one_value() / 2;
+        let mut x = self.clone();
+        let mut halvings = 0;
+        while x.value > half {
+            x = x.div_integer(2);
+            halvings += 1;
+        }
+        let one = self.one_value();
+        let mut term = one.clone();
+        let mut sum = one.clone();
+        let mut k = 1;
+        while !term.is_zero() {
+            term = term * &x.value / &one / k;
+            sum += &term;
+            k += 1;
+        }
+        for _ in 0..halvings {
+            sum = &sum * &sum / &one;
+        }
+        Ok(Self::new(sum, self.scale))
+    }
+
+    // atanh(x) = x + x³/3 + x⁵/5 + ..., for small |x|.
+    fn atanh_series(&self) -> Self {
+        let one = self.one_value();
+        let x_squared = &self.value * &self.value / &one;
+        let mut power = self.value.clone();
+        let mut sum = power.clone();
+        let mut k = 1;
+        while !power.is_zero() {
+            power = power * &x_squared / &one;
+            sum += &power / (2 * k + 1);
+            k += 1;
+        }
+        Self::new(sum, self.scale)
+    }
+
+    pub fn ln(&self) -> Result<Self, ErrorKind> {
+        if self.is_minus() || self.is_zero() {
+            return Err(ErrorKind::NonPositiveLogarithm);
+        }
+        // ln(x) = k ln(2) + ln(y) with 1 <= y < 2, and
+        // ln(y) = 2 atanh((y - 1) / (y + 1)).
+        let one = Self::from_integer(1, self.scale);
+        let two = self.one_value() * 2;
+        let mut y = self.clone();
+        let mut k = 0;
+        while y.value >= two {
+            y = y.div_integer(2);
+            k += 1;
+        }
+        while y.value < one.value {
+            y = y.mul_integer(2);
+            k -= 1;
+        }
+        let ln_y = y.sub(&one).div(&y.add(&one))?.atanh_series().mul_integer(2);
+        let ln_2 = Self::from_ratio(1, 3, self.scale).atanh_series().mul_integer(2);
+        Ok(ln_y.add(&ln_2.mul_integer(k)))
+    }
+
+    // Brings x into (-π, π].
+    fn reduce_angle(&self) -> Self {
+        let pi = Self::pi(self.scale);
+        let two_pi = pi.mul_integer(2);
+        let value = self.value.mod_floor(&two_pi.value);
+        let angle = Self::new(value, self.scale);
+        if angle.value > pi.value { angle.sub(&two_pi) } else { angle }
+    }
+
+    // Σ (-1)^k x^(2k + offset) / (2k + offset)!
+    fn trigonometric_series(&self, offset: i64) -> Self {
+        let one = self.one_value();
+        let x_squared = &self.value * &self.value / &one;
+        let mut term = if offset == 0 { one.clone() } else { self.value.clone() };
+        let mut sum = term.clone();
+        let mut k = 1;
+        while !term.is_zero() {
+            let n = 2 * k + offset;
+            term = -(term * &x_squared / &one) / ((n - 1) * n);
+            sum += &term;
+            k += 1;
+        }
+        Self::new(sum, self.scale)
+    }
+
+    pub fn sin(&self) -> Self {
+        self.reduce_angle().trigonometric_series(1)
+    }
+
+    pub fn cos(&self) -> Self {
+        self.reduce_angle().trigonometric_series(0)
+    }
+
+    pub fn tan(&self) -> Result<Self, ErrorKind> {
+        let cos = self.cos();
+        // cos underflows to a few units in the last place at odd multiples of 90°.
+        if cos.value.abs() < BigInt::from(1_000_000) {
+            return Err(ErrorKind::TangentUndefined);
+        }
+        self.sin().div(&cos)
+    }
+
+    pub fn atan(&self) -> Result<Self, ErrorKind> {
+        let one = Self::from_integer(1, self.scale);
+        if self.is_minus() {
+            return Ok(self.minus().atan()?.minus());
+        }
+        if self.value > one.value {
+            // atan(x) = π/2 - atan(1/x)
+            return Ok(Self::pi(self.scale).div_integer(2).sub(&one.div(self)?.atan()?));
+        }
+        // atan(x) = 2 atan(x / (1 + √(1 + x²))) until x is small.
+        let small = self.one_value() / 10;
+        let mut x = self.clone();
+        let mut doublings = 0;
+        while x.value > small {
+            let root = one.add(&x.mul(&x)?).sqrt()?;
+            x = x.div(&one.add(&root))?;
+            doublings += 1;
+        }
+        let unit = self.one_value();
+        let x_squared = &x.value * &x.value / &unit;
+        let mut power = x.value.clone();
+        let mut sum = power.clone();
+        let mut k = 1;
+        while !power.is_zero() {
+            power = -(power * &x_squared / &unit);
+            sum += &power / (2 * k + 1);
+            k += 1;
+        }
+        Ok(Self::new(sum << doublings, self.scale))
+    }
+
+    pub fn asin(&self) -> Result<Self, ErrorKind> {
+        let one = Self::from_integer(1, self.scale);
+        if self.value.abs() > one.value {
+            return Err(ErrorKind::OutsideUnitRange);
+        }
+        if self.value.abs() == one.value {
+            let right_angle = Self::pi(self.scale).div_integer(2);
+            return Ok(if self.is_minus() { right_angle.minus() } else { right_angle });
+        }
+        self.div(&one.sub(&self.mul(self)?).sqrt()?)?.atan()
+    }
+
+    pub fn acos(&self) -> Result<Self, ErrorKind> {
+        Ok(Self::pi(self.scale).div_integer(2).sub(&self.asin()?))
+    }
+
+    /// The value rounded to `digits` significant digits, without trailing
+    /// zeros after the decimal point.
+    pub fn to_significant(&self, digits: u32) -> String {
+        let magnitude = self.value.abs();
+        let length = magnitude.to_string().len() as u32;
+        let (mut rounded, mut exponent) = (magnitude, -(self.scale as i64));
+        if length > digits {
+            let dropped = length - digits;
+            let divisor = pow10(dropped);
+            let (quotient, remainder) = rounded.div_rem(&divisor);
+            rounded = if remainder * 2 >= divisor { quotient + 1 } else { quotient };
+            exponent += dropped as i64;
+        }
+        let mut text = rounded.to_string();
+        if exponent >= 0 {
+            text.push_str(&"0".repeat(exponent as usize));
+        } else {
+            let fraction_digits = (-exponent) as usize;
+            if text.len() <= fraction_digits {
+                text = format!("{}{}", "0".repeat(fraction_digits + 1 - text.len()), text);
+            }
+            text.insert(text.len() - fraction_digits, '.');
+            text = text.trim_end_matches('0').trim_end_matches('.').to_string();
+        }
+        if self.is_minus() && text != "0" {
+            text.insert(0, '-');
+        }
+        text
+    }
+}
+
+/// Whether `num` is a rational number whose decimal expansion ends within
+/// `digits` significant digits.
+pub fn is_exact_to(num: &Num, digits: u32) -> bool {
+    let Some(value) = num.to_rational() else {
+        return false;
+    };
+    let mut rest = value.denom().clone();
+    let mut fraction_digits = 0;
+    for factor in [2, 5] {
+        let factor = BigInt::from(factor);
+        let mut count = 0;
+        while rest.is_multiple_of(&factor) {
+            rest /= &factor;
+            count += 1;
+        }
+        fraction_digits = fraction_digits.max(count);
+    }
+    if !rest.is_one() {
+        return false;
+    }
+    let scaled = value.numer() * pow10(fraction_digits) / value.denom();
+    scaled.abs().to_string().trim_end_matches('0').len() as u32 <= digits
+}
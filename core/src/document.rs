@@ -0,0 +1,177 @@
+use super::calculation::Calculation;
+use super::environment::Environment;
+use super::settings::Settings;
+use super::operator::Operator;
+use super::function::Function;
+use super::error::{ErrorKind, Span};
+
+const LINE_REFERENCE_PREFIX: &str = "line";
+const COMMENT_PREFIX: char = '#';
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LineResult {
+    Comment,
+    Value(Calculation),
+    Error(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocumentLine {
+    pub input: String,
+    pub result: LineResult,
+}
+
+/// Multi-line notepad where every line is evaluated on its own, `;`
+/// separating statements within a line as in the calculator.
+/// Results of earlier lines can be referenced as `line1`, `line2`, ...
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Document {
+    lines: Vec<DocumentLine>,
+}
+
+impl Document {
+    pub fn evaluate(text: &str, settings: &Settings) -> Self {
+        let mut environment = Environment::default();
+        let lines = text
+            .lines()
+            .enumerate()
+            .map(|(index, input)| DocumentLine {
+                input: input.to_string(),
+                result: Self::evaluate_line(index + 1, input, &mut environment, settings),
+            })
+            .collect();
+        Self { lines }
+    }
+
+    pub fn lines(&self) -> &[DocumentLine] {
+        &self.lines
+    }
+
+    fn evaluate_line(
+        number: usize,
+        input: &str,
+        environment: &mut Environment,
+        settings: &Settings,
+    ) -> LineResult {
+        let statement = input.trim();
+        if statement.is_empty() || statement.starts_with(COMMENT_PREFIX) {
+            return LineResult::Comment;
+        }
+        // `;`-separated statements run in order; the line shows the last.
+        let mut last = None;
+        for (index, (statement, _)) in Calculation::split_statements(statement).into_iter().enumerate() {
+            match Calculation::calculate_statement_with_span(statement, environment, settings) {
+                Ok(value) => last = Some(value),
+                Err((kind, span)) if index == 0 && Self::is_text(statement, kind, span) => {
+                    return LineResult::Comment;
+                },
+                Err((kind, _)) => return LineResult::Error(kind.to_string()),
+            }
+        }
+        let Some((calculation, result)) = last else {
+            return LineResult::Comment;
+        };
+        let name = format!("{}{}", LINE_REFERENCE_PREFIX, number);
+        match environment.set(&name, result) {
+            Ok(_) => LineResult::Value(calculation),
+            Err(e) => LineResult::Error(e.to_string()),
+        }
+    }
+
+    // A line failing in its first statement is prose when that statement
+    // has no numbers or operators before the first character that the
+    // parser does not accept, or none at all when every character is
+    // accepted.
+    fn is_text(statement: &str, kind: ErrorKind, span: Span) -> bool {
+        let words = match kind {
+            ErrorKind::UnsupportedCharacter => Self::take_utf16(statement, span.start),
+            _ => statement,
+        };
+        !words.chars().any(|c| {
+            c.is_ascii_digit() || Operator::is_operator(c) || Function::from_symbol(c).is_some()
+        })
+    }
+
+    fn take_utf16(text: &str, length: usize) -> &str {
+        let mut count = 0;
+        let end = text
+            .char_indices()
+            .find(|(_, c)| {
+                count += c.len_utf16();
+                count > length
+            })
+            .map_or(text.len(), |(index, _)| index);
+        &text[..end]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn results(text: &str) -> Vec<String> {
+        Document::evaluate(text, &Settings::default())
+            .lines()
+            .iter()
+            .map(|line| match &line.result {
+                LineResult::Comment => "#".to_string(),
+                LineResult::Value(calculation) => calculation.result.clone(),
+                LineResult::Error(error) => error.clone(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn evaluate_lines_with_references() {
+        let text = "# 3月の予算\nrent = 1200\nfood = 300 * 4\n\nrent + food\nline5 / 2 + line2";
+        let expected = vec!["#", "1200", "1200", "#", "2400", "2400"];
+        assert_eq!(expected, results(text));
+    }
+
+    #[test]
+    fn evaluate_semicolon_statements() {
+        let expected = vec!["25", "50", "0で割ることはできません", "#"];
+        assert_eq!(expected, results("a = 3; b = 4; a*a + b*b\nline1 * 2\na; a / 0; b\nNote; see above"));
+    }
+
+    #[test]
+    fn evaluate_text_lines_as_comments() {
+        let expected = vec!["#", "#", "3"];
+        assert_eq!(expected, results("Budget for March\n家賃: 1200\n1 + 2"));
+        let expected = vec!["#", "#", "対応していない文字です"];
+        assert_eq!(expected, results("Total: 1200\nBudget für März\n1 + 2 @"));
+    }
+
+    #[test]
+    fn evaluate_errors_with_non_ascii_operators() {
+        let expected = vec![
+            "0で割ることはできません",
+            "演算子が連続しています",
+            "整数以外はビット演算できません",
+            "0で割ることはできません",
+            "0で割ることはできません",
+            "0で割ることはできません",
+        ];
+        assert_eq!(expected, results("π / 0\n√-4\n1/2 & 1\n1万 / 0\nsin(90°) / 0\n1，000 / 0"));
+    }
+
+    #[test]
+    fn evaluate_errors() {
+        let expected = vec![
+            "0で割ることはできません",
+            "定義されていない変数です",
+            "定義されていない変数です",
+        ];
+        assert_eq!(expected, results("1 / 0\nline1 + 1\nx + 1"));
+    }
+
+    #[test]
+    fn evaluate_keeps_line_inputs() {
+        let document = Document::evaluate("1 + 2\nmemo", &Settings::default());
+        let inputs: Vec<&str> = document.lines()
+            .iter()
+            .map(|line| line.input.as_str())
+            .collect();
+        assert_eq!(vec!["1 + 2", "memo"], inputs);
+    }
+}
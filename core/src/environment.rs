@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+use serde::{Serialize, Deserialize};
+use super::num::Num;
+use super::symbolic::{Constant, Symbolic};
+use super::function::Function;
+use super::operator::Operator;
+use super::error::ErrorKind;
+
+const ANS: &str = "ans";
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Environment {
+    ans: Option<Num>,
+    variables: HashMap<String, Num>,
+}
+
+impl Environment {
+    pub fn get(&self, name: &str) -> Result<Num, ErrorKind> {
+        if let Some(constant) = Constant::parse(name) {
+            return Ok(Num::Symbolic(Box::new(Symbolic::new(Num::I32(1), constant))));
+        }
+        match name {
+            ANS => self.ans
+                .clone()
+                .ok_or(ErrorKind::AnsUndefined),
+            _ => self.variables
+                .get(name)
+                .cloned()
+                .ok_or(ErrorKind::UndefinedVariable),
+        }
+    }
+
+    pub fn check_assignable(name: &str) -> Result<(), ErrorKind> {
+        match name {
+            ANS => Err(ErrorKind::AssignmentToAns),
+            _ if Constant::parse(name).is_some() => Err(ErrorKind::AssignmentToConstant),
+            _ if Function::parse(name).is_some() => Err(ErrorKind::AssignmentToFunction),
+            _ if Operator::parse_keyword(name).is_some() => Err(ErrorKind::AssignmentToKeyword),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn set(&mut self, name: &str, value: Num) -> Result<(), ErrorKind> {
+        Self::check_assignable(name)?;
+        self.variables.insert(name.to_string(), value);
+        Ok(())
+    }
+
+    pub fn set_ans(&mut self, value: Num) {
+        self.ans = Some(value);
+    }
+
+    /// The last result when it is an integer, for showing it in other bases.
+    pub fn ans_integer(&self) -> Option<i128> {
+        self.ans.as_ref().and_then(Num::to_i128)
+    }
+
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string(self)
+            .map_err(|_| "変数の保存に失敗しました".to_string())
+    }
+
+    pub fn from_json(value: &str) -> Result<Self, String> {
+        serde_json::from_str(value)
+            .map_err(|_| "変数の読み込みに失敗しました".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calculation::Calculation;
+    use crate::settings::Settings;
+
+    #[test]
+    fn environment_round_trip() {
+        let mut environment = Environment::default();
+        let inputs = [
+            "a = 1 / 3", "b = 2√2", "c = π / 4", "w = 0xFFFFFFFFFFFFFFFF",
+            "x = 6.02e23", "y = 1e-30", "12345678901234567890123 / 1000",
+        ];
+        for input in inputs {
+            Calculation::calculate(input, &mut environment, &Settings::default()).unwrap();
+        }
+        let value = environment.to_json().unwrap();
+        assert_eq!(environment, Environment::from_json(&value).unwrap());
+    }
+}
@@ -0,0 +1,199 @@
+use serde::Serialize;
+
+/// Why a calculation failed. Errors are raised with their kind where they
+/// happen, so that the code for programs does not depend on the wording of
+/// the message for people. Several kinds may share a code, like the
+/// different overflows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    UnsupportedCharacter,
+    ConsecutiveNumbers,
+    ConsecutiveOperators,
+    UnmatchedParen,
+    UnclosedParen,
+    EmptyParen,
+    SyntaxError,
+    InvalidNumber,
+    DivisionByZero,
+    AngleOverflow,
+    WideOverflow,
+    RealOverflow,
+    TooManyDigits,
+    UnitOverflow,
+    AnsUndefined,
+    UndefinedVariable,
+    InvalidAssignmentTarget,
+    AssignmentToAns,
+    AssignmentToConstant,
+    AssignmentToFunction,
+    AssignmentToKeyword,
+    MissingFunctionParen,
+    NegativeSquareRoot,
+    TangentUndefined,
+    OutsideUnitRange,
+    NonPositiveLogarithm,
+    InvalidLogarithmBase,
+    MisplacedDegreeSign,
+    ArgumentCount,
+    MisplacedComma,
+    InvalidPrecision,
+    MisplacedNumeric,
+    MissingOperand,
+    MisplacedConversion,
+    InvalidRadix,
+    NonIntegerConversion,
+    NonIntegerBitwise,
+    InvalidShift,
+    NonIntegerProgrammer,
+    InvalidOperator,
+    InvalidOperation,
+    NotRational,
+    Int32Conversion,
+    ParenParseFailure,
+}
+
+impl ErrorKind {
+    fn describe(&self) -> (&'static str, &'static str) {
+        match self {
+            ErrorKind::UnsupportedCharacter => ("unsupported_character", "対応していない文字です"),
+            ErrorKind::ConsecutiveNumbers => ("consecutive_numbers", "数値が連続しています"),
+            ErrorKind::ConsecutiveOperators => ("consecutive_operators", "演算子が連続しています"),
+            ErrorKind::UnmatchedParen => ("unmatched_paren", "括弧の対応が取れていません"),
+            ErrorKind::UnclosedParen => ("unclosed_paren", "括弧が閉じられていません"),
+            ErrorKind::EmptyParen => ("empty_paren", "括弧の中に要素がありませんでした"),
+            ErrorKind::SyntaxError => ("syntax_error", "構文解析に失敗しました"),
+            ErrorKind::InvalidNumber => ("invalid_number", "数値に変換できません"),
+            ErrorKind::DivisionByZero => ("division_by_zero", "0で割ることはできません"),
+            ErrorKind::AngleOverflow => ("overflow", "int32の範囲を超える角度です"),
+            ErrorKind::WideOverflow => ("overflow", "128ビットの範囲を超える整数です"),
+            ErrorKind::RealOverflow => ("overflow", "計算結果が実数の範囲を超えました"),
+            ErrorKind::TooManyDigits => ("overflow", "桁数が多すぎて正確に計算できません"),
+            ErrorKind::UnitOverflow => ("overflow", "極より大きな数は万進表記や漢数字で表せません"),
+            ErrorKind::AnsUndefined => ("ans_undefined", "ansはまだ計算結果を持っていません"),
+            ErrorKind::UndefinedVariable => ("undefined_variable", "定義されていない変数です"),
+            ErrorKind::InvalidAssignmentTarget => ("invalid_assignment_target", "代入先が変数名ではありません"),
+            ErrorKind::AssignmentToAns => ("reserved_variable", "ansには代入できません"),
+            ErrorKind::AssignmentToConstant => ("reserved_variable", "定数には代入できません"),
+            ErrorKind::AssignmentToFunction => ("reserved_variable", "関数名には代入できません"),
+            ErrorKind::AssignmentToKeyword => ("reserved_variable", "予約語には代入できません"),
+            ErrorKind::MissingFunctionParen => ("missing_function_paren", "関数の後に括弧がありません"),
+            ErrorKind::NegativeSquareRoot => ("domain_error", "負の数の平方根は計算できません"),
+            ErrorKind::TangentUndefined => ("domain_error", "tanが定義されない角度です"),
+            ErrorKind::OutsideUnitRange => ("domain_error", "-1から1の範囲外の値です"),
+            ErrorKind::NonPositiveLogarithm => ("domain_error", "真数は正の数でなければなりません"),
+            ErrorKind::InvalidLogarithmBase => ("domain_error", "底は1以外の正の数でなければなりません"),
+            ErrorKind::MisplacedDegreeSign => ("misplaced_degree_sign", "°は数値の直後に付けてください"),
+            ErrorKind::ArgumentCount => ("argument_count", "関数の引数の数が正しくありません"),
+            ErrorKind::MisplacedComma => ("misplaced_comma", "カンマは関数の引数の区切りにだけ使えます"),
+            ErrorKind::InvalidPrecision => ("invalid_precision", "桁数は1から10000までの整数で指定してください"),
+            ErrorKind::MisplacedNumeric => ("misplaced_numeric", "Nは式全体にだけ使えます"),
+            ErrorKind::MissingOperand => ("missing_operand", "演算子の前に値がありません"),
+            ErrorKind::MisplacedConversion => ("misplaced_conversion", "基数変換は式全体にだけ使えます"),
+            ErrorKind::InvalidRadix => ("invalid_radix", "変換先はhex, dec, oct, binのいずれかです"),
+            ErrorKind::NonIntegerConversion => ("non_integer_conversion", "整数以外は基数変換できません"),
+            ErrorKind::NonIntegerBitwise => ("non_integer_bitwise", "整数以外はビット演算できません"),
+            ErrorKind::InvalidShift => ("invalid_shift", "シフト量はビット幅未満の0以上の整数で指定してください"),
+            ErrorKind::NonIntegerProgrammer => ("non_integer_programmer", "整数以外はプログラマーモードで計算できません"),
+            ErrorKind::InvalidOperator => ("unknown", "無効な演算子です"),
+            ErrorKind::InvalidOperation => ("unknown", "無効な演算です"),
+            ErrorKind::NotRational => ("unknown", "有理数ではありません"),
+            ErrorKind::Int32Conversion => ("unknown", "int32に変換できません"),
+            ErrorKind::ParenParseFailure => ("unknown", "括弧にparseができませんでした"),
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        self.describe().0
+    }
+
+    pub fn message(&self) -> &'static str {
+        self.describe().1
+    }
+}
+
+impl std::fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.message())
+    }
+}
+
+impl From<ErrorKind> for String {
+    fn from(kind: ErrorKind) -> Self {
+        kind.to_string()
+    }
+}
+
+/// Range in the input, counted in UTF-16 code units like JavaScript strings.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+
+    /// Span of `input[start..end]` without surrounding whitespace.
+    pub(crate) fn from_byte_range(input: &str, start: usize, end: usize) -> Self {
+        let part = &input[start..end];
+        let offset = input[..start].encode_utf16().count();
+        let leading = part.len() - part.trim_start().len();
+        let start = offset + part[..leading].encode_utf16().count();
+        let end = offset + part.trim_end().encode_utf16().count();
+        // An all-whitespace part becomes an empty span at its end.
+        Self::new(start, end.max(start))
+    }
+
+    pub(crate) fn shift(self, offset: usize) -> Self {
+        Self::new(self.start + offset, self.end + offset)
+    }
+}
+
+/// Error found while evaluating a parsed expression, with the span of the
+/// token that raised it when there is one, like an undefined variable or
+/// the `/` of a division by zero.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct TokenError {
+    pub kind: ErrorKind,
+    pub span: Option<Span>,
+}
+
+impl From<ErrorKind> for TokenError {
+    fn from(kind: ErrorKind) -> Self {
+        Self { kind, span: None }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CalculationError {
+    pub code: &'static str,
+    pub message: String,
+    pub span: Option<Span>,
+}
+
+impl CalculationError {
+    pub fn new(kind: ErrorKind, span: Option<Span>) -> Self {
+        Self { code: kind.code(), message: kind.to_string(), span }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_code_from_kind() {
+        let error = CalculationError::new(ErrorKind::DivisionByZero, None);
+        assert_eq!("division_by_zero", error.code);
+        assert_eq!("0で割ることはできません", error.message);
+        assert_eq!("overflow", ErrorKind::TooManyDigits.code());
+    }
+
+    #[test]
+    fn error_code_unknown() {
+        let error = CalculationError::new(ErrorKind::InvalidOperation, None);
+        assert_eq!("unknown", error.code);
+    }
+}
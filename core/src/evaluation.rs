@@ -0,0 +1,308 @@
+use serde::Serialize;
+use serde_json::Value;
+use super::expression::Expression;
+use super::environment::Environment;
+use super::settings::Settings;
+use super::error::CalculationError;
+use super::num::Num;
+use super::Calculation;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ValueKind {
+    Integer,
+    Fraction,
+    Symbolic,
+    Radical,
+    Approximate,
+}
+
+impl From<&Num> for ValueKind {
+    fn from(num: &Num) -> Self {
+        match num {
+            Num::I32(_) | Num::Wide(_) => Self::Integer,
+            Num::Fraction(_) => Self::Fraction,
+            Num::Symbolic(_) => Self::Symbolic,
+            Num::Radical(_) => Self::Radical,
+            Num::Real(_) => Self::Approximate,
+        }
+    }
+}
+
+/// Result of evaluating one input without the calculator UI.
+///
+/// The input is a single expression evaluated in an empty environment, so
+/// assignments such as `x = 1` are rejected with `unsupported_character` at
+/// the `=`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Evaluation {
+    pub input: String,
+    pub expression: Option<String>,
+    pub result: Option<String>,
+    pub decimal: Option<String>,
+    /// Whether `result` is rounded rather than exact.
+    pub approximate: Option<bool>,
+    /// Kind of the value in `result`, which is `approximate` whenever the
+    /// result is rounded, as for `N(π)`.
+    pub kind: Option<ValueKind>,
+    pub error: Option<CalculationError>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ParseResult {
+    pub input: String,
+    pub ast: Option<Value>,
+    pub error: Option<CalculationError>,
+}
+
+impl Evaluation {
+    pub fn evaluate(input: &str) -> Self {
+        let mut evaluation = Self {
+            input: input.to_string(),
+            expression: None,
+            result: None,
+            decimal: None,
+            approximate: None,
+            kind: None,
+            error: None,
+        };
+        let expression = match Expression::parse_with_span(input) {
+            Ok(expression) => expression,
+            Err((kind, span)) => {
+                evaluation.error = Some(CalculationError::new(kind, Some(span)));
+                return evaluation;
+            }
+        };
+        let mut environment = Environment::default();
+        match Calculation::calculate_expression(&expression, &mut environment, &Settings::default()) {
+            Ok((calculation, num)) => {
+                evaluation.expression = Some(calculation.expression);
+                evaluation.result = Some(calculation.result);
+                evaluation.decimal = Some(calculation.decimal);
+                evaluation.approximate = Some(calculation.approximate);
+                evaluation.kind = Some(match calculation.approximate {
+                    true => ValueKind::Approximate,
+                    false => ValueKind::from(&num),
+                });
+            }
+            Err(error) => {
+                evaluation.error = Some(CalculationError::new(error.kind, error.span));
+            }
+        }
+        evaluation
+    }
+
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string(self)
+            .map_err(|_| "計算結果の変換に失敗しました".to_string())
+    }
+}
+
+impl ParseResult {
+    pub fn parse(input: &str) -> Self {
+        let ast = Expression::parse_with_span(input)
+            .map_err(|(kind, span)| CalculationError::new(kind, Some(span)))
+            .and_then(|expression| {
+                expression.to_json()
+                    .map_err(|kind| CalculationError::new(kind, None))
+            });
+        match ast {
+            Ok(ast) => Self {
+                input: input.to_string(),
+                ast: Some(ast),
+                error: None,
+            },
+            Err(error) => Self {
+                input: input.to_string(),
+                ast: None,
+                error: Some(error),
+            },
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string(self)
+            .map_err(|_| "構文木の変換に失敗しました".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use crate::error::Span;
+
+    #[test]
+    fn evaluate_fraction() {
+        let evaluation = Evaluation::evaluate("1/2 + 3/4");
+        assert_eq!(Some("1 / 2 + 3 / 4".to_string()), evaluation.expression);
+        assert_eq!(Some("5 / 4".to_string()), evaluation.result);
+        assert_eq!(Some("1.25".to_string()), evaluation.decimal);
+        assert_eq!(Some(ValueKind::Fraction), evaluation.kind);
+        assert_eq!(None, evaluation.error);
+    }
+
+    #[test]
+    fn evaluate_integer() {
+        let evaluation = Evaluation::evaluate("2(1 + 2)");
+        assert_eq!(Some("6".to_string()), evaluation.result);
+        assert_eq!(Some(ValueKind::Integer), evaluation.kind);
+    }
+
+    #[test]
+    fn evaluate_scientific_literals_exactly() {
+        let evaluation = Evaluation::evaluate("6.02e23");
+        assert_eq!(Some("602000000000000000000000".to_string()), evaluation.result);
+        assert_eq!(Some(ValueKind::Integer), evaluation.kind);
+        assert_eq!(Some(ValueKind::Fraction), Evaluation::evaluate("1e-30").kind);
+    }
+
+    #[test]
+    fn evaluate_constant_kinds() {
+        let evaluation = Evaluation::evaluate("π / 2");
+        assert_eq!(Some("π / 2".to_string()), evaluation.result);
+        assert_eq!(Some(ValueKind::Symbolic), evaluation.kind);
+        assert_eq!(Some(ValueKind::Approximate), Evaluation::evaluate("e + 1").kind);
+    }
+
+    #[test]
+    fn evaluate_kind_of_returned_value() {
+        let evaluation = Evaluation::evaluate("N(pi)");
+        assert_eq!(Some(true), evaluation.approximate);
+        assert_eq!(Some(ValueKind::Approximate), evaluation.kind);
+        let evaluation = Evaluation::evaluate("N(1/4)");
+        assert_eq!(Some("0.25".to_string()), evaluation.result);
+        assert_eq!(Some(false), evaluation.approximate);
+        assert_eq!(Some(ValueKind::Fraction), evaluation.kind);
+        assert_eq!(Some(ValueKind::Approximate), Evaluation::evaluate("N(√2, 5)").kind);
+        assert_eq!(Some(false), Evaluation::evaluate("π / 2").approximate);
+        assert_eq!(None, Evaluation::evaluate("1 / 0").approximate);
+    }
+
+    #[test]
+    fn evaluate_parse_error_has_span() {
+        let evaluation = Evaluation::evaluate(" 1 $ 2");
+        let error = evaluation.error.unwrap();
+        assert_eq!("unsupported_character", error.code);
+        assert_eq!("対応していない文字です", error.message);
+        assert_eq!(Some(Span::new(3, 4)), error.span);
+        assert_eq!(None, evaluation.result);
+    }
+
+    #[test]
+    fn evaluate_number_error_spans_token() {
+        let error = Evaluation::evaluate("1 + 23 45").error.unwrap();
+        assert_eq!("consecutive_numbers", error.code);
+        assert_eq!(Some(Span::new(7, 9)), error.span);
+    }
+
+    #[test]
+    fn evaluate_rejects_assignment() {
+        let error = Evaluation::evaluate("x = 1").error.unwrap();
+        assert_eq!("unsupported_character", error.code);
+        assert_eq!(Some(Span::new(2, 3)), error.span);
+    }
+
+    #[test]
+    fn evaluate_calculation_error_spans_token() {
+        let error = Evaluation::evaluate("1 / 0").error.unwrap();
+        assert_eq!("division_by_zero", error.code);
+        assert_eq!(Some(Span::new(2, 3)), error.span);
+        let error = Evaluation::evaluate("2 * (1 + months)").error.unwrap();
+        assert_eq!("undefined_variable", error.code);
+        assert_eq!(Some(Span::new(9, 15)), error.span);
+        let error = Evaluation::evaluate("sqrt(-4)").error.unwrap();
+        assert_eq!(Some(Span::new(0, 4)), error.span);
+    }
+
+    #[test]
+    fn evaluation_to_json() {
+        let json: Value = serde_json::from_str(
+            &Evaluation::evaluate("1 / 2").to_json().unwrap()).unwrap();
+        assert_eq!(json!({
+            "input": "1 / 2",
+            "expression": "1 / 2",
+            "result": "1 / 2",
+            "decimal": "0.5",
+            "approximate": false,
+            "kind": "fraction",
+            "error": null,
+        }), json);
+    }
+
+    #[test]
+    fn parse_ast() {
+        let result = ParseResult::parse("-(1 + x) * 2");
+        assert_eq!(Some(json!({
+            "type": "binary",
+            "operator": "*",
+            "operands": [
+                {
+                    "type": "unary",
+                    "operator": "-",
+                    "operands": [{
+                        "type": "paren",
+                        "expression": {
+                            "type": "binary",
+                            "operator": "+",
+                            "operands": [
+                                { "type": "number", "value": "1" },
+                                { "type": "variable", "name": "x" },
+                            ],
+                        },
+                    }],
+                },
+                { "type": "number", "value": "2" },
+            ],
+        })), result.ast);
+        assert_eq!(None, result.error);
+    }
+
+    #[test]
+    fn parse_function_ast() {
+        let expected = Some(json!({
+            "type": "function",
+            "name": "sqrt",
+            "arguments": [{ "type": "number", "value": "8" }],
+        }));
+        assert_eq!(expected, ParseResult::parse("sqrt(8)").ast);
+        assert_eq!(expected, ParseResult::parse("√8").ast);
+        assert_eq!(Some(ValueKind::Radical), Evaluation::evaluate("√8").kind);
+    }
+
+    #[test]
+    fn parse_function_arguments_ast() {
+        let expected = Some(json!({
+            "type": "function",
+            "name": "log",
+            "arguments": [
+                { "type": "number", "value": "2" },
+                {
+                    "type": "binary",
+                    "operator": "*",
+                    "operands": [
+                        { "type": "number", "value": "4" },
+                        { "type": "number", "value": "8" },
+                    ],
+                },
+            ],
+        }));
+        assert_eq!(expected, ParseResult::parse("log(2, 4 * 8)").ast);
+    }
+
+    #[test]
+    fn parse_degree_ast() {
+        let expected = Some(json!({
+            "type": "function",
+            "name": "sin",
+            "arguments": [{ "type": "degree", "value": "30" }],
+        }));
+        assert_eq!(expected, ParseResult::parse("sin(30°)").ast);
+    }
+
+    #[test]
+    fn parse_unclosed_paren() {
+        let error = ParseResult::parse("(1 + 2").error.unwrap();
+        assert_eq!("unclosed_paren", error.code);
+    }
+}
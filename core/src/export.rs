@@ -0,0 +1,186 @@
+use super::history_entry::{History, HistoryEntry};
+
+const EXPORT_COLUMNS: [&str; 6] = [
+    "input", "expression", "result", "decimal", "approximate", "error",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    Json,
+    Csv,
+    Markdown,
+}
+
+impl ExportFormat {
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "history.json",
+            ExportFormat::Csv => "history.csv",
+            ExportFormat::Markdown => "history.md",
+        }
+    }
+
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "application/json",
+            ExportFormat::Csv => "text/csv",
+            ExportFormat::Markdown => "text/markdown",
+        }
+    }
+}
+
+impl History {
+    // Like the other result columns, `approximate` is empty for errors.
+    fn export_columns(entry: &HistoryEntry) -> [&str; 6] {
+        let approximate = match (&entry.result, entry.approximate) {
+            (None, _) => "",
+            (Some(_), true) => "true",
+            (Some(_), false) => "false",
+        };
+        [
+            &entry.input,
+            entry.expression.as_deref().unwrap_or(""),
+            entry.result.as_deref().unwrap_or(""),
+            entry.decimal.as_deref().unwrap_or(""),
+            approximate,
+            entry.error.as_deref().unwrap_or(""),
+        ]
+    }
+
+    fn escape_csv(value: &str) -> String {
+        if value.contains(|c| matches!(c, ',' | '"' | '\n' | '\r')) {
+            format!("\"{}\"", value.replace('"', "\"\""))
+        } else {
+            value.to_string()
+        }
+    }
+
+    fn escape_markdown(value: &str) -> String {
+        value
+            .replace('|', "\\|")
+            .replace("\r\n", "<br>")
+            .replace('\n', "<br>")
+    }
+
+    pub fn export_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self.entries())
+            .map_err(|_| "履歴のエクスポートに失敗しました".to_string())
+    }
+
+    pub fn export_csv(&self) -> String {
+        let mut lines = vec![EXPORT_COLUMNS.join(",")];
+        for entry in self.entries() {
+            let columns: Vec<String> = Self::export_columns(entry)
+                .iter()
+                .map(|value| Self::escape_csv(value))
+                .collect();
+            lines.push(columns.join(","));
+        }
+        lines.join("\r\n") + "\r\n"
+    }
+
+    pub fn export_markdown(&self) -> String {
+        let mut lines = vec![
+            format!("| {} |", EXPORT_COLUMNS.join(" | ")),
+            format!("|{}", " --- |".repeat(EXPORT_COLUMNS.len())),
+        ];
+        for entry in self.entries() {
+            let columns: Vec<String> = Self::export_columns(entry)
+                .iter()
+                .map(|value| Self::escape_markdown(value))
+                .collect();
+            lines.push(format!("| {} |", columns.join(" | ")));
+        }
+        lines.join("\n") + "\n"
+    }
+
+    pub fn export(&self, format: ExportFormat) -> Result<String, String> {
+        match format {
+            ExportFormat::Json => self.export_json(),
+            ExportFormat::Csv => Ok(self.export_csv()),
+            ExportFormat::Markdown => Ok(self.export_markdown()),
+        }
+    }
+
+    pub fn import_json(value: &str) -> Result<Vec<HistoryEntry>, String> {
+        let entries: Vec<HistoryEntry> = serde_json::from_str(value)
+            .map_err(|_| "履歴のインポートに失敗しました".to_string())?;
+        for entry in &entries {
+            if entry.error.is_none() && entry.calculation().is_err() {
+                return Err("インポートできない履歴が含まれています".to_string());
+            }
+        }
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Calculation;
+
+    fn history() -> History {
+        let entries = ["1/2 + 3/4", "π + 1", "1, 2", "1 / 0"]
+            .iter()
+            .map(|input| HistoryEntry::new(input, &Calculation::from_input(input)))
+            .collect();
+        History::new(entries)
+    }
+
+    #[test]
+    fn export_csv_normal() {
+        let expected = "input,expression,result,decimal,approximate,error\r\n\
+            1/2 + 3/4,1 / 2 + 3 / 4,5 / 4,1.25,false,\r\n\
+            π + 1,π + 1,4.1415926536,4.1415926536,true,\r\n\
+            \"1, 2\",,,,,カンマは関数の引数の区切りにだけ使えます\r\n\
+            1 / 0,,,,,0で割ることはできません\r\n";
+        assert_eq!(expected, history().export_csv());
+    }
+
+    #[test]
+    fn export_csv_quote() {
+        assert_eq!("\"a\"\"b\"", History::escape_csv("a\"b"));
+    }
+
+    #[test]
+    fn export_markdown_normal() {
+        let expected = "| input | expression | result | decimal | approximate | error |\n\
+            | --- | --- | --- | --- | --- | --- |\n\
+            | 1/2 + 3/4 | 1 / 2 + 3 / 4 | 5 / 4 | 1.25 | false |  |\n\
+            | π + 1 | π + 1 | 4.1415926536 | 4.1415926536 | true |  |\n\
+            | 1, 2 |  |  |  |  | カンマは関数の引数の区切りにだけ使えます |\n\
+            | 1 / 0 |  |  |  |  | 0で割ることはできません |\n";
+        assert_eq!(expected, history().export_markdown());
+    }
+
+    #[test]
+    fn export_markdown_escape_pipe() {
+        assert_eq!("1 \\| 2<br>3", History::escape_markdown("1 | 2\n3"));
+    }
+
+    #[test]
+    fn export_json_round_trip() {
+        let value = history().export_json().unwrap();
+        let actual = History::import_json(&value).unwrap();
+        assert_eq!(history().entries(), actual);
+    }
+
+    #[test]
+    fn import_json_invalid_entry() {
+        let value = r#"[{"input": "1 + 2", "expression": null, "result": null, "error": null}]"#;
+        let expected = "インポートできない履歴が含まれています";
+        match History::import_json(value) {
+            Ok(_) => panic!("should be error"),
+            Err(e) => assert_eq!(expected, e),
+        }
+    }
+
+    #[test]
+    fn import_json_invalid_value() {
+        let expected = "履歴のインポートに失敗しました";
+        match History::import_json("not json") {
+            Ok(_) => panic!("should be error"),
+            Err(e) => assert_eq!(expected, e),
+        }
+    }
+}
@@ -0,0 +1,121 @@
+mod parse;
+mod display;
+mod calculate;
+mod json;
+mod precise;
+mod conversion;
+
+use super::term::Term;
+use super::operator::Operator;
+use super::node::Node;
+use super::error::{ErrorKind, TokenError};
+
+#[derive(Debug, Clone)]
+pub struct Expression {
+    ast: Node<Term>,
+}
+
+enum TermType {
+    Num,
+    Operator,
+    Paren,
+}
+
+impl Expression {
+    pub fn new(ast: Node<Term>) -> Self {
+        Self {
+            ast,
+        }
+    }
+
+    fn get_term_type(node: &Node<Term>) -> Result<TermType, ErrorKind> {
+        match node.value().as_ref() {
+            Some(Term::Num(_)) => Ok(TermType::Num),
+            Some(Term::Variable(_)) => Ok(TermType::Num),
+            Some(Term::Degree(_)) => Ok(TermType::Num),
+            Some(Term::Literal(..)) => Ok(TermType::Num),
+            Some(Term::Operator(_)) => Ok(TermType::Operator),
+            Some(Term::Paren(_)) => Ok(TermType::Paren),
+            _ => Err(ErrorKind::SyntaxError),
+        }
+    }
+
+    // Errors raised by `node` itself point at its token.
+    fn token_error(node: &Node<Term>) -> impl Fn(ErrorKind) -> TokenError {
+        let span = node.span();
+        move |kind| TokenError { kind, span }
+    }
+
+    fn is_unary_operator(node: &Node<Term>) -> Result<bool, ErrorKind> {
+        match node.value().as_ref() {
+            Some(Term::Operator(op)) => Ok(op.is_unary()),
+            _ => Err(ErrorKind::SyntaxError),
+        }
+    }
+
+    // Arguments of a function node: the comma-separated expressions inside
+    // its parentheses, or the single value after `√`.
+    fn function_arguments(node: &Node<Term>) -> Result<Vec<Node<Term>>, ErrorKind> {
+        fn split_commas(node: &Node<Term>, arguments: &mut Vec<Node<Term>>) {
+            if let Some(Term::Operator(Operator::Comma)) = node.value().as_ref() {
+                for child in node.children().iter() {
+                    split_commas(child, arguments);
+                }
+            } else {
+                arguments.push(node.clone());
+            }
+        }
+
+        let argument = match node.children().first() {
+            Some(argument) if node.len_children() == 1 => argument.clone(),
+            _ => return Err(ErrorKind::SyntaxError),
+        };
+        if !matches!(argument.value().as_ref(), Some(Term::Paren(_))) {
+            return Ok(vec![argument]);
+        }
+        if !Self::is_right_paren(&argument)? {
+            return Err(ErrorKind::UnclosedParen);
+        }
+        let mut arguments = Vec::new();
+        for child in argument.children().iter() {
+            split_commas(child, &mut arguments);
+        }
+        Ok(arguments)
+    }
+
+    fn is_right_paren(node: &Node<Term>) -> Result<bool, ErrorKind> {
+        match node.value().as_ref() {
+            Some(Term::Paren(paren)) => Ok(!paren.is_left()),
+            _ => Err(ErrorKind::SyntaxError),
+        }
+    }
+}
+
+impl std::fmt::Display for Expression {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.display())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::num::Num;
+
+    #[test]
+    fn test_parse() {
+        let input = "1 + 2 * 3 - 1";
+        let expression = Expression::parse(input).unwrap();
+        println!("{} = {}", input, expression.display());
+        assert_eq!(expression.display(), input);
+    }
+
+    #[test]
+    fn test_calculate() {
+        let input = "-1 + 2 * 3 - 1";
+        let expression = Expression::parse(input).unwrap();
+        let result = expression.calculate().unwrap();
+        println!("{} = {}", input, result);
+        assert_eq!(result, Term::Num(Num::I32(4)));
+    }
+}
@@ -0,0 +1,92 @@
+use super::super::term::Term;
+use super::super::operator::Operator;
+use super::super::node::Node;
+use super::super::environment::Environment;
+use super::super::settings::Settings;
+use super::super::trigonometry;
+use super::{Expression, TermType};
+use super::super::error::{ErrorKind, TokenError};
+
+impl Expression {
+    pub(super) fn calculate_loop(
+        node: &Node<Term>,
+        environment: &Environment,
+        settings: &Settings,
+    ) -> Result<Term, TokenError> {
+        let at = Self::token_error(node);
+        let term_type = Self::get_term_type(node).map_err(&at)?;
+        match term_type {
+            TermType::Operator => {
+                if let Some(Term::Operator(Operator::Function(function))) = node.value().as_ref() {
+                    let arguments = Self::function_arguments(node)
+                        .map_err(&at)?
+                        .iter()
+                        .map(|argument| Self::calculate_loop(argument, environment, settings))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    return function.calculate(&arguments, settings).map_err(at);
+                }
+                if Self::is_unary_operator(node).map_err(&at)? {
+                    if node.len_children() != 1 {
+                        return Err(at(ErrorKind::SyntaxError));
+                    }
+                    let child_result = Self::calculate_loop(
+                        node.children().get(0).unwrap(), environment, settings)?;
+                    match node.value().as_ref().unwrap() {
+                        Term::Operator(operator) => {
+                            operator.calculate_unary(&child_result, settings).map_err(at)
+                        },
+                        _ => Err(at(ErrorKind::SyntaxError))
+                    }
+                } else {
+                    if node.len_children() != 2 {
+                        return Err(at(ErrorKind::SyntaxError));
+                    }
+                    // The right side of `in` names a radix, not a variable.
+                    if let Some(Term::Operator(Operator::In)) = node.value().as_ref() {
+                        return Err(at(ErrorKind::MisplacedConversion));
+                    }
+                    let left_result = Self::calculate_loop(
+                        node.children().get(0).unwrap(), environment, settings)?;
+                    let right_result = Self::calculate_loop(
+                        node.children().get(1).unwrap(), environment, settings)?;
+                    match node.value().as_ref().unwrap() {
+                        Term::Operator(operator) => {
+                            operator.calculate_binary(&left_result, &right_result, settings).map_err(at)
+                        },
+                        _ => Err(at(ErrorKind::SyntaxError))
+                    }
+                }
+            },
+            TermType::Num => {
+                if node.len_children() != 0 {
+                    return Err(at(ErrorKind::SyntaxError));
+                }
+                match node.value().as_ref().unwrap() {
+                    Term::Variable(name) => environment.get(name).map(Term::Num),
+                    Term::Degree(num) => trigonometry::degree(num, settings.angle_unit).map(Term::Num),
+                    Term::Literal(num, _) => Ok(Term::Num(num.clone())),
+                    term => Ok(term.clone()),
+                }.map_err(at)
+            },
+            TermType::Paren => {
+                if !Self::is_right_paren(node).map_err(&at)? {
+                    return Err(at(ErrorKind::UnclosedParen));
+                }
+                if node.len_children() != 1 {
+                    return Err(at(ErrorKind::SyntaxError));
+                }
+                Self::calculate_loop(node.children().get(0).unwrap(), environment, settings)
+            },
+        }
+    }
+
+    #[cfg(test)]
+    pub fn calculate(&self) -> Result<Term, String> {
+        self.calculate_with(&Environment::default(), &Settings::default())
+            .map_err(|error| String::from(error.kind))
+    }
+
+    pub(crate) fn calculate_with(&self, environment: &Environment, settings: &Settings) -> Result<Term, TokenError> {
+        Self::calculate_loop(&self.ast, environment, settings)
+    }
+}
@@ -0,0 +1,36 @@
+use super::super::term::Term;
+use super::super::operator::Operator;
+use super::super::function::Function;
+use super::super::radix::Radix;
+use super::Expression;
+use super::super::error::ErrorKind;
+
+impl Expression {
+    /// The converted expression and the target radix when the whole
+    /// expression is `x in hex` or `to_hex(x)`.
+    pub fn radix_conversion(&self) -> Result<Option<(Self, Radix)>, ErrorKind> {
+        match self.ast.value().as_ref() {
+            Some(Term::Operator(Operator::In)) => {
+                let children = self.ast.children().clone();
+                let [argument, target] = children.as_slice() else {
+                    return Err(ErrorKind::SyntaxError);
+                };
+                let radix = match target.value().as_ref() {
+                    Some(Term::Variable(name)) if target.len_children() == 0 => Radix::parse(name),
+                    _ => None,
+                };
+                match radix {
+                    Some(radix) => Ok(Some((Self::new(argument.clone()), radix))),
+                    None => Err(ErrorKind::InvalidRadix),
+                }
+            },
+            Some(Term::Operator(Operator::Function(Function::ToRadix(radix)))) => {
+                match <[_; 1]>::try_from(Self::function_arguments(&self.ast)?) {
+                    Ok([argument]) => Ok(Some((Self::new(argument), *radix))),
+                    Err(_) => Err(ErrorKind::ArgumentCount),
+                }
+            },
+            _ => Ok(None),
+        }
+    }
+}
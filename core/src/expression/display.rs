@@ -1,16 +1,17 @@
 use super::super::term::Term;
 use super::super::node::Node;
 use super::{Expression, TermType};
+use super::super::error::ErrorKind;
 
 impl Expression {
-    fn display_loop(node: &Node<Term>) -> Result<Vec<String>, String> {
+    fn display_loop(node: &Node<Term>) -> Result<Vec<String>, ErrorKind> {
         let mut result = Vec::new();
         let term_type = Self::get_term_type(node)?;
         match term_type {
             TermType::Operator => {
                 if Self::is_unary_operator(node)? {
                     if node.len_children() != 1 {
-                        return Err("構文解析に失敗しました".to_string());
+                        return Err(ErrorKind::SyntaxError);
                     }
                     result.push(
                         node.value().as_ref().unwrap().to_string());
@@ -19,7 +20,7 @@ impl Expression {
                     result.extend(child_result);
                 } else {
                     if node.len_children() != 2 {
-                        return Err("構文解析に失敗しました".to_string());
+                        return Err(ErrorKind::SyntaxError);
                     }
                     let left_result = Self::display_loop(
                         node.children().get(0).unwrap())?;
@@ -33,16 +34,16 @@ impl Expression {
             },
             TermType::Num => {
                 if node.len_children() != 0 {
-                    return Err("構文解析に失敗しました".to_string());
+                    return Err(ErrorKind::SyntaxError);
                 }
                 result.push(node.value().as_ref().unwrap().to_string());
             }
             TermType::Paren => {
                 if !Self::is_right_paren(node)? {
-                    return Err("括弧が閉じられていません".to_string());
+                    return Err(ErrorKind::UnclosedParen);
                 }
                 if node.len_children() != 1 {
-                    return Err("構文解析に失敗しました".to_string());
+                    return Err(ErrorKind::SyntaxError);
                 }
                 result.push("(".to_string());
                 let child_result = Self::display_loop(
@@ -57,7 +58,7 @@ impl Expression {
     pub fn display(&self) -> String {
         let mut result = Self::display_loop(&self.ast);
         match result {
-            Err(_) => "構文解析に失敗しました".to_string(),
+            Err(_) => ErrorKind::SyntaxError.to_string(),
             Ok(ref mut result) => {
                 result.join(" ")
             },
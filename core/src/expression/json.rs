@@ -0,0 +1,82 @@
+use serde_json::{json, Value};
+use super::super::term::Term;
+use super::super::operator::Operator;
+use super::super::function::Function;
+use super::super::node::Node;
+use super::{Expression, TermType};
+use super::super::error::ErrorKind;
+
+impl Expression {
+    fn json_loop(node: &Node<Term>) -> Result<Value, ErrorKind> {
+        let term_type = Self::get_term_type(node)?;
+        if let Some(Term::Operator(Operator::Function(function))) = node.value().as_ref() {
+            return Self::function_json(function, node);
+        }
+        let children = node.children()
+            .iter()
+            .map(Self::json_loop)
+            .collect::<Result<Vec<_>, _>>()?;
+        let value = node.value().as_ref().unwrap().to_string();
+        match term_type {
+            TermType::Operator => {
+                let arity = if Self::is_unary_operator(node)? { 1 } else { 2 };
+                if children.len() != arity {
+                    return Err(ErrorKind::SyntaxError);
+                }
+                Ok(json!({
+                    "type": if arity == 1 { "unary" } else { "binary" },
+                    "operator": value,
+                    "operands": children,
+                }))
+            },
+            TermType::Num => {
+                if !children.is_empty() {
+                    return Err(ErrorKind::SyntaxError);
+                }
+                match node.value().as_ref() {
+                    Some(Term::Variable(_)) => Ok(json!({
+                        "type": "variable",
+                        "name": value,
+                    })),
+                    Some(Term::Degree(num)) => Ok(json!({
+                        "type": "degree",
+                        "value": num.to_string(),
+                    })),
+                    _ => Ok(json!({
+                        "type": "number",
+                        "value": value,
+                    })),
+                }
+            },
+            TermType::Paren => {
+                if !Self::is_right_paren(node)? {
+                    return Err(ErrorKind::UnclosedParen);
+                }
+                match <[Value; 1]>::try_from(children) {
+                    Ok([child]) => Ok(json!({
+                        "type": "paren",
+                        "expression": child,
+                    })),
+                    Err(_) => Err(ErrorKind::SyntaxError),
+                }
+            },
+        }
+    }
+
+    // `sqrt(2)` and `√2` have the same shape.
+    fn function_json(function: &Function, node: &Node<Term>) -> Result<Value, ErrorKind> {
+        let arguments = Self::function_arguments(node)?
+            .iter()
+            .map(Self::json_loop)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(json!({
+            "type": "function",
+            "name": function.name(),
+            "arguments": arguments,
+        }))
+    }
+
+    pub fn to_json(&self) -> Result<Value, ErrorKind> {
+        Self::json_loop(&self.ast)
+    }
+}
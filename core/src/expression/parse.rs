@@ -0,0 +1,398 @@
+use std::iter::Peekable;
+use std::str::Chars;
+use super::super::num::Num;
+use super::super::term::{Term, DEGREE_SIGN};
+use super::super::operator::Operator;
+use super::super::function::Function;
+use super::super::paren::Paren;
+use super::super::radix::Radix;
+use super::super::notation;
+use super::super::japanese;
+use super::super::grouping;
+use super::super::node::Node;
+use super::super::error::Span;
+use super::{Expression, TermType};
+use super::super::error::ErrorKind;
+
+impl Expression {
+    fn search_left_paren_loop(marker: Node<Term>) -> Result<Node<Term>, ErrorKind> {
+        match marker.parent() {
+            Some((parent, _)) => {
+                let recursive_flag = match parent.value().as_ref().unwrap() {
+                    Term::Paren(paren) => {
+                        !paren.is_left()
+                    },
+                    _ => true,
+                };
+                if recursive_flag {
+                    Self::search_left_paren_loop(parent)
+                } else {
+                    Ok(parent)
+                }
+            },
+            None => {
+                Err(ErrorKind::UnmatchedParen)
+            },
+        }
+    }
+
+    fn append_right_paren_to_node(marker: Node<Term>) -> Result<Node<Term>, ErrorKind> {
+        if let Term::Paren(paren) = marker.value().as_ref().unwrap() {
+            if paren.is_left() {
+                return Err(ErrorKind::EmptyParen);
+            }
+        }
+        let mut marker = Self::search_left_paren_loop(marker)?;
+        marker.set_value(Term::Paren(Paren::Right));
+        Ok(marker)
+    }
+
+    fn append_left_paren_to_node(mut marker: Node<Term>) -> Result<Node<Term>, ErrorKind> {
+        if marker.value().is_none() {
+            marker.set_value(Term::Paren(Paren::Left));
+            return Ok(marker);
+        }
+        let term_type = Self::get_term_type(&marker)?;
+        let mut marker = match term_type {
+            TermType::Num => {
+                Self::append_op_to_node(marker, Operator::Mul)?
+            },
+            TermType::Operator => {
+                marker
+            },
+            TermType::Paren => {
+                if Self::is_right_paren(&marker)? {
+                    Self::append_op_to_node(marker, Operator::Mul)?
+                } else {
+                    marker
+                }
+            },
+        };
+        let new_marker = Node::new(Some(Term::Paren(Paren::Left)));
+        marker.add_child(new_marker.clone());
+        Ok(new_marker)
+    }
+
+    fn append_to_ast_paren(marker: Node<Term>, c: char) -> Result<Node<Term>, ErrorKind> {
+        let paren = Paren::parse(c)?;
+
+        match paren {
+            Paren::Left => Self::append_left_paren_to_node(marker),
+            Paren::Right => Self::append_right_paren_to_node(marker),
+        }
+    }
+
+    fn append_op_to_node(marker: Node<Term>, op: Operator) -> Result<Node<Term>, ErrorKind> {
+        match marker.clone().parent() {
+            Some((mut parent, index)) => {
+                let recursive_flag = match parent.clone().value().as_ref().unwrap() {
+                    Term::Operator(parent_op) => {
+                        parent_op.priority() >= op.priority()
+                    },
+                    Term::Num(_) | Term::Variable(_) | Term::Degree(_) | Term::Literal(..) => {
+                        return Err(ErrorKind::SyntaxError)
+                    },
+                    Term::Paren(paren) => {
+                        if paren.is_left() {
+                            false
+                        } else {
+                            return Err(ErrorKind::SyntaxError)
+                        }
+                    },
+                };
+                if recursive_flag {
+                    Self::append_op_to_node(parent, op)
+                } else {
+                    let mut new_marker = Node::new(Some(Term::Operator(op)));
+                    new_marker.add_child(marker.clone());
+                    parent.replace_child(index, new_marker.clone());
+                    Ok(new_marker)
+                }
+            },
+            None => {
+                let mut new_marker = Node::new(Some(Term::Operator(op)));
+                new_marker.add_child(marker.clone());
+                Ok(new_marker)
+            },
+        }
+    }
+
+    fn append_to_ast_ops(mut marker: Node<Term>, symbol: &str) -> Result<Node<Term>, ErrorKind> {
+        let term_type = Self::get_term_type(&marker);
+        let op = match term_type {
+            Ok(TermType::Operator) => {
+                return Err(ErrorKind::ConsecutiveOperators);
+            }
+            Ok(TermType::Paren) => {
+                if Self::is_right_paren(&marker)? {
+                    Operator::parse(symbol, false)?
+                } else {
+                    let op = Operator::parse(symbol, true)?;
+                    let new_marker = Node::new(Some(Term::Operator(op)));
+                    marker.add_child(new_marker.clone());
+                    return Ok(new_marker);
+                }
+            },
+            Err(_) => {
+                let op = Operator::parse(symbol, true)?;
+                marker.set_value(Term::Operator(op));
+                return Ok(marker);
+            }
+            _ => Operator::parse(symbol, false)?
+        };
+        Self::append_op_to_node(marker, op)
+    }
+
+    fn append_to_ast_value(mut marker: Node<Term>, term: Term) -> Result<Node<Term>, ErrorKind> {
+        if marker.value().is_none() {
+            marker.set_value(term);
+            return Ok(marker);
+        }
+        let term_type = Self::get_term_type(&marker)?;
+        match term_type {
+            TermType::Num => {
+                let implicit_mul = matches!(
+                    (marker.value().as_ref(), &term),
+                    (Some(Term::Num(_) | Term::Literal(..)), Term::Variable(_))
+                        | (
+                            Some(Term::Num(_) | Term::Literal(..) | Term::Variable(_)),
+                            Term::Operator(Operator::Function(_)),
+                        )
+                );
+                if implicit_mul {
+                    let mut marker = Self::append_op_to_node(marker, Operator::Mul)?;
+                    let new_marker = Node::new(Some(term));
+                    marker.add_child(new_marker.clone());
+                    Ok(new_marker)
+                } else {
+                    Err(ErrorKind::ConsecutiveNumbers)
+                }
+            },
+            TermType::Operator => {
+                let new_marker = Node::new(Some(term));
+                marker.add_child(new_marker.clone());
+                Ok(new_marker)
+            },
+            TermType::Paren => {
+                if Self::is_right_paren(&marker)? {
+                    let mut marker = Self::append_op_to_node(marker, Operator::Mul)?;
+                    let new_marker = Node::new(Some(term));
+                    marker.add_child(new_marker.clone());
+                    Ok(new_marker)
+                } else {
+                    let new_marker = Node::new(Some(term));
+                    marker.add_child(new_marker.clone());
+                    Ok(new_marker)
+                }
+            },
+        }
+    }
+
+    fn append_to_ast_num(marker: Node<Term>, target: &mut String) -> Result<Node<Term>, ErrorKind> {
+        let term = match Self::parse_number(target)? {
+            (num, true) => Term::Literal(num, target.clone()),
+            (num, false) => Term::Num(num),
+        };
+        target.clear();
+        Self::append_to_ast_value(marker, term)
+    }
+
+    fn append_to_ast_degree(marker: Node<Term>, target: &mut String) -> Result<Node<Term>, ErrorKind> {
+        let (num, _) = Self::parse_number(target)?;
+        target.clear();
+        Self::append_to_ast_value(marker, Term::Degree(num))
+    }
+
+    // The value of a number literal, and whether it has to be shown as
+    // written because it has a decimal point, a unit or digit separators.
+    fn parse_number(literal: &str) -> Result<(Num, bool), ErrorKind> {
+        let plain = grouping::strip(literal);
+        if japanese::is_literal(&plain) {
+            Ok((japanese::parse(&plain)?, true))
+        } else if notation::is_notation(&plain) {
+            Ok((notation::parse(&plain)?, true))
+        } else {
+            Ok((Num::parse(&plain)?, plain != literal))
+        }
+    }
+
+    // `next` is the first non-space character after the identifier, which
+    // must open the argument list when the identifier names a function.
+    fn append_to_ast_identifier(
+        marker: Node<Term>,
+        target: &mut String,
+        next: Option<char>,
+    ) -> Result<Node<Term>, ErrorKind> {
+        let name = target.clone();
+        target.clear();
+        if let Some(op) = Operator::parse_keyword(&name) {
+            return Self::append_to_ast_keyword(marker, op);
+        }
+        match Function::parse(&name) {
+            Some(_) if next != Some('(') => Err(ErrorKind::MissingFunctionParen),
+            Some(function) => Self::append_to_ast_function(marker, function),
+            None => Self::append_to_ast_value(marker, Term::Variable(name)),
+        }
+    }
+
+    // Operators written as words, like `in`, follow a value.
+    fn append_to_ast_keyword(marker: Node<Term>, op: Operator) -> Result<Node<Term>, ErrorKind> {
+        match Self::get_term_type(&marker) {
+            Ok(TermType::Num) => Self::append_op_to_node(marker, op),
+            Ok(TermType::Paren) if Self::is_right_paren(&marker)? => Self::append_op_to_node(marker, op),
+            _ => Err(ErrorKind::MissingOperand),
+        }
+    }
+
+    // Functions are prefix operators that bind tighter than anything else,
+    // and like values they may follow a number without `*`.
+    fn append_to_ast_function(marker: Node<Term>, function: Function) -> Result<Node<Term>, ErrorKind> {
+        Self::append_to_ast_value(marker, Term::Operator(Operator::Function(function)))
+    }
+
+    // A comma inside the arguments of a function separates them rather than
+    // digits, so that `log(10,100)` still takes two arguments.
+    fn continues_number(target: &str, c: char, chars: &Peekable<Chars>, marker: &Node<Term>) -> bool {
+        let ahead: Vec<char> = chars.clone().skip(1).take(4).collect();
+        let (next, after) = (ahead.first().copied(), ahead.get(1).copied());
+        Radix::continues_literal(target, c)
+            || notation::continues_literal(target, c, next, after)
+            || japanese::continues_literal(target, c, next)
+            || (grouping::continues_literal(target, c, &ahead) && (c == '_' || !Self::is_in_arguments(marker)))
+    }
+
+    fn is_in_arguments(marker: &Node<Term>) -> bool {
+        let mut node = marker.clone();
+        loop {
+            if let Some(Term::Paren(Paren::Left)) = node.value().as_ref() {
+                return node.parent().is_some_and(|(parent, _)| {
+                    matches!(parent.value().as_ref(), Some(Term::Operator(Operator::Function(_))))
+                });
+            }
+            match node.parent() {
+                Some((parent, _)) => node = parent,
+                None => return false,
+            }
+        }
+    }
+
+    fn is_identifier_start(c: char) -> bool {
+        c.is_ascii_alphabetic() || c == '_' || c == 'π'
+    }
+
+    pub(crate) fn is_identifier_continue(c: char) -> bool {
+        c.is_ascii_alphanumeric() || c == '_'
+    }
+
+    pub fn is_identifier(name: &str) -> bool {
+        let mut chars = name.chars();
+        match chars.next() {
+            Some(c) => Self::is_identifier_start(c) && chars.all(Self::is_identifier_continue),
+            None => false,
+        }
+    }
+
+    #[cfg(test)]
+    pub fn parse(input: &str) -> Result<Self, String> {
+        Self::parse_with_span(input).map_err(|(error, _)| error.to_string())
+    }
+
+    pub fn parse_with_span(input: &str) -> Result<Self, (ErrorKind, Span)> {
+        let mut root = Node::<Term>::new(None);
+        let mut marker = root.clone();
+
+        let mut chars = input.chars().peekable();
+
+        let mut target_str = String::new();
+        let mut identifier = String::new();
+        let mut position = 0;
+        let mut token_start = 0;
+        while let Some(&c) = chars.peek() {
+            let token_span = Span::new(token_start, position);
+            let char_span = Span::new(position, position + c.len_utf16());
+            if !identifier.is_empty() && Self::is_identifier_continue(c) {
+                identifier.push(c);
+            } else if c == DEGREE_SIGN && !target_str.is_empty() {
+                let token_span = Span::new(token_start, position + c.len_utf16());
+                marker = Self::append_to_ast_degree(marker, &mut target_str)
+                    .map_err(|e| (e, token_span))?;
+                marker.set_span(token_span);
+            } else if c.is_ascii_digit() || Self::continues_number(&target_str, c, &chars, &marker) {
+                if target_str.is_empty() {
+                    token_start = position;
+                }
+                target_str.push(c);
+            } else {
+                if !target_str.is_empty() {
+                    marker = Self::append_to_ast_num(marker, &mut target_str)
+                        .map_err(|e| (e, token_span))?;
+                    marker.set_span(token_span);
+                }
+                if !identifier.is_empty() {
+                    let next = chars.clone().find(|c| !c.is_whitespace());
+                    marker = Self::append_to_ast_identifier(marker, &mut identifier, next)
+                        .map_err(|e| (e, token_span))?;
+                    marker.set_span(token_span);
+                }
+                if Self::is_identifier_start(c) {
+                    token_start = position;
+                    identifier.push(c);
+                } else if c.is_whitespace() {
+                    // skip
+                } else if Operator::is_operator(c) {
+                    marker = Self::append_to_ast_ops(marker, &c.to_string())
+                        .map_err(|e| (e, char_span))?;
+                    marker.set_span(char_span);
+                } else if Operator::is_shift(c) {
+                    // `<<` and `>>` take the next character as well.
+                    let mut rest = chars.clone();
+                    rest.next();
+                    if rest.peek() != Some(&c) {
+                        return Err((ErrorKind::UnsupportedCharacter, char_span));
+                    }
+                    let symbol_span = Span::new(position, position + 2);
+                    marker = Self::append_to_ast_ops(marker, &format!("{}{}", c, c))
+                        .map_err(|e| (e, symbol_span))?;
+                    marker.set_span(symbol_span);
+                    position += c.len_utf16();
+                    chars.next();
+                } else if let Some(function) = Function::from_symbol(c) {
+                    marker = Self::append_to_ast_function(marker, function)
+                        .map_err(|e| (e, char_span))?;
+                    marker.set_span(char_span);
+                } else if c == DEGREE_SIGN {
+                    return Err((ErrorKind::MisplacedDegreeSign, char_span));
+                } else if Paren::is_paren(c) {
+                    marker = Self::append_to_ast_paren(marker, c)
+                        .map_err(|e| (e, char_span))?;
+                    // A right parenthesis closes the node of the left one,
+                    // which keeps pointing at where it opened.
+                    let is_left = matches!(marker.value().as_ref(), Some(Term::Paren(Paren::Left)));
+                    if is_left {
+                        marker.set_span(char_span);
+                    }
+                } else {
+                    return Err((ErrorKind::UnsupportedCharacter, char_span));
+                }
+            }
+            position += c.len_utf16();
+            chars.next();
+        }
+
+        let token_span = Span::new(token_start, position);
+        if !target_str.is_empty() {
+            marker = Self::append_to_ast_num(marker, &mut target_str)
+                .map_err(|e| (e, token_span))?;
+            marker.set_span(token_span);
+        }
+        if !identifier.is_empty() {
+            marker = Self::append_to_ast_identifier(marker, &mut identifier, None)
+                .map_err(|e| (e, token_span))?;
+            marker.set_span(token_span);
+        }
+
+        root = marker.root();
+
+        Ok(Self::new(root))
+    }
+}
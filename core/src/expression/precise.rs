@@ -0,0 +1,107 @@
+use super::super::term::Term;
+use super::super::node::Node;
+use super::super::operator::Operator;
+use super::super::function::Function;
+use super::super::environment::Environment;
+use super::super::settings::Settings;
+use super::super::decimal::Decimal;
+use super::super::trigonometry;
+use super::{Expression, TermType};
+use super::super::error::{ErrorKind, TokenError};
+
+impl Expression {
+    fn calculate_precise_loop(
+        node: &Node<Term>,
+        environment: &Environment,
+        settings: &Settings,
+        scale: u32,
+    ) -> Result<Decimal, TokenError> {
+        let calculate = |node: &Node<Term>| {
+            Self::calculate_precise_loop(node, environment, settings, scale)
+        };
+        let at = Self::token_error(node);
+        match Self::get_term_type(node).map_err(&at)? {
+            TermType::Operator => {
+                let operator = match node.value().as_ref() {
+                    Some(Term::Operator(operator)) => operator.clone(),
+                    _ => return Err(at(ErrorKind::SyntaxError)),
+                };
+                if let Operator::Function(function) = operator {
+                    let arguments = Self::function_arguments(node)
+                        .map_err(&at)?
+                        .iter()
+                        .map(calculate)
+                        .collect::<Result<Vec<_>, _>>()?;
+                    return function.calculate_precise(&arguments, settings).map_err(at);
+                }
+                // Bitwise results are integers, so they are already exact.
+                if operator.is_bitwise() {
+                    return match Self::calculate_loop(node, environment, settings)? {
+                        Term::Num(num) => Decimal::from_num(&num, scale).map_err(at),
+                        _ => Err(at(ErrorKind::SyntaxError)),
+                    };
+                }
+                let operands = node.children()
+                    .iter()
+                    .map(calculate)
+                    .collect::<Result<Vec<_>, _>>()?;
+                match (operator, operands.as_slice()) {
+                    (Operator::Plus, [x]) => Ok(x.clone()),
+                    (Operator::Minus, [x]) => Ok(x.minus()),
+                    (Operator::Add, [x, y]) => Ok(x.add(y)),
+                    (Operator::Sub, [x, y]) => Ok(x.sub(y)),
+                    (Operator::Mul, [x, y]) => x.mul(y),
+                    (Operator::Div, [x, y]) => x.div(y),
+                    (Operator::Comma, [_, _]) => Err(ErrorKind::MisplacedComma),
+                    _ => Err(ErrorKind::SyntaxError),
+                }.map_err(at)
+            },
+            TermType::Num => {
+                if node.len_children() != 0 {
+                    return Err(at(ErrorKind::SyntaxError));
+                }
+                let num = match node.value().as_ref().unwrap() {
+                    Term::Num(num) => Ok(num.clone()),
+                    Term::Variable(name) => environment.get(name),
+                    Term::Degree(num) => trigonometry::degree(num, settings.angle_unit),
+                    Term::Literal(num, _) => Ok(num.clone()),
+                    _ => Err(ErrorKind::SyntaxError),
+                };
+                num.and_then(|num| Decimal::from_num(&num, scale)).map_err(at)
+            },
+            TermType::Paren => {
+                if !Self::is_right_paren(node).map_err(&at)? {
+                    return Err(at(ErrorKind::UnclosedParen));
+                }
+                match node.children().first() {
+                    Some(child) if node.len_children() == 1 => calculate(child),
+                    _ => Err(at(ErrorKind::SyntaxError)),
+                }
+            },
+        }
+    }
+
+    /// Evaluates with `scale` digits after the decimal point instead of f64
+    /// for the parts that cannot be calculated exactly.
+    pub(crate) fn calculate_precise(
+        &self,
+        environment: &Environment,
+        settings: &Settings,
+        scale: u32,
+    ) -> Result<Decimal, TokenError> {
+        Self::calculate_precise_loop(&self.ast, environment, settings, scale)
+    }
+
+    /// The argument and the optional number of digits when the whole
+    /// expression is `N(x, digits)`.
+    pub fn numeric_arguments(&self) -> Result<Option<(Self, Option<Self>)>, ErrorKind> {
+        if !matches!(self.ast.value().as_ref(), Some(Term::Operator(Operator::Function(Function::Numeric)))) {
+            return Ok(None);
+        }
+        let mut arguments = Self::function_arguments(&self.ast)?.into_iter().map(Self::new);
+        match (arguments.next(), arguments.next(), arguments.next()) {
+            (Some(argument), digits, None) => Ok(Some((argument, digits))),
+            _ => Err(ErrorKind::ArgumentCount),
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use num::{BigInt, BigRational, Signed};
+use serde::{Serialize, Deserialize};
+use super::num::Num;
+
+/// Rational number that is not an integer, always irreducible and with a
+/// positive denominator.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Fraction {
+    value: BigRational,
+}
+
+impl Fraction {
+    /// `value` as an integer when its denominator is one, and as a
+    /// `Fraction` otherwise.
+    pub fn new_result(value: BigRational) -> Num {
+        if value.is_integer() {
+            return Num::from_bigint(value.to_integer());
+        }
+        Num::Fraction(Box::new(Self { value }))
+    }
+
+    pub fn value(&self) -> &BigRational {
+        &self.value
+    }
+
+    pub fn numerator(&self) -> Num {
+        Num::from_bigint(self.value.numer().clone())
+    }
+
+    pub fn denominator(&self) -> Num {
+        Num::from_bigint(self.value.denom().clone())
+    }
+
+    pub fn is_minus(&self) -> bool {
+        self.value.is_negative()
+    }
+
+    pub fn to_decimal(&self, digits: u32) -> String {
+        let numerator = self.value.numer();
+        let denominator = self.value.denom();
+        let scale = num::pow(BigInt::from(10), digits as usize);
+        let scaled = numerator.abs() * &scale;
+        let mut quotient = &scaled / denominator;
+        if (scaled % denominator) * 2 >= *denominator {
+            quotient += 1;
+        }
+        let integer = &quotient / &scale;
+        let decimal = format!("{:0>width$}", (quotient % scale).to_string(), width = digits as usize);
+        let decimal = decimal.trim_end_matches('0');
+        let sign = if numerator.is_negative() { "-" } else { "" };
+        if decimal.is_empty() {
+            format!("{}{}", sign, integer)
+        } else {
+            format!("{}{}.{}", sign, integer, decimal)
+        }
+    }
+}
+
+impl std::fmt::Display for Fraction {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} / {}", self.value.numer(), self.value.denom())
+    }
+}
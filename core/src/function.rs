@@ -0,0 +1,157 @@
+use super::num::Num;
+use super::term::Term;
+use super::settings::Settings;
+use super::trigonometry;
+use super::logarithm;
+use super::decimal::Decimal;
+use super::radix::Radix;
+use super::error::ErrorKind;
+
+/// Function applied to the value that follows it, like `sqrt(2)` or `√2`.
+/// Functions of several arguments take them separated by commas, as in
+/// `log(2, 8)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Function {
+    Sqrt,
+    Sin,
+    Cos,
+    Tan,
+    Asin,
+    Acos,
+    Atan,
+    Ln,
+    Log,
+    Log10,
+    Log2,
+    Exp,
+    // `N(x, digits)`, which is only valid as a whole expression.
+    Numeric,
+    // `to_hex(x)` and the like, which are only valid as a whole expression.
+    ToRadix(Radix),
+}
+
+impl Function {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "sqrt" => Some(Function::Sqrt),
+            "sin" => Some(Function::Sin),
+            "cos" => Some(Function::Cos),
+            "tan" => Some(Function::Tan),
+            "asin" => Some(Function::Asin),
+            "acos" => Some(Function::Acos),
+            "atan" => Some(Function::Atan),
+            "ln" => Some(Function::Ln),
+            "log" => Some(Function::Log),
+            "log10" => Some(Function::Log10),
+            "log2" => Some(Function::Log2),
+            "exp" => Some(Function::Exp),
+            "N" => Some(Function::Numeric),
+            "to_hex" => Some(Function::ToRadix(Radix::Hexadecimal)),
+            "to_dec" => Some(Function::ToRadix(Radix::Decimal)),
+            "to_oct" => Some(Function::ToRadix(Radix::Octal)),
+            "to_bin" => Some(Function::ToRadix(Radix::Binary)),
+            _ => None,
+        }
+    }
+
+    pub fn from_symbol(c: char) -> Option<Self> {
+        match c {
+            '√' => Some(Function::Sqrt),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Function::Sqrt => "sqrt",
+            Function::Sin => "sin",
+            Function::Cos => "cos",
+            Function::Tan => "tan",
+            Function::Asin => "asin",
+            Function::Acos => "acos",
+            Function::Atan => "atan",
+            Function::Ln => "ln",
+            Function::Log => "log",
+            Function::Log10 => "log10",
+            Function::Log2 => "log2",
+            Function::Exp => "exp",
+            Function::Numeric => "N",
+            Function::ToRadix(Radix::Hexadecimal) => "to_hex",
+            Function::ToRadix(Radix::Decimal) => "to_dec",
+            Function::ToRadix(Radix::Octal) => "to_oct",
+            Function::ToRadix(Radix::Binary) => "to_bin",
+        }
+    }
+
+    fn check_arguments<T>(&self, arguments: &[T]) -> Result<(), ErrorKind> {
+        let arities = match self {
+            Function::Log => 2..=2,
+            Function::Numeric => 1..=2,
+            _ => 1..=1,
+        };
+        if !arities.contains(&arguments.len()) {
+            return Err(ErrorKind::ArgumentCount);
+        }
+        Ok(())
+    }
+
+    pub fn calculate(&self, arguments: &[Term], settings: &Settings) -> Result<Term, ErrorKind> {
+        self.check_arguments(arguments)?;
+        let arguments = arguments
+            .iter()
+            .map(|argument| match argument {
+                Term::Num(num) => Ok(num),
+                _ => Err(ErrorKind::InvalidOperation),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let num = arguments[0];
+        let unit = settings.angle_unit;
+        match self {
+            Function::Sqrt => num.sqrt(),
+            Function::Sin => trigonometry::sin(num, unit),
+            Function::Cos => trigonometry::cos(num, unit),
+            Function::Tan => trigonometry::tan(num, unit),
+            Function::Asin => trigonometry::asin(num, unit),
+            Function::Acos => trigonometry::acos(num, unit),
+            Function::Atan => trigonometry::atan(num, unit),
+            Function::Ln => logarithm::ln(num),
+            Function::Log => logarithm::log(num, arguments[1]),
+            Function::Log10 => logarithm::log(&Num::I32(10), num),
+            Function::Log2 => logarithm::log(&Num::I32(2), num),
+            Function::Exp => logarithm::exp(num),
+            Function::Numeric => Err(ErrorKind::MisplacedNumeric),
+            Function::ToRadix(_) => Err(ErrorKind::MisplacedConversion),
+        }.map(Term::Num)
+    }
+
+    pub fn calculate_precise(&self, arguments: &[Decimal], settings: &Settings) -> Result<Decimal, ErrorKind> {
+        self.check_arguments(arguments)?;
+        let x = &arguments[0];
+        let unit = settings.angle_unit;
+        match self {
+            Function::Sqrt => x.sqrt(),
+            Function::Sin => trigonometry::sin_precise(x, unit),
+            Function::Cos => trigonometry::cos_precise(x, unit),
+            Function::Tan => trigonometry::tan_precise(x, unit),
+            Function::Asin => trigonometry::asin_precise(x, unit),
+            Function::Acos => trigonometry::acos_precise(x, unit),
+            Function::Atan => trigonometry::atan_precise(x, unit),
+            Function::Ln => x.ln(),
+            Function::Log => logarithm::log_precise(x, &arguments[1]),
+            Function::Log10 => logarithm::log_precise(&Decimal::from_integer(10, x.scale()), x),
+            Function::Log2 => logarithm::log_precise(&Decimal::from_integer(2, x.scale()), x),
+            Function::Exp => x.exp(),
+            Function::Numeric => Err(ErrorKind::MisplacedNumeric),
+            Function::ToRadix(_) => Err(ErrorKind::MisplacedConversion),
+        }
+    }
+}
+
+impl std::fmt::Display for Function {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Function::Sqrt => write!(f, "√"),
+            function => write!(f, "{}", function.name()),
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use super::settings::Locale;
+use super::japanese;
+
+/// Whether `c` separates groups of digits in literals, as in `1,000,000`,
+/// `1_000_000` or `1，000`.
+pub fn is_separator(c: char) -> bool {
+    matches!(c, ',' | '_' | '，')
+}
+
+/// Whether `c` continues the literal `target` as a digit separator.
+/// `ahead` are the characters following `c`. `_` may stand between any
+/// two digits, but a comma must start a group of exactly three digits
+/// after a group of at most three, so that `1,2` stays two values.
+pub fn continues_literal(target: &str, c: char, ahead: &[char]) -> bool {
+    if !is_separator(c) || !target.starts_with(|c: char| c.is_ascii_digit()) {
+        return false;
+    }
+    // Separators group the digits of a plain integer, or of each part of a
+    // number written in units of ten thousand like `1,200万`.
+    let start = match target.char_indices().rev().find(|(_, c)| !c.is_ascii_digit() && !is_separator(*c)) {
+        None => 0,
+        Some((index, unit)) if japanese::is_unit(unit) => index + unit.len_utf8(),
+        Some(_) => return false,
+    };
+    let integer = &target[start..];
+    if !integer.ends_with(|c: char| c.is_ascii_digit()) {
+        return false;
+    }
+    if c == '_' {
+        return ahead.first().is_some_and(char::is_ascii_digit);
+    }
+    if integer.contains(|other: char| is_separator(other) && other != c) {
+        return false;
+    }
+    let group = integer.rsplit(c).next().unwrap_or(integer);
+    let group_fits = match integer.contains(c) {
+        true => group.len() == 3,
+        false => group.len() <= 3,
+    };
+    group_fits
+        && ahead.len() >= 3
+        && ahead[..3].iter().all(char::is_ascii_digit)
+        && !ahead.get(3).is_some_and(char::is_ascii_digit)
+}
+
+/// `literal` without its digit separators.
+pub fn strip(literal: &str) -> String {
+    literal.chars().filter(|c| !is_separator(*c)).collect()
+}
+
+impl Locale {
+    /// `text` with the integer part of every plain decimal in it, like
+    /// `1234567.5`, grouped as `1,234,567.5` or `1.234.567,5`. Other
+    /// words, like `0xFF` or `3億`, are left as they are.
+    pub fn group(&self, text: &str) -> String {
+        text.split(' ')
+            .map(|word| self.group_decimal(word).unwrap_or_else(|| word.to_string()))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn group_decimal(&self, word: &str) -> Option<String> {
+        let (sign, magnitude) = match word.strip_prefix('-') {
+            Some(magnitude) => ("-", magnitude),
+            None => ("", word),
+        };
+        let (integer, fraction) = match magnitude.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (magnitude, None),
+        };
+        let is_digits = |digits: &str| !digits.is_empty() && digits.bytes().all(|c| c.is_ascii_digit());
+        if !is_digits(integer) || !fraction.map_or(true, is_digits) {
+            return None;
+        }
+        let (separator, decimal_mark) = match self {
+            Locale::English | Locale::Indian => (",", '.'),
+            Locale::German => (".", ','),
+            Locale::French => ("\u{202F}", ','),
+        };
+        // Indian grouping puts the first three digits together and the rest
+        // in pairs, as in 12,34,567.
+        let mut groups = Vec::new();
+        let mut end = integer.len();
+        let mut size = 3;
+        while end > 0 {
+            let start = end.saturating_sub(size);
+            groups.push(&integer[start..end]);
+            end = start;
+            if *self == Locale::Indian {
+                size = 2;
+            }
+        }
+        groups.reverse();
+        let mut text = format!("{}{}", sign, groups.join(separator));
+        if let Some(fraction) = fraction {
+            text.push(decimal_mark);
+            text.push_str(fraction);
+        }
+        Some(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn group_separates_thousands() {
+        assert_eq!("1,234,567", Locale::English.group("1234567"));
+        assert_eq!("-1.234.567,25", Locale::German.group("-1234567.25"));
+        assert_eq!("1\u{202F}000 / 7", Locale::French.group("1000 / 7"));
+        assert_eq!("12,34,56,789", Locale::Indian.group("123456789"));
+        assert_eq!("999", Locale::English.group("999"));
+        assert_eq!("0xFFFF", Locale::English.group("0xFFFF"));
+        assert_eq!("2√2", Locale::English.group("2√2"));
+        assert_eq!("3億2000万", Locale::English.group("3億2000万"));
+    }
+}
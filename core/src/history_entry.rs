@@ -0,0 +1,160 @@
+use serde::{Serialize, Deserialize};
+use super::calculation::Calculation;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub input: String,
+    pub expression: Option<String>,
+    pub result: Option<String>,
+    #[serde(default)]
+    pub decimal: Option<String>,
+    /// The value a click inserts, kept only when it differs from `result`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    pub error: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub approximate: bool,
+}
+
+impl HistoryEntry {
+    pub fn new(input: &str, result: &Result<Calculation, String>) -> Self {
+        match result {
+            Ok(calculation) => Self {
+                input: input.to_string(),
+                expression: Some(calculation.expression.clone()),
+                result: Some(calculation.result.clone()),
+                decimal: Some(calculation.decimal.clone()),
+                value: Some(calculation.value.clone()).filter(|value| *value != calculation.result),
+                error: None,
+                approximate: calculation.approximate,
+            },
+            Err(error) => Self {
+                input: input.to_string(),
+                expression: None,
+                result: None,
+                decimal: None,
+                value: None,
+                error: Some(error.clone()),
+                approximate: false,
+            },
+        }
+    }
+
+    pub fn calculation(&self) -> Result<Calculation, String> {
+        match (&self.expression, &self.result, &self.error) {
+            (Some(expression), Some(result), None) => Ok(Calculation {
+                expression: expression.clone(),
+                result: result.clone(),
+                decimal: self.decimal.clone().unwrap_or_else(|| result.clone()),
+                value: self.value.clone().unwrap_or_else(|| result.clone()),
+                approximate: self.approximate,
+            }),
+            (_, _, Some(error)) => Err(error.clone()),
+            _ => Err("履歴の形式が正しくありません".to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct History {
+    entries: Vec<HistoryEntry>,
+}
+
+impl History {
+    pub fn new(entries: Vec<HistoryEntry>) -> Self {
+        Self { entries }
+    }
+
+    pub fn entries(&self) -> &[HistoryEntry] {
+        &self.entries
+    }
+
+    pub fn extend(&mut self, entries: &[HistoryEntry]) {
+        self.entries.extend_from_slice(entries);
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn cap(&mut self, max: usize) {
+        if self.entries.len() > max {
+            self.entries.drain(..self.entries.len() - max);
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string(&self.entries)
+            .map_err(|_| "履歴の保存に失敗しました".to_string())
+    }
+
+    pub fn from_json(value: &str) -> Result<Self, String> {
+        serde_json::from_str(value)
+            .map(Self::new)
+            .map_err(|_| "履歴の読み込みに失敗しました".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::environment::Environment;
+    use crate::settings::Settings;
+
+    fn entry(input: &str) -> HistoryEntry {
+        let result = Calculation::calculate(input, &mut Environment::default(), &Settings::default());
+        HistoryEntry::new(input, &result)
+    }
+
+    #[test]
+    fn history_keeps_value_in_other_notation() {
+        let settings = Settings { notation: crate::settings::Notation::Kanji, ..Settings::default() };
+        let result = Calculation::calculate("300 + 20", &mut Environment::default(), &settings);
+        let history = History::new(vec![HistoryEntry::new("300 + 20", &result), entry("1 + 2")]);
+        let value = history.to_json().unwrap();
+        assert_eq!(1, value.matches("\"value\"").count());
+        let actual = History::from_json(&value).unwrap();
+        assert_eq!("320", actual.entries()[0].calculation().unwrap().value);
+        assert_eq!("3", actual.entries()[1].calculation().unwrap().value);
+    }
+
+    #[test]
+    fn history_round_trip() {
+        let history = History::new(vec![entry("1 + 2"), entry("1 / 0"), entry("π + 1")]);
+        let value = history.to_json().unwrap();
+        let actual = History::from_json(&value).unwrap();
+        assert_eq!(history, actual);
+        assert!(actual.entries()[2].approximate);
+    }
+
+    #[test]
+    fn history_without_approximate_flag_is_exact() {
+        let history = History::from_json(
+            r#"[{"input":"1","expression":"1","result":"1","decimal":"1","error":null}]"#).unwrap();
+        assert!(!history.entries()[0].approximate);
+    }
+
+    #[test]
+    fn history_error_entry_keeps_error() {
+        let actual = entry("1 / 0");
+        assert_eq!(actual.expression, None);
+        assert_eq!(actual.result, None);
+        assert_eq!(actual.error, Some("0で割ることはできません".to_string()));
+    }
+
+    #[test]
+    fn history_invalid_value() {
+        let expected = "履歴の読み込みに失敗しました";
+        match History::from_json("{") {
+            Ok(_) => panic!("should be error"),
+            Err(e) => assert_eq!(expected, e),
+        }
+    }
+
+    #[test]
+    fn history_cap_keeps_latest() {
+        let mut history = History::new(vec![entry("1"), entry("2"), entry("3")]);
+        history.cap(2);
+        assert_eq!(&[entry("2"), entry("3")], history.entries());
+    }
+}
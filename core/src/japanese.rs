@@ -0,0 +1,216 @@
+use num::BigInt;
+use super::num::Num;
+use super::grouping;
+use super::error::ErrorKind;
+
+// Each unit is ten thousand times the previous one.
+const UNITS: [(char, u32); 12] = [
+    ('万', 4), ('億', 8), ('兆', 12), ('京', 16), ('垓', 20), ('秭', 24),
+    ('穣', 28), ('溝', 32), ('澗', 36), ('正', 40), ('載', 44), ('極', 48),
+];
+// Digits of the largest integer part that the units can write, up to
+// 9999極.
+const MAX_INTEGER_DIGITS: usize = UNITS[UNITS.len() - 1].1 as usize + 4;
+const KANJI_DIGITS: [char; 10] = ['〇', '一', '二', '三', '四', '五', '六', '七', '八', '九'];
+const KANJI_PLACES: [&str; 4] = ["", "十", "百", "千"];
+
+fn unit_exponent(c: char) -> Option<u32> {
+    UNITS.iter().find(|(unit, _)| *unit == c).map(|(_, exponent)| *exponent)
+}
+
+/// Whether `c` is 万, 億 or another unit of ten thousand.
+pub fn is_unit(c: char) -> bool {
+    unit_exponent(c).is_some()
+}
+
+/// Whether `literal` contains a unit of ten thousand.
+pub fn has_unit(literal: &str) -> bool {
+    literal.chars().any(is_unit)
+}
+
+/// Whether `c` continues the literal `target` written in units of ten
+/// thousand, as in `3億2000万` or `1.5万`. Each unit must follow a digit and
+/// be smaller than the units before it.
+pub fn continues_literal(target: &str, c: char, next: Option<char>) -> bool {
+    let is_part = |c: char| c.is_ascii_digit() || c == '.' || is_unit(c) || grouping::is_separator(c);
+    if !target.starts_with(|c: char| c.is_ascii_digit()) || !target.chars().all(is_part) {
+        return false;
+    }
+    let group = target.rsplit(is_unit).next().unwrap_or(target);
+    match (c, unit_exponent(c)) {
+        ('.', _) => {
+            has_unit(target) && !group.is_empty() && !group.contains('.')
+                && next.is_some_and(|c| c.is_ascii_digit())
+        },
+        (_, Some(exponent)) => {
+            target.ends_with(|c: char| c.is_ascii_digit())
+                && target.chars().filter_map(unit_exponent).all(|before| before > exponent)
+        },
+        _ => false,
+    }
+}
+
+/// Whether `literal` needs `parse` rather than plain integer parsing.
+pub fn is_literal(literal: &str) -> bool {
+    literal.starts_with(|c: char| c.is_ascii_digit()) && has_unit(literal)
+}
+
+/// The exact value of a literal accepted by `continues_literal`.
+pub fn parse(literal: &str) -> Result<Num, ErrorKind> {
+    let invalid = || ErrorKind::InvalidNumber;
+    let mut groups = Vec::new();
+    let mut start = 0;
+    for (index, c) in literal.char_indices() {
+        if let Some(exponent) = unit_exponent(c) {
+            groups.push((&literal[start..index], exponent));
+            start = index + c.len_utf8();
+        }
+    }
+    if start < literal.len() {
+        groups.push((&literal[start..], 0));
+    }
+    let mut terms = Vec::new();
+    for (group, exponent) in groups {
+        let fraction_digits = group.split_once('.').map_or(0, |(_, fraction)| fraction.len() as i32);
+        let digits = group.replace('.', "").parse::<BigInt>().map_err(|_| invalid())?;
+        terms.push((digits, exponent as i32 - fraction_digits));
+    }
+    let exponent = terms.iter().map(|(_, exponent)| *exponent).min().ok_or_else(invalid)?;
+    let total = terms.into_iter().fold(BigInt::from(0), |total, (digits, e)| {
+        total + digits * num::pow(BigInt::from(10), (e - exponent) as usize)
+    });
+    Num::from_decimal(total, exponent)
+}
+
+fn split_decimal(decimal: &str) -> (&str, &str, &str) {
+    let (sign, magnitude) = match decimal.strip_prefix('-') {
+        Some(magnitude) => ("-", magnitude),
+        None => ("", decimal),
+    };
+    let (integer, fraction) = magnitude.split_once('.').unwrap_or((magnitude, ""));
+    (sign, integer, fraction)
+}
+
+// The digits of `integer` in groups of four from the right, the most
+// significant first, each with its unit and without leading zeros.
+fn groups(integer: &str) -> Vec<(&str, Option<char>)> {
+    let mut groups = Vec::new();
+    let mut end = integer.len();
+    let mut units = std::iter::once(None).chain(UNITS.iter().map(|(unit, _)| Some(*unit))).peekable();
+    while let Some(unit) = units.next() {
+        let start = if units.peek().is_some() { end.saturating_sub(4) } else { 0 };
+        groups.push((integer[start..end].trim_start_matches('0'), unit));
+        end = start;
+        if end == 0 {
+            break;
+        }
+    }
+    groups.reverse();
+    groups
+}
+
+// The non-zero groups of `integer`, each written by `write` and followed
+// by its unit.
+fn join_groups(integer: &str, write: impl Fn(&str) -> String) -> String {
+    let mut text = String::new();
+    for (group, unit) in groups(integer).into_iter().filter(|(group, _)| !group.is_empty()) {
+        text.push_str(&write(group));
+        text.extend(unit);
+    }
+    text
+}
+
+/// Whether the integer part of the plain decimal `decimal` can be written
+/// with the units, that is below 10^52.
+pub fn fits_units(decimal: &str) -> bool {
+    split_decimal(decimal).1.len() <= MAX_INTEGER_DIGITS
+}
+
+/// Rewrites a plain decimal like `320005000.5` in units of ten thousand,
+/// as `3億5000.5`.
+pub fn myriad(decimal: &str) -> String {
+    let (sign, integer, fraction) = split_decimal(decimal);
+    let mut text = join_groups(integer, str::to_string);
+    if text.is_empty() || (!fraction.is_empty() && !text.ends_with(|c: char| c.is_ascii_digit())) {
+        text.push('0');
+    }
+    if !fraction.is_empty() {
+        text = format!("{}.{}", text, fraction);
+    }
+    format!("{}{}", sign, text)
+}
+
+fn kanji_digits(digits: &str) -> String {
+    digits.bytes().map(|digit| KANJI_DIGITS[(digit - b'0') as usize]).collect()
+}
+
+// One group of up to four digits, like 三千二十 for 3020. `一` is left
+// out before 十, 百 and 千.
+fn kanji_group(group: &str) -> String {
+    if group.len() > KANJI_PLACES.len() {
+        return kanji_digits(group);
+    }
+    group.bytes()
+        .map(|digit| (digit - b'0') as usize)
+        .zip(KANJI_PLACES.iter().take(group.len()).rev())
+        .filter(|(digit, _)| *digit != 0)
+        .map(|(digit, place)| match (digit, *place) {
+            (1, place) if !place.is_empty() => place.to_string(),
+            (digit, place) => format!("{}{}", KANJI_DIGITS[digit], place),
+        })
+        .collect()
+}
+
+/// Rewrites a plain decimal like `320` in kanji numerals, as `三百二十`.
+/// Digits after the decimal point follow `・` one by one.
+pub fn kanji(decimal: &str) -> String {
+    let (sign, integer, fraction) = split_decimal(decimal);
+    let mut text = join_groups(integer, kanji_group);
+    if text.is_empty() {
+        text.push(KANJI_DIGITS[0]);
+    }
+    if !fraction.is_empty() {
+        text = format!("{}・{}", text, kanji_digits(fraction));
+    }
+    format!("{}{}", sign, text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn myriad_groups_by_ten_thousand() {
+        assert_eq!("3億2000万", myriad("320000000"));
+        assert_eq!("3億5000.5", myriad("300005000.5"));
+        assert_eq!("1万2345.6", myriad("12345.6"));
+        assert_eq!("-1兆1", myriad("-1000000000001"));
+        assert_eq!("1844京6744兆737億955万1615", myriad("18446744073709551615"));
+        assert_eq!("1億0.5", myriad("100000000.5"));
+        assert_eq!("0.25", myriad("0.25"));
+        assert_eq!("0", myriad("0"));
+        assert_eq!("1秭", myriad(&format!("1{}", "0".repeat(24))));
+        assert_eq!("1極2", myriad(&format!("1{}2", "0".repeat(47))));
+    }
+
+    #[test]
+    fn kanji_reads_each_place() {
+        assert_eq!("三百二十", kanji("320"));
+        assert_eq!("千十一", kanji("1011"));
+        assert_eq!("一万二千三百四十五", kanji("12345"));
+        assert_eq!("三億二千万", kanji("320000000"));
+        assert_eq!("三・一四", kanji("3.14"));
+        assert_eq!("-五", kanji("-5"));
+        assert_eq!("〇", kanji("0"));
+        assert_eq!("一秭", kanji(&format!("1{}", "0".repeat(24))));
+        assert_eq!("三溝二穣", kanji(&format!("30002{}", "0".repeat(28))));
+        assert_eq!("九千九百九十九極", kanji(&format!("9999{}", "0".repeat(48))));
+    }
+
+    #[test]
+    fn units_end_at_goku() {
+        assert!(fits_units(&format!("{}.5", "9".repeat(52))));
+        assert!(!fits_units(&format!("1{}", "0".repeat(52))));
+        assert!(!fits_units(&format!("-1{}", "0".repeat(52))));
+    }
+}
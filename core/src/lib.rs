@@ -0,0 +1,44 @@
+mod num;
+mod fraction;
+mod symbolic;
+mod radical;
+mod function;
+mod trigonometry;
+mod logarithm;
+mod decimal;
+mod radix;
+mod bitwise;
+mod machine;
+mod notation;
+mod japanese;
+mod grouping;
+mod term;
+mod operator;
+mod paren;
+mod node;
+mod expression;
+mod environment;
+mod settings;
+mod error;
+mod calculation;
+mod evaluation;
+mod document;
+mod script;
+mod history_entry;
+mod export;
+
+pub use calculation::Calculation;
+pub use environment::Environment;
+pub use settings::{AngleUnit, BitWidth, Locale, Notation, Settings};
+pub use radix::Radix;
+pub use error::{CalculationError, Span};
+pub use evaluation::{Evaluation, ParseResult, ValueKind};
+pub use document::{Document, DocumentLine, LineResult};
+pub use script::{Script, ScriptLine, Statement, ScriptError};
+pub use history_entry::{History, HistoryEntry};
+pub use export::ExportFormat;
+
+const DECIMAL_DIGITS: u32 = 10;
+const MAX_STORED_HISTORY: usize = 200;
+const PRECISION: u32 = 50;
+const MAX_PRECISION: u32 = 10000;
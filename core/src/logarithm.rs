@@ -0,0 +1,97 @@
+use num::{BigInt, BigRational};
+use super::num::Num;
+use super::decimal::Decimal;
+use super::symbolic::{Constant, Symbolic};
+use super::error::ErrorKind;
+
+fn e() -> Num {
+    Num::Symbolic(Box::new(Symbolic::new(Num::I32(1), Constant::E)))
+}
+
+fn check_argument(x: &Num) -> Result<(), ErrorKind> {
+    if x.is_minus() || x.is_zero() {
+        return Err(ErrorKind::NonPositiveLogarithm);
+    }
+    Ok(())
+}
+
+fn check_base(base: &Num) -> Result<(), ErrorKind> {
+    if base.is_minus() || base.is_zero() || base.to_f64()? == 1.0 {
+        return Err(ErrorKind::InvalidLogarithmBase);
+    }
+    Ok(())
+}
+
+fn integer_root(n: &BigInt, exponent: u32) -> Option<BigInt> {
+    let root = n.nth_root(exponent);
+    (num::pow(root.clone(), exponent as usize) == *n).then_some(root)
+}
+
+// Splits a positive rational other than 1 into `(r, k)` with `value = r^k`
+// and `k` as large as possible. No exponent beyond the bit length of the
+// numerator or the denominator leaves a root other than 1.
+fn perfect_power(value: &BigRational) -> (BigRational, u32) {
+    let bits = value.numer().bits().max(value.denom().bits()) as u32;
+    for exponent in (2..=bits).rev() {
+        if let (Some(p), Some(q)) = (
+            integer_root(value.numer(), exponent),
+            integer_root(value.denom(), exponent),
+        ) {
+            return (BigRational::new(p, q), exponent);
+        }
+    }
+    (value.clone(), 1)
+}
+
+// log_b(x) is rational exactly when b and x are powers of a common root.
+fn exact_log(base: &Num, x: &Num) -> Result<Option<Num>, ErrorKind> {
+    if x.is_one() {
+        return Ok(Some(Num::I32(0)));
+    }
+    if base == x {
+        return Ok(Some(Num::I32(1)));
+    }
+    let (Some(base), Some(x)) = (base.to_rational(), x.to_rational()) else {
+        return Ok(None);
+    };
+    let (base_root, base_exponent) = perfect_power(&base);
+    let (root, exponent) = perfect_power(&x);
+    let exponent = if root == base_root {
+        exponent as i32
+    } else if root == base_root.recip() {
+        -(exponent as i32)
+    } else {
+        return Ok(None);
+    };
+    Num::I32(exponent).div(&Num::I32(base_exponent as i32)).map(Some)
+}
+
+pub fn log(base: &Num, x: &Num) -> Result<Num, ErrorKind> {
+    check_base(base)?;
+    check_argument(x)?;
+    if let Some(value) = exact_log(base, x)? {
+        return Ok(value);
+    }
+    Num::from_f64(x.to_f64()?.ln() / base.to_f64()?.ln())
+}
+
+pub fn ln(x: &Num) -> Result<Num, ErrorKind> {
+    log(&e(), x)
+}
+
+pub fn exp(x: &Num) -> Result<Num, ErrorKind> {
+    if x.is_exact() && x.is_zero() {
+        return Ok(Num::I32(1));
+    }
+    if x.is_one() {
+        return Ok(e());
+    }
+    Num::from_f64(x.to_f64()?.exp())
+}
+
+pub fn log_precise(base: &Decimal, x: &Decimal) -> Result<Decimal, ErrorKind> {
+    if base.is_minus() || base.is_zero() || base.is_one() {
+        return Err(ErrorKind::InvalidLogarithmBase);
+    }
+    x.ln()?.div(&base.ln()?)
+}
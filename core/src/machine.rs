@@ -0,0 +1,58 @@
+use super::num::Num;
+use super::operator::Operator;
+use super::settings::BitWidth;
+use super::error::ErrorKind;
+
+fn to_integer(num: &Num, width: BitWidth) -> Result<i128, ErrorKind> {
+    num.to_bigint().map(|num| width.wrap_integer(&num)).ok_or(ErrorKind::NonIntegerProgrammer)
+}
+
+// Arithmetic of programmer mode, where integers behave like the machine
+// type `width`: results wrap around and division truncates toward zero.
+// `None` leaves other operators to their usual calculation.
+pub fn calculate_binary(
+    operator: &Operator,
+    x: &Num,
+    y: &Num,
+    width: BitWidth,
+) -> Option<Result<Num, ErrorKind>> {
+    if !matches!(operator, Operator::Add | Operator::Sub | Operator::Mul | Operator::Div) {
+        return None;
+    }
+    let (x, y) = match (to_integer(x, width), to_integer(y, width)) {
+        (Ok(x), Ok(y)) => (x, y),
+        (Err(e), _) | (_, Err(e)) => return Some(Err(e)),
+    };
+    let num = match operator {
+        Operator::Add => x.wrapping_add(y),
+        Operator::Sub => x.wrapping_sub(y),
+        // Products of 64-bit values overflow i128, but not their low bits.
+        Operator::Mul => x.wrapping_mul(y),
+        Operator::Div if y == 0 => return Some(Err(ErrorKind::DivisionByZero)),
+        Operator::Div => x / y,
+        _ => return None,
+    };
+    Some(Ok(Num::from_i128(width.wrap(num))))
+}
+
+pub fn calculate_unary(operator: &Operator, x: &Num, width: BitWidth) -> Option<Result<Num, ErrorKind>> {
+    if !matches!(operator, Operator::Plus | Operator::Minus) {
+        return None;
+    }
+    let x = match to_integer(x, width) {
+        Ok(x) => x,
+        Err(e) => return Some(Err(e)),
+    };
+    let num = match operator {
+        Operator::Plus => x,
+        Operator::Minus => -x,
+        _ => return None,
+    };
+    Some(Ok(Num::from_i128(width.wrap(num))))
+}
+
+/// `num` as the machine type `width`. Values like `1.5` or `π` have no
+/// such type.
+pub fn wrap(num: Num, width: BitWidth) -> Result<Num, ErrorKind> {
+    Ok(Num::from_i128(to_integer(&num, width)?))
+}
@@ -1,11 +1,14 @@
 use std::rc::Rc;
 use std::cell::{RefCell, Ref};
+use super::error::Span;
 
 #[derive(Debug, Clone)]
 struct NodeInner<T> {
     value: Option<T>,
     children: Vec<Node<T>>,
     parent: Option<(Node<T>, usize)>,
+    // The token in the input that the value came from, when there is one.
+    span: Option<Span>,
 }
 
 #[derive(Debug)]
@@ -21,6 +24,7 @@ impl<T> Node<T> {
                     value,
                     children: Vec::new(),
                     parent: None,
+                    span: None,
                 }))
         }
     }
@@ -35,6 +39,14 @@ impl<T> Node<T> {
         inner.value = Some(value);
     }
 
+    pub fn span(&self) -> Option<Span> {
+        self.inner.borrow().span
+    }
+
+    pub fn set_span(&mut self, span: Span) {
+        self.inner.borrow_mut().span = Some(span);
+    }
+
     pub fn children(&self) -> Ref<Vec<Node<T>>> {
         let inner = self.inner.borrow();
         Ref::map(inner, |inner| &inner.children)
@@ -70,11 +82,8 @@ impl<T> Node<T> {
 
     pub fn root(&self) -> Node<T> {
         let mut node = self.clone();
-        loop {
-            match node.parent() {
-                Some((parent, _)) => node = parent,
-                None => break,
-            }
+        while let Some((parent, _)) = node.parent() {
+            node = parent;
         }
         node
     }
@@ -0,0 +1,149 @@
+use num::BigInt;
+use super::num::Num;
+use super::expression::Expression;
+use super::radix::Radix;
+use super::settings::Notation;
+use super::japanese;
+use super::error::ErrorKind;
+
+const SI_PREFIXES: [(char, i32); 10] = [
+    ('p', -12), ('n', -9), ('u', -6), ('µ', -6), ('μ', -6),
+    ('m', -3), ('k', 3), ('M', 6), ('G', 9), ('T', 12),
+];
+
+fn si_exponent(c: char) -> Option<i32> {
+    SI_PREFIXES.iter().find(|(prefix, _)| *prefix == c).map(|(_, exponent)| *exponent)
+}
+
+// `u` is only accepted in input; results use the micro sign.
+fn si_prefix(exponent: i32) -> Option<char> {
+    match exponent {
+        -6 => Some('µ'),
+        0 => None,
+        exponent => SI_PREFIXES.iter().find(|(_, e)| *e == exponent).map(|(prefix, _)| *prefix),
+    }
+}
+
+/// Whether `c` continues the decimal literal `target`, as in `1.5`,
+/// `6.02e23`, `1.5E-3` or `470n`. `next` and `after` are the characters
+/// following `c`. An `e` is only an exponent when digits follow it, so
+/// that `2e` stays `2 * e`, and an SI prefix must end the token, so that
+/// `2min` stays `2 * min`. The prefix wins over a variable of the same
+/// name: `2k` is 2000 even when `k` is assigned.
+pub fn continues_literal(target: &str, c: char, next: Option<char>, after: Option<char>) -> bool {
+    let is_digit = |c: Option<char>| c.map_or(false, |c| c.is_ascii_digit());
+    let Some(last) = target.chars().last() else {
+        return false;
+    };
+    if !is_decimal(target) || si_exponent(last).is_some() {
+        return false;
+    }
+    let has_exponent = target.contains(['e', 'E']);
+    match c {
+        '.' => !target.contains('.') && !has_exponent && is_digit(next),
+        'e' | 'E' if !has_exponent => {
+            is_digit(next) || (matches!(next, Some('+' | '-')) && is_digit(after))
+        },
+        '+' | '-' => matches!(last, 'e' | 'E') && is_digit(next),
+        c if si_exponent(c).is_some() => !next.map_or(false, Expression::is_identifier_continue),
+        _ => false,
+    }
+}
+
+fn is_decimal(literal: &str) -> bool {
+    literal.starts_with(|c: char| c.is_ascii_digit())
+        && Radix::split_prefix(literal).0 == Radix::Decimal
+        && !japanese::has_unit(literal)
+}
+
+/// Whether `literal` needs `parse` rather than plain integer parsing.
+pub fn is_notation(literal: &str) -> bool {
+    let has_prefix = literal.chars().last().and_then(si_exponent).is_some();
+    is_decimal(literal) && (literal.contains(['.', 'e', 'E']) || has_prefix)
+}
+
+/// The exact value of a literal accepted by `continues_literal`.
+pub fn parse(literal: &str) -> Result<Num, ErrorKind> {
+    let invalid = || ErrorKind::InvalidNumber;
+    let prefix = literal.chars().last().and_then(|c| Some((c, si_exponent(c)?)));
+    let (literal, si) = match prefix {
+        Some((prefix, exponent)) => (literal.strip_suffix(prefix).unwrap_or(literal), exponent),
+        None => (literal, 0),
+    };
+    let (mantissa, exponent) = match literal.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, exponent.parse::<i32>().map_err(|_| invalid())?),
+        None => (literal, 0),
+    };
+    let fraction_digits = mantissa.split_once('.').map_or(0, |(_, fraction)| fraction.len() as i32);
+    let digits = mantissa.replace('.', "").parse::<BigInt>().map_err(|_| invalid())?;
+    let exponent = exponent.checked_add(si - fraction_digits).ok_or_else(invalid)?;
+    Num::from_decimal(digits, exponent)
+}
+
+/// Rewrites a plain decimal in `notation`. Japanese units end at 極, so
+/// larger numbers cannot be written in them.
+pub fn format(decimal: &str, notation: Notation) -> Result<String, ErrorKind> {
+    match notation {
+        Notation::Myriad | Notation::Kanji if !japanese::fits_units(decimal) => {
+            Err(ErrorKind::UnitOverflow)
+        },
+        Notation::Standard => Ok(decimal.to_string()),
+        Notation::Engineering => Ok(engineering(decimal, false)),
+        Notation::Si => Ok(engineering(decimal, true)),
+        Notation::Myriad => Ok(japanese::myriad(decimal)),
+        Notation::Kanji => Ok(japanese::kanji(decimal)),
+    }
+}
+
+/// Rewrites a plain decimal like `0.00047` with an exponent that is a
+/// multiple of 3, as `470e-6`, or with an SI prefix, as `470µ`, when
+/// `si` is set and one exists.
+pub fn engineering(decimal: &str, si: bool) -> String {
+    let (sign, magnitude) = match decimal.strip_prefix('-') {
+        Some(magnitude) => ("-", magnitude),
+        None => ("", decimal),
+    };
+    let (integer, fraction) = magnitude.split_once('.').unwrap_or((magnitude, ""));
+    let (digits, exponent) = match integer.trim_start_matches('0') {
+        "" => {
+            let significant = fraction.trim_start_matches('0');
+            let zeros = fraction.len() - significant.len();
+            (significant.to_string(), -(zeros as i32) - 1)
+        },
+        integer => (format!("{}{}", integer, fraction), integer.len() as i32 - 1),
+    };
+    let digits = digits.trim_end_matches('0');
+    if digits.is_empty() {
+        return "0".to_string();
+    }
+    let engineering_exponent = exponent - exponent.rem_euclid(3);
+    let integer_digits = (exponent - engineering_exponent + 1) as usize;
+    let digits = format!("{:0<width$}", digits, width = integer_digits);
+    let mantissa = match digits.split_at(integer_digits) {
+        (integer, "") => integer.to_string(),
+        (integer, fraction) => format!("{}.{}", integer, fraction),
+    };
+    let suffix = match (engineering_exponent, si.then(|| si_prefix(engineering_exponent)).flatten()) {
+        (0, _) => String::new(),
+        (_, Some(prefix)) => prefix.to_string(),
+        (exponent, None) => format!("e{}", exponent),
+    };
+    format!("{}{}{}", sign, mantissa, suffix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn engineering_moves_exponent_to_multiples_of_three() {
+        assert_eq!("470e-6", engineering("0.00047", false));
+        assert_eq!("470µ", engineering("0.00047", true));
+        assert_eq!("12.345k", engineering("12345", true));
+        assert_eq!("-1.5e3", engineering("-1500", false));
+        assert_eq!("42", engineering("42", true));
+        assert_eq!("100m", engineering("0.1", true));
+        assert_eq!("602e21", engineering("602000000000000000000000", true));
+        assert_eq!("0", engineering("0", true));
+    }
+}
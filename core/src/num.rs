@@ -0,0 +1,306 @@
+use num::{BigInt, BigRational, Signed, ToPrimitive, Zero};
+use serde::{Serialize, Deserialize};
+use super::fraction::Fraction;
+use super::symbolic::Symbolic;
+use super::radical::Radical;
+use super::radix::Radix;
+use super::DECIMAL_DIGITS;
+use super::error::ErrorKind;
+
+const F64_EXACT_LIMIT: f64 = 9007199254740992.0;
+// Powers of ten in literals like `1e-400` are kept exactly up to this.
+const MAX_EXPONENT: u32 = 10_000;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Num {
+    I32(i32),
+    Fraction(Box<Fraction>),
+    Symbolic(Box<Symbolic>),
+    Radical(Box<Radical>),
+    Real(f64),
+    /// Integer beyond int32, from literals and exact arithmetic.
+    /// Always outside the range of `I32`.
+    Wide(BigInt),
+}
+
+impl Num {
+    pub fn parse(input: &str) -> Result<Self, ErrorKind> {
+        let (radix, digits) = Radix::split_prefix(input);
+        match BigInt::parse_bytes(digits.as_bytes(), radix.value()) {
+            Some(num) => Ok(Self::from_bigint(num)),
+            None => Err(ErrorKind::InvalidNumber),
+        }
+    }
+
+    pub fn from_bigint(num: BigInt) -> Self {
+        match num.to_i32() {
+            Some(num) => Self::I32(num),
+            None => Self::Wide(num),
+        }
+    }
+
+    pub fn from_i128(num: i128) -> Self {
+        Self::from_bigint(BigInt::from(num))
+    }
+
+    /// `digits × 10^exponent`, as written in literals like `6.02e23`.
+    pub fn from_decimal(digits: BigInt, exponent: i32) -> Result<Self, ErrorKind> {
+        if exponent.unsigned_abs() > MAX_EXPONENT {
+            return Err(ErrorKind::TooManyDigits);
+        }
+        let scale = num::pow(BigInt::from(10), exponent.unsigned_abs() as usize);
+        Ok(match exponent >= 0 {
+            true => Self::from_bigint(digits * scale),
+            false => Fraction::new_result(BigRational::new(digits, scale)),
+        })
+    }
+
+    pub fn to_bigint(&self) -> Option<BigInt> {
+        match self {
+            Self::I32(num) => Some(BigInt::from(*num)),
+            Self::Wide(num) => Some(num.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn to_i128(&self) -> Option<i128> {
+        self.to_bigint()?.to_i128()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        match self {
+            Self::I32(num) => *num == 0,
+            Self::Real(num) => *num == 0.0,
+            _ => false,
+        }
+    }
+
+    pub fn is_one(&self) -> bool {
+        match self {
+            Self::I32(num) => *num == 1,
+            _ => false,
+        }
+    }
+
+    pub fn is_minus(&self) -> bool {
+        match self {
+            Self::I32(num) => *num < 0,
+            Self::Fraction(fraction) => fraction.is_minus(),
+            Self::Symbolic(symbolic) => symbolic.is_minus(),
+            Self::Radical(radical) => radical.is_minus(),
+            Self::Real(num) => *num < 0.0,
+            Self::Wide(num) => num.is_negative(),
+        }
+    }
+
+    pub fn is_exact(&self) -> bool {
+        !matches!(self, Self::Real(_))
+    }
+
+    pub fn is_integer(&self) -> bool {
+        matches!(self, Self::I32(_) | Self::Wide(_))
+    }
+
+    pub fn is_rational(&self) -> bool {
+        matches!(self, Self::I32(_) | Self::Wide(_) | Self::Fraction(_))
+    }
+
+    fn is_exact_zero(&self) -> bool {
+        self.is_exact() && self.is_zero()
+    }
+
+    pub fn to_i32(&self) -> Result<i32, ErrorKind> {
+        match self {
+            Self::I32(num) => Ok(*num),
+            _ => Err(ErrorKind::Int32Conversion),
+        }
+    }
+
+    pub fn to_rational(&self) -> Option<BigRational> {
+        match self {
+            Self::Fraction(fraction) => Some(fraction.value().clone()),
+            num => num.to_bigint().map(BigRational::from_integer),
+        }
+    }
+
+    pub fn to_f64(&self) -> Result<f64, ErrorKind> {
+        match self {
+            Self::I32(num) => Ok(*num as f64),
+            Self::Fraction(fraction) => fraction.value().to_f64().ok_or(ErrorKind::RealOverflow),
+            Self::Symbolic(symbolic) => symbolic.to_f64(),
+            Self::Radical(radical) => radical.to_f64(),
+            Self::Real(num) => Ok(*num),
+            Self::Wide(num) => num.to_f64().ok_or(ErrorKind::RealOverflow),
+        }
+    }
+
+    pub fn to_decimal(&self, digits: u32) -> Result<String, ErrorKind> {
+        match self {
+            Self::I32(num) => Ok(num.to_string()),
+            Self::Wide(num) => Ok(num.to_string()),
+            Self::Fraction(fraction) => Ok(fraction.to_decimal(digits)),
+            _ => Ok(Self::format_f64(self.to_f64()?, digits)),
+        }
+    }
+
+    fn format_f64(num: f64, digits: u32) -> String {
+        // Beyond 2^53 every f64 is an integer whose exact expansion has
+        // digits that were never significant, like 601999999999999995805696
+        // for 6.02e23; the shortest form that round-trips is shown instead.
+        if num.abs() >= F64_EXACT_LIMIT {
+            return format!("{}", num);
+        }
+        let decimal = format!("{:.*}", digits as usize, num);
+        let decimal = match decimal.contains('.') {
+            true => decimal.trim_end_matches('0').trim_end_matches('.'),
+            false => &decimal,
+        };
+        match decimal {
+            "-0" => "0".to_string(),
+            decimal => decimal.to_string(),
+        }
+    }
+
+    // Fallback for values that cannot be combined exactly, such as `π + 1`.
+    fn approximate<F>(&self, other: &Self, operation: F) -> Result<Self, ErrorKind>
+    where
+        F: Fn(f64, f64) -> f64,
+    {
+        Self::from_f64(operation(self.to_f64()?, other.to_f64()?))
+    }
+
+    pub fn from_f64(num: f64) -> Result<Self, ErrorKind> {
+        if !num.is_finite() {
+            return Err(ErrorKind::RealOverflow);
+        }
+        Ok(Self::Real(num))
+    }
+
+    // Integers and fractions are combined exactly as big rationals. `None`
+    // leaves two int32 values to their own arithmetic, which moves up to
+    // wide integers when it overflows, and other values to the rules of
+    // their kind.
+    fn rational_operation<F>(&self, other: &Self, operation: F) -> Option<Result<Self, ErrorKind>>
+    where
+        F: Fn(BigRational, BigRational) -> Result<BigRational, ErrorKind>,
+    {
+        if matches!((self, other), (Self::I32(_), Self::I32(_))) {
+            return None;
+        }
+        let (x, y) = (self.to_rational()?, other.to_rational()?);
+        Some(operation(x, y).map(Fraction::new_result))
+    }
+
+    pub fn add(&self, other: &Self) -> Result<Self, ErrorKind> {
+        if let Some(result) = self.rational_operation(other, |x, y| Ok(x + y)) {
+            return result;
+        }
+        match (self, other) {
+            (Self::I32(x), Self::I32(y)) => Ok(match x.checked_add(*y) {
+                Some(num) => Self::I32(num),
+                None => Self::from_i128(*x as i128 + *y as i128),
+            }),
+            (Self::Symbolic(x), Self::Symbolic(y)) if x.constant() == y.constant() => x.add(y),
+            (Self::Radical(x), Self::Radical(y)) if x.radicand() == y.radicand() => x.add(y),
+            _ if other.is_exact_zero() => Ok(self.clone()),
+            _ if self.is_exact_zero() => Ok(other.clone()),
+            _ => self.approximate(other, |x, y| x + y),
+        }
+    }
+
+    pub fn sub(&self, other: &Self) -> Result<Self, ErrorKind> {
+        if let Some(result) = self.rational_operation(other, |x, y| Ok(x - y)) {
+            return result;
+        }
+        match (self, other) {
+            (Self::I32(x), Self::I32(y)) => Ok(match x.checked_sub(*y) {
+                Some(num) => Self::I32(num),
+                None => Self::from_i128(*x as i128 - *y as i128),
+            }),
+            (Self::Symbolic(x), Self::Symbolic(y)) if x.constant() == y.constant() => x.sub(y),
+            (Self::Radical(x), Self::Radical(y)) if x.radicand() == y.radicand() => x.sub(y),
+            _ if other.is_exact_zero() => Ok(self.clone()),
+            _ if self.is_exact_zero() => other.minus(),
+            _ => self.approximate(other, |x, y| x - y),
+        }
+    }
+
+    pub fn mul(&self, other: &Self) -> Result<Self, ErrorKind> {
+        if let Some(result) = self.rational_operation(other, |x, y| Ok(x * y)) {
+            return result;
+        }
+        match (self, other) {
+            (Self::I32(x), Self::I32(y)) => Ok(match x.checked_mul(*y) {
+                Some(num) => Self::I32(num),
+                None => Self::from_i128(*x as i128 * *y as i128),
+            }),
+            (Self::Symbolic(x), y) if y.is_rational() => x.mul_scalar(y),
+            (x, Self::Symbolic(y)) if x.is_rational() => y.mul_scalar(x),
+            (Self::Radical(x), y) if y.is_rational() => x.mul_scalar(y),
+            (x, Self::Radical(y)) if x.is_rational() => y.mul_scalar(x),
+            (Self::Radical(x), Self::Radical(y)) => x.mul(y),
+            _ => self.approximate(other, |x, y| x * y),
+        }
+    }
+
+    pub fn div(&self, other: &Self) -> Result<Self, ErrorKind> {
+        let quotient = |x: BigRational, y: BigRational| match y.is_zero() {
+            true => Err(ErrorKind::DivisionByZero),
+            false => Ok(x / y),
+        };
+        if let Some(result) = self.rational_operation(other, quotient) {
+            return result;
+        }
+        match (self, other) {
+            (Self::I32(_), Self::I32(0)) => Err(ErrorKind::DivisionByZero),
+            (Self::I32(x), Self::I32(y)) => {
+                Ok(Fraction::new_result(BigRational::new(BigInt::from(*x), BigInt::from(*y))))
+            },
+            _ if other.is_zero() => Err(ErrorKind::DivisionByZero),
+            (Self::Symbolic(x), y) if y.is_rational() => x.div_scalar(y),
+            (Self::Symbolic(x), Self::Symbolic(y)) if x.constant() == y.constant() => x.div(y),
+            (Self::Radical(x), y) if y.is_rational() => x.div_scalar(y),
+            (x, Self::Radical(y)) if x.is_rational() => y.div_scalar_reverse(x),
+            (Self::Radical(x), Self::Radical(y)) => x.div(y),
+            _ => self.approximate(other, |x, y| x / y),
+        }
+    }
+
+    pub fn plus(&self) -> Result<Self, ErrorKind> {
+        Ok(self.clone())
+    }
+
+    pub fn minus(&self) -> Result<Self, ErrorKind> {
+        match self {
+            Self::I32(x) => Ok(Self::from_i128(-(*x as i128))),
+            Self::Fraction(fraction) => Ok(Fraction::new_result(-fraction.value())),
+            Self::Symbolic(symbolic) => symbolic.minus(),
+            Self::Radical(radical) => radical.minus(),
+            Self::Real(num) => Ok(Self::Real(-num)),
+            Self::Wide(num) => Ok(Self::from_bigint(-num)),
+        }
+    }
+
+    pub fn sqrt(&self) -> Result<Self, ErrorKind> {
+        if self.is_rational() {
+            return Radical::sqrt(self);
+        }
+        if self.is_minus() {
+            return Err(ErrorKind::NegativeSquareRoot);
+        }
+        Ok(Self::Real(self.to_f64()?.sqrt()))
+    }
+}
+
+impl std::fmt::Display for Num {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::I32(num) => write!(f, "{}", num),
+            Self::Fraction(fraction) => write!(f, "{}", fraction),
+            Self::Symbolic(symbolic) => write!(f, "{}", symbolic),
+            Self::Radical(radical) => write!(f, "{}", radical),
+            Self::Real(num) => write!(f, "{}", Self::format_f64(*num, DECIMAL_DIGITS)),
+            Self::Wide(num) => write!(f, "{}", num),
+        }
+    }
+}
@@ -0,0 +1,234 @@
+use super::term::Term;
+use super::function::Function;
+use super::settings::Settings;
+use super::bitwise;
+use super::machine;
+use super::error::ErrorKind;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operator {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Comma,
+    // `255 in hex`, which is only valid as a whole expression.
+    In,
+    BitAnd,
+    BitOr,
+    BitXor,
+    ShiftLeft,
+    ShiftRight,
+    Plus,
+    Minus,
+    BitNot,
+    Function(Function),
+}
+
+impl Operator {
+    pub fn is_operator(c: char) -> bool {
+        "+-*/,&|~".contains(c)
+    }
+
+    /// First character of `<<` and `>>`, which must be doubled.
+    pub fn is_shift(c: char) -> bool {
+        c == '<' || c == '>'
+    }
+
+    /// Operators written as words.
+    pub fn parse_keyword(name: &str) -> Option<Self> {
+        match name {
+            "in" => Some(Operator::In),
+            "xor" => Some(Operator::BitXor),
+            _ => None,
+        }
+    }
+
+    pub fn is_unary(&self) -> bool {
+        match self {
+            Operator::Add => false,
+            Operator::Sub => false,
+            Operator::Mul => false,
+            Operator::Div => false,
+            Operator::Comma => false,
+            Operator::In => false,
+            Operator::BitAnd => false,
+            Operator::BitOr => false,
+            Operator::BitXor => false,
+            Operator::ShiftLeft => false,
+            Operator::ShiftRight => false,
+            Operator::Plus => true,
+            Operator::Minus => true,
+            Operator::BitNot => true,
+            Operator::Function(_) => true,
+        }
+    }
+
+    pub fn parse(symbol: &str, unary: bool) -> Result<Self, ErrorKind> {
+        match (symbol, unary) {
+            ("+", false) => Ok(Operator::Add),
+            ("-", false) => Ok(Operator::Sub),
+            ("*", false) => Ok(Operator::Mul),
+            ("/", false) => Ok(Operator::Div),
+            (",", false) => Ok(Operator::Comma),
+            ("&", false) => Ok(Operator::BitAnd),
+            ("|", false) => Ok(Operator::BitOr),
+            ("<<", false) => Ok(Operator::ShiftLeft),
+            (">>", false) => Ok(Operator::ShiftRight),
+            ("+", true) => Ok(Operator::Plus),
+            ("-", true) => Ok(Operator::Minus),
+            ("~", true) => Ok(Operator::BitNot),
+            _ => Err(ErrorKind::InvalidOperator),
+        }
+    }
+
+    // Bitwise operators bind as in C: below arithmetic, with `&` above `xor`
+    // above `|`.
+    pub fn priority(&self) -> u8 {
+        match self {
+            Operator::Comma => 0,
+            Operator::In => 0,
+            Operator::BitOr => 1,
+            Operator::BitXor => 2,
+            Operator::BitAnd => 3,
+            Operator::ShiftLeft => 4,
+            Operator::ShiftRight => 4,
+            Operator::Add => 5,
+            Operator::Sub => 5,
+            Operator::Mul => 6,
+            Operator::Div => 6,
+            Operator::Plus => 7,
+            Operator::Minus => 7,
+            Operator::BitNot => 7,
+            Operator::Function(_) => 8,
+        }
+    }
+
+    pub fn is_bitwise(&self) -> bool {
+        matches!(
+            self,
+            Operator::BitAnd | Operator::BitOr | Operator::BitXor
+                | Operator::ShiftLeft | Operator::ShiftRight | Operator::BitNot
+        )
+    }
+
+    pub fn calculate_unary(&self, term: &Term, settings: &Settings) -> Result<Term, ErrorKind> {
+        if let (true, Term::Num(x)) = (settings.programmer, term) {
+            if let Some(result) = machine::calculate_unary(self, x, settings.bit_width) {
+                return result.map(Term::Num);
+            }
+        }
+        match (self, term) {
+            (Operator::Plus, term) => Self::plus(term),
+            (Operator::Minus, term) => Self::minus(term),
+            (Operator::BitNot, Term::Num(x)) => bitwise::not(x, settings.bit_width).map(Term::Num),
+            _ => Err(ErrorKind::InvalidOperation),
+        }
+    }
+
+    pub fn calculate_binary(&self, left: &Term, right: &Term, settings: &Settings) -> Result<Term, ErrorKind> {
+        let width = settings.bit_width;
+        let (x, y) = match (left, right) {
+            (Term::Num(x), Term::Num(y)) => (x, y),
+            _ => return Err(ErrorKind::InvalidOperation),
+        };
+        if settings.programmer {
+            if let Some(result) = machine::calculate_binary(self, x, y, width) {
+                return result.map(Term::Num);
+            }
+        }
+        match self {
+            Operator::Add => Self::add(left, right),
+            Operator::Sub => Self::sub(left, right),
+            Operator::Mul => Self::mul(left, right),
+            Operator::Div => Self::div(left, right),
+            Operator::BitAnd => bitwise::and(x, y, width).map(Term::Num),
+            Operator::BitOr => bitwise::or(x, y, width).map(Term::Num),
+            Operator::BitXor => bitwise::xor(x, y, width).map(Term::Num),
+            Operator::ShiftLeft => bitwise::shift_left(x, y, width).map(Term::Num),
+            Operator::ShiftRight => bitwise::shift_right(x, y, width).map(Term::Num),
+            // Commas are consumed as argument separators by function calls.
+            Operator::Comma => Err(ErrorKind::MisplacedComma),
+            Operator::In => Err(ErrorKind::MisplacedConversion),
+            _ => Err(ErrorKind::InvalidOperation),
+        }
+    }
+
+    // pub fn calculate(&self, terms: &[Term]) -> Result<Term, ErrorKind> {
+    //     let terms_len = terms.len();
+    //     match terms_len {
+    //         1 => self.calculate_unary(&terms[0]),
+    //         2 => self.calculate_binary(&terms[0], &terms[1]),
+    //         _ => Err(ErrorKind::InvalidOperation),
+    //     }
+    // }
+
+    fn add(left: &Term, right: &Term) -> Result<Term, ErrorKind> {
+        match (left, right) {
+            (Term::Num(x), Term::Num(y))
+                => x.add(y).map(Term::Num),
+            _ => Err(ErrorKind::InvalidOperation),
+        }
+    }
+
+    fn sub(left: &Term, right: &Term) -> Result<Term, ErrorKind> {
+        match (left, right) {
+            (Term::Num(x), Term::Num(y))
+                => x.sub(y).map(Term::Num),
+            _ => Err(ErrorKind::InvalidOperation),
+        }
+    }
+
+    fn mul(left: &Term, right: &Term) -> Result<Term, ErrorKind> {
+        match (left, right) {
+            (Term::Num(x), Term::Num(y))
+                => x.mul(y).map(Term::Num),
+            _ => Err(ErrorKind::InvalidOperation),
+        }
+    }
+
+    fn div(left: &Term, right: &Term) -> Result<Term, ErrorKind> {
+        match (left, right) {
+            (Term::Num(x), Term::Num(y))
+                => x.div(y).map(Term::Num),
+            _ => Err(ErrorKind::InvalidOperation),
+        }
+    }
+
+    fn plus(term: &Term) -> Result<Term, ErrorKind> {
+        match term {
+            Term::Num(x) => x.plus().map(Term::Num),
+            _ => Err(ErrorKind::InvalidOperation),
+        }
+    }
+
+    fn minus(term: &Term) -> Result<Term, ErrorKind> {
+        match term {
+            Term::Num(x) => x.minus().map(Term::Num),
+            _ => Err(ErrorKind::InvalidOperation),
+        }
+    }
+}
+
+impl std::fmt::Display for Operator {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let op = match self {
+            Operator::Add => "+",
+            Operator::Sub => "-",
+            Operator::Mul => "*",
+            Operator::Div => "/",
+            Operator::Comma => ",",
+            Operator::In => "in",
+            Operator::BitAnd => "&",
+            Operator::BitOr => "|",
+            Operator::BitXor => "xor",
+            Operator::ShiftLeft => "<<",
+            Operator::ShiftRight => ">>",
+            Operator::Plus => "+",
+            Operator::Minus => "-",
+            Operator::BitNot => "~",
+            Operator::Function(function) => return write!(f, "{}", function),
+        };
+        write!(f, "{}", op)
+    }
+}
@@ -1,3 +1,4 @@
+use super::error::ErrorKind;
 #[derive(Debug, Clone, PartialEq)]
 pub enum Paren {
     Left,
@@ -9,11 +10,11 @@ impl Paren {
         "()".contains(c)
     }
 
-    pub fn parse(c: char) -> Result<Self, String> {
+    pub fn parse(c: char) -> Result<Self, ErrorKind> {
         match c {
             '(' => Ok(Paren::Left),
             ')' => Ok(Paren::Right),
-            _ => Err("括弧にparseができませんでした".to_string()),
+            _ => Err(ErrorKind::ParenParseFailure),
         }
     }
 
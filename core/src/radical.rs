@@ -0,0 +1,146 @@
+use num::{BigInt, BigRational, Integer, One, ToPrimitive, Zero};
+use serde::{Serialize, Deserialize};
+use super::num::Num;
+use super::fraction::Fraction;
+use super::symbolic::write_multiple;
+use super::error::ErrorKind;
+
+// Factors tried when simplifying a radicand. Larger square factors of
+// radicands beyond this cubed are left under the root.
+const TRIAL_DIVISION_LIMIT: u32 = 100_000;
+
+/// Rational multiple of a square root, such as `2√2` or `√3 / 2`.
+/// The radicand is square-free and greater than one.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Radical {
+    coefficient: Num,
+    radicand: BigInt,
+}
+
+impl Radical {
+    fn new_result(coefficient: Num, radicand: BigInt) -> Result<Num, ErrorKind> {
+        if coefficient.is_zero() || radicand.is_one() {
+            return Ok(coefficient);
+        }
+        Ok(Num::Radical(Box::new(Self {
+            coefficient,
+            radicand,
+        })))
+    }
+
+    // Splits `n` into `(a, r)` with `n = a² * r` and `r` square-free. Once
+    // the cube of the next factor exceeds the rest, the rest has at most
+    // two prime factors and holds a square only if it is one.
+    fn split_square(n: &BigInt) -> (BigInt, BigInt) {
+        let mut square = BigInt::one();
+        let mut free = BigInt::one();
+        let mut rest = n.clone();
+        let mut factor = 2u32;
+        while factor <= TRIAL_DIVISION_LIMIT && num::pow(BigInt::from(factor), 3) <= rest {
+            let mut count = 0;
+            while (&rest % factor).is_zero() {
+                rest /= factor;
+                count += 1;
+            }
+            square *= num::pow(BigInt::from(factor), count / 2);
+            if count % 2 == 1 {
+                free *= factor;
+            }
+            factor += 1;
+        }
+        let root = rest.sqrt();
+        if &root * &root == rest {
+            square *= root;
+        } else {
+            free *= rest;
+        }
+        (square, free)
+    }
+
+    // √x * √y as `(a, r)` with `a√r`, using that both are square-free.
+    fn mul_radicands(x: &BigInt, y: &BigInt) -> (BigInt, BigInt) {
+        let gcd = x.gcd(y);
+        let radicand = (x / &gcd) * (y / &gcd);
+        (gcd, radicand)
+    }
+
+    /// Exact square root of a non-negative rational number.
+    pub fn sqrt(num: &Num) -> Result<Num, ErrorKind> {
+        if num.is_minus() {
+            return Err(ErrorKind::NegativeSquareRoot);
+        }
+        let value = num.to_rational().ok_or(ErrorKind::NotRational)?;
+        // √(p / q) = a√r / (b√s) = a√(rs) / bs, and rs is square-free
+        // because p / q is irreducible.
+        let (a, r) = Self::split_square(value.numer());
+        let (b, s) = Self::split_square(value.denom());
+        let coefficient = Fraction::new_result(BigRational::new(a, b * &s));
+        Self::new_result(coefficient, r * s)
+    }
+
+    pub fn coefficient(&self) -> &Num {
+        &self.coefficient
+    }
+
+    pub fn radicand(&self) -> &BigInt {
+        &self.radicand
+    }
+
+    pub fn is_minus(&self) -> bool {
+        self.coefficient.is_minus()
+    }
+
+    pub fn to_f64(&self) -> Result<f64, ErrorKind> {
+        let radicand = self.radicand.to_f64().ok_or(ErrorKind::RealOverflow)?;
+        Ok(self.coefficient.to_f64()? * radicand.sqrt())
+    }
+
+    pub fn add(&self, other: &Self) -> Result<Num, ErrorKind> {
+        Self::new_result(self.coefficient.add(&other.coefficient)?, self.radicand.clone())
+    }
+
+    pub fn sub(&self, other: &Self) -> Result<Num, ErrorKind> {
+        Self::new_result(self.coefficient.sub(&other.coefficient)?, self.radicand.clone())
+    }
+
+    pub fn mul(&self, other: &Self) -> Result<Num, ErrorKind> {
+        let (square, radicand) = Self::mul_radicands(&self.radicand, &other.radicand);
+        let coefficient = self.coefficient
+            .mul(&other.coefficient)?
+            .mul(&Num::from_bigint(square))?;
+        Self::new_result(coefficient, radicand)
+    }
+
+    pub fn div(&self, other: &Self) -> Result<Num, ErrorKind> {
+        // a√x / (b√y) = a√(xy) / by
+        let (square, radicand) = Self::mul_radicands(&self.radicand, &other.radicand);
+        let coefficient = self.coefficient
+            .mul(&Num::from_bigint(square))?
+            .div(&other.coefficient.mul(&Num::from_bigint(other.radicand.clone()))?)?;
+        Self::new_result(coefficient, radicand)
+    }
+
+    pub fn mul_scalar(&self, other: &Num) -> Result<Num, ErrorKind> {
+        Self::new_result(self.coefficient.mul(other)?, self.radicand.clone())
+    }
+
+    pub fn div_scalar(&self, other: &Num) -> Result<Num, ErrorKind> {
+        Self::new_result(self.coefficient.div(other)?, self.radicand.clone())
+    }
+
+    pub fn div_scalar_reverse(&self, other: &Num) -> Result<Num, ErrorKind> {
+        // x / (a√r) = x√r / ar
+        let denominator = self.coefficient.mul(&Num::from_bigint(self.radicand.clone()))?;
+        Self::new_result(other.div(&denominator)?, self.radicand.clone())
+    }
+
+    pub fn minus(&self) -> Result<Num, ErrorKind> {
+        Self::new_result(self.coefficient.minus()?, self.radicand.clone())
+    }
+}
+
+impl std::fmt::Display for Radical {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write_multiple(f, &self.coefficient, &format!("√{}", self.radicand))
+    }
+}
@@ -0,0 +1,94 @@
+use super::settings::BitWidth;
+
+/// Base of integer literals and of converted results.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Radix {
+    Hexadecimal,
+    Decimal,
+    Octal,
+    Binary,
+}
+
+impl Radix {
+    pub const ALL: [Radix; 4] = [Radix::Hexadecimal, Radix::Decimal, Radix::Octal, Radix::Binary];
+
+    /// Target of `255 in hex`.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "hex" => Some(Radix::Hexadecimal),
+            "dec" => Some(Radix::Decimal),
+            "oct" => Some(Radix::Octal),
+            "bin" => Some(Radix::Binary),
+            _ => None,
+        }
+    }
+
+    /// Splits `0x1F` into the radix and `1F`; literals without a prefix are
+    /// decimal.
+    pub fn split_prefix(literal: &str) -> (Self, &str) {
+        let lower = literal.get(..2).map(|prefix| prefix.to_ascii_lowercase());
+        match lower.as_deref() {
+            Some("0x") => (Radix::Hexadecimal, &literal[2..]),
+            Some("0o") => (Radix::Octal, &literal[2..]),
+            Some("0b") => (Radix::Binary, &literal[2..]),
+            _ => (Radix::Decimal, literal),
+        }
+    }
+
+    /// Whether `c` continues the literal `target`, such as `x` after `0`
+    /// or `F` in `0x1F`.
+    pub fn continues_literal(target: &str, c: char) -> bool {
+        match target {
+            "0" => "xXoObB".contains(c),
+            _ => matches!(Self::split_prefix(target), (Radix::Hexadecimal, _)) && c.is_ascii_hexdigit(),
+        }
+    }
+
+    pub fn value(&self) -> u32 {
+        match self {
+            Radix::Hexadecimal => 16,
+            Radix::Decimal => 10,
+            Radix::Octal => 8,
+            Radix::Binary => 2,
+        }
+    }
+
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            Radix::Hexadecimal => "0x",
+            Radix::Decimal => "",
+            Radix::Octal => "0o",
+            Radix::Binary => "0b",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Radix::Hexadecimal => "HEX",
+            Radix::Decimal => "DEC",
+            Radix::Octal => "OCT",
+            Radix::Binary => "BIN",
+        }
+    }
+
+    /// `num` with the prefix of this radix, like `0xFF`. Negative values
+    /// are written as their two's complement in `width`, or with a sign
+    /// when they do not fit in it.
+    pub fn format(&self, num: i128, width: BitWidth) -> String {
+        if *self == Radix::Decimal {
+            return num.to_string();
+        }
+        let (sign, magnitude) = match num < 0 {
+            true if width.has_bits_for(num) => ("", width.to_bits(num) as u128),
+            true => ("-", num.unsigned_abs()),
+            false => ("", num.unsigned_abs()),
+        };
+        let digits = match self {
+            Radix::Hexadecimal => format!("{:X}", magnitude),
+            Radix::Decimal => magnitude.to_string(),
+            Radix::Octal => format!("{:o}", magnitude),
+            Radix::Binary => format!("{:b}", magnitude),
+        };
+        format!("{}{}{}", sign, self.prefix(), digits)
+    }
+}
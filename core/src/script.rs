@@ -0,0 +1,245 @@
+use super::calculation::Calculation;
+use super::environment::Environment;
+use super::settings::Settings;
+
+const COMMENT_PREFIX: char = '#';
+const ERROR_ANNOTATION: &str = "# エラー";
+const ANNOTATION_PREFIXES: [&str; 3] = ["# →", "# ≈", ERROR_ANNOTATION];
+
+/// Error of one script statement, with a 1-based line and column.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl std::fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Statement {
+    pub input: String,
+    pub result: Result<Calculation, ScriptError>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptLine {
+    pub number: usize,
+    pub source: String,
+    /// The `;`-separated statements of the line up to the first error.
+    pub statements: Vec<Statement>,
+}
+
+/// `.calc` script: one statement per line, or several separated by `;`
+/// as in the calculator, and `#` starts a comment.
+///
+/// The annotated output is itself a valid script, so a sheet can be checked
+/// in with its results and re-run to verify them.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Script {
+    lines: Vec<ScriptLine>,
+}
+
+impl Script {
+    pub fn run(text: &str, environment: &mut Environment, settings: &Settings) -> Self {
+        let lines = text
+            .lines()
+            .enumerate()
+            .map(|(index, source)| ScriptLine {
+                number: index + 1,
+                source: source.to_string(),
+                statements: Self::run_line(index + 1, source, environment, settings),
+            })
+            .collect();
+        Self { lines }
+    }
+
+    pub fn lines(&self) -> &[ScriptLine] {
+        &self.lines
+    }
+
+    pub fn statements(&self) -> impl Iterator<Item = &Statement> {
+        self.lines.iter().flat_map(|line| &line.statements)
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.statements().all(|statement| statement.result.is_ok())
+    }
+
+    /// The script with each line followed by the result of its last
+    /// statement, as a decimal when `decimal` is set, or its error.
+    pub fn annotate(&self, decimal: bool) -> String {
+        self.lines
+            .iter()
+            .map(|line| match line.statements.last() {
+                None => format!("{}\n", line.source),
+                Some(statement) => match &statement.result {
+                    Ok(calculation) => format!(
+                        "{}  {} {} {}\n", Self::without_annotation(&line.source).trim_end(),
+                        COMMENT_PREFIX, calculation.relation(),
+                        if decimal { &calculation.decimal } else { &calculation.result }),
+                    Err(error) => format!(
+                        "{}  {} {}\n", Self::without_annotation(&line.source).trim_end(),
+                        ERROR_ANNOTATION, error),
+                },
+            })
+            .collect()
+    }
+
+    fn run_line(
+        number: usize,
+        source: &str,
+        environment: &mut Environment,
+        settings: &Settings,
+    ) -> Vec<Statement> {
+        let code = Self::code(source);
+        let mut statements = Vec::new();
+        for (statement, offset) in Calculation::split_statements(code) {
+            let result = Calculation::calculate_statement_with_span(statement, environment, settings)
+                .map(|(calculation, _)| calculation)
+                .map_err(|(kind, span)| ScriptError {
+                    line: number,
+                    column: Self::column(code, span.shift(offset).start),
+                    message: kind.to_string(),
+                });
+            let failed = result.is_err();
+            statements.push(Statement {
+                input: statement.trim().to_string(),
+                result,
+            });
+            if failed {
+                break;
+            }
+        }
+        statements
+    }
+
+    fn code(source: &str) -> &str {
+        match source.find(COMMENT_PREFIX) {
+            Some(position) => &source[..position],
+            None => source,
+        }
+    }
+
+    // The line with the user's own comment but without the result of an
+    // earlier run, so that annotating again replaces it.
+    fn without_annotation(source: &str) -> &str {
+        let code = Self::code(source).len();
+        let end = ANNOTATION_PREFIXES
+            .iter()
+            .filter_map(|prefix| source[code..].find(prefix))
+            .min()
+            .map_or(source.len(), |position| code + position);
+        &source[..end]
+    }
+
+    // Spans count UTF-16 units; columns count characters.
+    fn column(code: &str, position: usize) -> usize {
+        let mut units = 0;
+        let mut column = 1;
+        for c in code.chars() {
+            if units >= position {
+                break;
+            }
+            units += c.len_utf16();
+            column += 1;
+        }
+        column
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(text: &str) -> Script {
+        Script::run(text, &mut Environment::default(), &Settings::default())
+    }
+
+    #[test]
+    fn run_statements_in_order() {
+        let script = run("# 予算\nrent = 1200\n\nfood = 300 * 4  # 週ごと\nrent + food\n");
+        let results: Vec<String> = script.statements()
+            .map(|statement| statement.result.clone().unwrap().result)
+            .collect();
+        assert_eq!(vec!["1200", "1200", "2400"], results);
+        assert_eq!(5, script.lines().len());
+        assert!(script.is_success());
+    }
+
+    #[test]
+    fn run_reports_line_and_column() {
+        let script = run("x = 1\ny = x $ 2\nz = x / 0\n1 = 2\n家賃 = 3");
+        let errors: Vec<String> = script.statements()
+            .filter_map(|statement| statement.result.clone().err())
+            .map(|error| error.to_string())
+            .collect();
+        assert_eq!(vec![
+            "2:7: 対応していない文字です",
+            "3:7: 0で割ることはできません",
+            "4:1: 代入先が変数名ではありません",
+            "5:1: 代入先が変数名ではありません",
+        ], errors);
+        assert!(!script.is_success());
+    }
+
+    #[test]
+    fn run_semicolon_statements_in_order() {
+        let script = run("a = 3; b = 4; a*a + b*b\nc = 1; c / 0; c\n");
+        let inputs: Vec<&str> = script.statements()
+            .map(|statement| statement.input.as_str())
+            .collect();
+        assert_eq!(vec!["a = 3", "b = 4", "a*a + b*b", "c = 1", "c / 0"], inputs);
+        let expected = "\
+a = 3; b = 4; a*a + b*b  # → 25
+c = 1; c / 0; c  # エラー 2:10: 0で割ることはできません
+";
+        assert_eq!(expected, script.annotate(false));
+    }
+
+    #[test]
+    fn annotate_results_and_errors() {
+        let script = run("# 予算\nx = 1/2\nx * 3  # 3倍\n\nx / 0\n");
+        let expected = "\
+# 予算
+x = 1/2  # → 1 / 2
+x * 3  # 3倍  # → 3 / 2
+
+x / 0  # エラー 5:3: 0で割ることはできません
+";
+        assert_eq!(expected, script.annotate(false));
+    }
+
+    #[test]
+    fn annotate_points_at_failing_token() {
+        let script = run("# budget\nrent = 1200\nrent * 12  # per year\nrent / months\n");
+        let expected = "\
+# budget
+rent = 1200  # → 1200
+rent * 12  # per year  # → 14400
+rent / months  # エラー 4:8: 定義されていない変数です
+";
+        assert_eq!(expected, script.annotate(false));
+    }
+
+    #[test]
+    fn annotated_script_runs_again_unchanged() {
+        let annotated = run("a = 2  # 辺\na * a\nb + 1  # 未定義\n").annotate(false);
+        assert_eq!(annotated, run(&annotated).annotate(false));
+        assert!(annotated.contains("a = 2  # 辺  # → 2\n"));
+    }
+
+    #[test]
+    fn annotate_approximate_results() {
+        assert_eq!("π + 1  # ≈ 4.1415926536\n", run("π + 1").annotate(false));
+    }
+
+    #[test]
+    fn annotate_decimal_results() {
+        assert_eq!("1/4  # → 0.25\n", run("1/4").annotate(true));
+    }
+}
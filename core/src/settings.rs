@@ -0,0 +1,289 @@
+use num::{BigInt, Integer, ToPrimitive};
+use serde::{Serialize, Deserialize};
+use super::{DECIMAL_DIGITS, MAX_STORED_HISTORY};
+
+/// Unit of the arguments of `sin`, `cos` and `tan` and of the results of
+/// their inverses.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum AngleUnit {
+    Degree,
+    #[default]
+    Radian,
+    Gradian,
+}
+
+impl AngleUnit {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "deg" => Some(AngleUnit::Degree),
+            "rad" => Some(AngleUnit::Radian),
+            "grad" => Some(AngleUnit::Gradian),
+            _ => None,
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            AngleUnit::Radian => AngleUnit::Degree,
+            AngleUnit::Degree => AngleUnit::Gradian,
+            AngleUnit::Gradian => AngleUnit::Radian,
+        }
+    }
+}
+
+impl std::fmt::Display for AngleUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            AngleUnit::Degree => write!(f, "DEG"),
+            AngleUnit::Radian => write!(f, "RAD"),
+            AngleUnit::Gradian => write!(f, "GRAD"),
+        }
+    }
+}
+
+/// Machine integer type that bitwise operations wrap to, like `u8` or
+/// `i32`. Negative values are shown in other bases as its two's
+/// complement.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BitWidth {
+    pub bits: u32,
+    pub signed: bool,
+}
+
+impl Default for BitWidth {
+    fn default() -> Self {
+        Self { bits: 32, signed: true }
+    }
+}
+
+impl BitWidth {
+    pub const ALL: [BitWidth; 8] = [
+        BitWidth { bits: 8, signed: true },
+        BitWidth { bits: 16, signed: true },
+        BitWidth { bits: 32, signed: true },
+        BitWidth { bits: 64, signed: true },
+        BitWidth { bits: 8, signed: false },
+        BitWidth { bits: 16, signed: false },
+        BitWidth { bits: 32, signed: false },
+        BitWidth { bits: 64, signed: false },
+    ];
+
+    pub fn parse(name: &str) -> Option<Self> {
+        let (signed, bits) = match name.split_at(name.len().min(1)) {
+            ("i", bits) => (true, bits),
+            ("u", bits) => (false, bits),
+            _ => return None,
+        };
+        match bits.parse() {
+            Ok(bits @ (8 | 16 | 32 | 64)) => Some(Self { bits, signed }),
+            _ => None,
+        }
+    }
+
+    fn modulus(&self) -> i128 {
+        1 << self.bits
+    }
+
+    /// `num` reduced to this type, as a cast in C would.
+    pub fn wrap(&self, num: i128) -> i128 {
+        let num = num.rem_euclid(self.modulus());
+        if self.signed && num >= self.modulus() / 2 { num - self.modulus() } else { num }
+    }
+
+    /// Like `wrap`, for integers of any size.
+    pub fn wrap_integer(&self, num: &BigInt) -> i128 {
+        let low_bits = num.mod_floor(&BigInt::from(self.modulus()));
+        self.wrap(low_bits.to_i128().unwrap_or_default())
+    }
+
+    /// Whether `num` has a bit pattern in this many bits, as a signed or
+    /// an unsigned value.
+    pub fn has_bits_for(&self, num: i128) -> bool {
+        (-(self.modulus() / 2)..self.modulus()).contains(&num)
+    }
+
+    /// The bit pattern of `num`, which must have one.
+    pub fn to_bits(&self, num: i128) -> u64 {
+        num.rem_euclid(self.modulus()) as u64
+    }
+
+    /// The bits of `num` as this type, one byte at a time from the most
+    /// significant, each with the index of its highest bit and written in
+    /// groups of four like `1100 1000`.
+    pub fn bit_field(&self, num: i128) -> Vec<(u32, String)> {
+        let bits = format!("{:0width$b}", self.to_bits(self.wrap(num)), width = self.bits as usize);
+        bits.as_bytes()
+            .chunks(8)
+            .enumerate()
+            .map(|(index, byte)| {
+                let high = self.bits - 1 - index as u32 * 8;
+                let (upper, lower) = byte.split_at(4);
+                let text = format!(
+                    "{} {}",
+                    String::from_utf8_lossy(upper),
+                    String::from_utf8_lossy(lower),
+                );
+                (high, text)
+            })
+            .collect()
+    }
+}
+
+impl std::fmt::Display for BitWidth {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}{}", if self.signed { "i" } else { "u" }, self.bits)
+    }
+}
+
+/// How real results are written: as plain decimals, with an exponent that
+/// is a multiple of 3 like `470e-6`, with an SI prefix like `470µ`, in
+/// units of ten thousand like `3億2000万`, or in kanji numerals like
+/// `三百二十`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum Notation {
+    #[default]
+    Standard,
+    Engineering,
+    Si,
+    Myriad,
+    Kanji,
+}
+
+impl Notation {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "std" => Some(Notation::Standard),
+            "eng" => Some(Notation::Engineering),
+            "si" => Some(Notation::Si),
+            "ja" => Some(Notation::Myriad),
+            "kanji" => Some(Notation::Kanji),
+            _ => None,
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            Notation::Standard => Notation::Engineering,
+            Notation::Engineering => Notation::Si,
+            Notation::Si => Notation::Myriad,
+            Notation::Myriad => Notation::Kanji,
+            Notation::Kanji => Notation::Standard,
+        }
+    }
+}
+
+impl std::fmt::Display for Notation {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Notation::Standard => write!(f, "STD"),
+            Notation::Engineering => write!(f, "ENG"),
+            Notation::Si => write!(f, "SI"),
+            Notation::Myriad => write!(f, "万"),
+            Notation::Kanji => write!(f, "漢"),
+        }
+    }
+}
+
+/// Convention for grouping the digits of displayed numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Locale {
+    /// `1,234,567.5`
+    English,
+    /// `1.234.567,5`
+    German,
+    /// `1 234 567,5`
+    French,
+    /// `12,34,567.5`
+    Indian,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 4] = [
+        Locale::English,
+        Locale::German,
+        Locale::French,
+        Locale::Indian,
+    ];
+
+    /// The convention of a language tag like `ja`, `de-CH` or `en-IN`.
+    pub fn parse(tag: &str) -> Option<Self> {
+        let tag = tag.to_ascii_lowercase();
+        let (language, region) = tag.split_once(['-', '_']).unwrap_or((&tag, ""));
+        match (language, region) {
+            ("en", "in") | ("hi", _) => Some(Locale::Indian),
+            ("en" | "ja" | "zh" | "ko", _) => Some(Locale::English),
+            ("de" | "es" | "it" | "nl" | "pt" | "id" | "tr", _) => Some(Locale::German),
+            ("fr", _) => Some(Locale::French),
+            _ => None,
+        }
+    }
+}
+
+// Missing fields take their defaults, so settings saved by an older
+// version still load.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    // Not chosen by the user, so stored settings do not pin them.
+    #[serde(skip)]
+    pub decimal_digits: u32,
+    #[serde(skip)]
+    pub max_stored_history: usize,
+    pub angle_unit: AngleUnit,
+    /// Significant digits of approximate results, calculated with
+    /// arbitrary precision instead of f64 when set.
+    pub precision: Option<u32>,
+    pub bit_width: BitWidth,
+    /// Whether integers behave like `bit_width` in arithmetic too, wrapping
+    /// on overflow and truncating on division.
+    pub programmer: bool,
+    pub notation: Notation,
+    /// How digits of displayed results are grouped, if at all. Results
+    /// themselves stay ungrouped so that they can be used as input again.
+    pub locale: Option<Locale>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            decimal_digits: DECIMAL_DIGITS,
+            max_stored_history: MAX_STORED_HISTORY,
+            angle_unit: AngleUnit::default(),
+            precision: None,
+            bit_width: BitWidth::default(),
+            programmer: false,
+            notation: Notation::default(),
+            locale: None,
+        }
+    }
+}
+
+impl Settings {
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string(self)
+            .map_err(|_| "設定の保存に失敗しました".to_string())
+    }
+
+    pub fn from_json(value: &str) -> Result<Self, String> {
+        serde_json::from_str(value)
+            .map_err(|_| "設定の読み込みに失敗しました".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settings_round_trip_keeps_fixed_fields() {
+        let settings = Settings {
+            decimal_digits: 3,
+            angle_unit: AngleUnit::Gradian,
+            programmer: true,
+            locale: Some(Locale::Indian),
+            ..Settings::default()
+        };
+        let actual = Settings::from_json(&settings.to_json().unwrap()).unwrap();
+        assert_eq!(Settings { decimal_digits: Settings::default().decimal_digits, ..settings }, actual);
+    }
+}
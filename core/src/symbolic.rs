@@ -0,0 +1,121 @@
+use serde::{Serialize, Deserialize};
+use super::num::Num;
+use super::error::ErrorKind;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Constant {
+    Pi,
+    E,
+}
+
+impl Constant {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "pi" | "π" => Some(Constant::Pi),
+            "e" => Some(Constant::E),
+            _ => None,
+        }
+    }
+
+    pub fn value(&self) -> f64 {
+        match self {
+            Constant::Pi => std::f64::consts::PI,
+            Constant::E => std::f64::consts::E,
+        }
+    }
+}
+
+impl std::fmt::Display for Constant {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Constant::Pi => write!(f, "π"),
+            Constant::E => write!(f, "e"),
+        }
+    }
+}
+
+/// Rational multiple of a constant, such as `2π` or `π / 2`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Symbolic {
+    coefficient: Num,
+    constant: Constant,
+}
+
+impl Symbolic {
+    pub fn new(coefficient: Num, constant: Constant) -> Self {
+        Self {
+            coefficient,
+            constant,
+        }
+    }
+
+    pub fn new_result(coefficient: Num, constant: Constant) -> Result<Num, ErrorKind> {
+        if coefficient.is_zero() {
+            return Ok(Num::I32(0));
+        }
+        Ok(Num::Symbolic(Box::new(Self::new(coefficient, constant))))
+    }
+
+    pub fn coefficient(&self) -> &Num {
+        &self.coefficient
+    }
+
+    pub fn constant(&self) -> Constant {
+        self.constant
+    }
+
+    pub fn is_minus(&self) -> bool {
+        self.coefficient.is_minus()
+    }
+
+    pub fn to_f64(&self) -> Result<f64, ErrorKind> {
+        Ok(self.coefficient.to_f64()? * self.constant.value())
+    }
+
+    pub fn add(&self, other: &Self) -> Result<Num, ErrorKind> {
+        Self::new_result(self.coefficient.add(&other.coefficient)?, self.constant)
+    }
+
+    pub fn sub(&self, other: &Self) -> Result<Num, ErrorKind> {
+        Self::new_result(self.coefficient.sub(&other.coefficient)?, self.constant)
+    }
+
+    pub fn div(&self, other: &Self) -> Result<Num, ErrorKind> {
+        self.coefficient.div(&other.coefficient)
+    }
+
+    pub fn mul_scalar(&self, other: &Num) -> Result<Num, ErrorKind> {
+        Self::new_result(self.coefficient.mul(other)?, self.constant)
+    }
+
+    pub fn div_scalar(&self, other: &Num) -> Result<Num, ErrorKind> {
+        Self::new_result(self.coefficient.div(other)?, self.constant)
+    }
+
+    pub fn minus(&self) -> Result<Num, ErrorKind> {
+        Self::new_result(self.coefficient.minus()?, self.constant)
+    }
+}
+
+/// Writes `coefficient` times `symbol` as `2π`, `-π` or `3π / 4`.
+pub fn write_multiple(f: &mut std::fmt::Formatter, coefficient: &Num, symbol: &str) -> std::fmt::Result {
+    let (numerator, denominator) = match coefficient {
+        Num::Fraction(fraction) => (fraction.numerator(), Some(fraction.denominator())),
+        coefficient => (coefficient.clone(), None),
+    };
+    match numerator {
+        Num::I32(1) => write!(f, "{}", symbol)?,
+        Num::I32(-1) => write!(f, "-{}", symbol)?,
+        numerator => write!(f, "{}{}", numerator, symbol)?,
+    }
+    if let Some(denominator) = denominator {
+        write!(f, " / {}", denominator)?;
+    }
+    Ok(())
+}
+
+impl std::fmt::Display for Symbolic {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write_multiple(f, &self.coefficient, &self.constant.to_string())
+    }
+}
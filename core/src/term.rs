@@ -0,0 +1,31 @@
+use super::num::Num;
+use super::operator::Operator;
+use super::paren::Paren;
+
+pub const DEGREE_SIGN: char = '°';
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Term {
+    Num(Num),
+    Operator(Operator),
+    Paren(Paren),
+    Variable(String),
+    // Literal in degrees, like `30°`, converted to the angle unit when calculated.
+    Degree(Num),
+    // Number in decimal, scientific or SI notation, like `2.2k`, shown as
+    // written rather than as its value.
+    Literal(Num, String),
+}
+
+impl std::fmt::Display for Term {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Term::Num(num) => write!(f, "{}", num),
+            Term::Operator(op) => write!(f, "{}", op),
+            Term::Paren(paren) => write!(f, "{}", paren),
+            Term::Variable(name) => write!(f, "{}", name),
+            Term::Degree(num) => write!(f, "{}{}", num, DEGREE_SIGN),
+            Term::Literal(_, literal) => write!(f, "{}", literal),
+        }
+    }
+}
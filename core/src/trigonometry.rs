@@ -0,0 +1,243 @@
+use num::{BigInt, Integer, ToPrimitive};
+use super::num::Num;
+use super::decimal::Decimal;
+use super::radical::Radical;
+use super::settings::AngleUnit;
+use super::symbolic::{Constant, Symbolic};
+use super::error::ErrorKind;
+
+// Special angles as `(numerator, denominator)` turns, within the ranges of
+// `asin` and `atan` (-90° to 90°) and of `acos` (0° to 180°).
+const SIGNED_SPECIAL_TURNS: [(i64, i64); 9] = [
+    (-3, 12), (-1, 8), (-2, 12), (-1, 12), (0, 1), (1, 12), (2, 12), (1, 8), (3, 12),
+];
+const HALF_TURN_SPECIAL_TURNS: [(i64, i64); 9] = [
+    (0, 1), (1, 12), (1, 8), (2, 12), (3, 12), (4, 12), (3, 8), (5, 12), (6, 12),
+];
+
+fn rational(numerator: i64, denominator: i64) -> Result<Num, ErrorKind> {
+    let to_num = |value: i64| i32::try_from(value)
+        .map(Num::I32)
+        .map_err(|_| ErrorKind::AngleOverflow);
+    to_num(numerator)?.div(&to_num(denominator)?)
+}
+
+fn pi() -> Num {
+    Num::Symbolic(Box::new(Symbolic::new(Num::I32(1), Constant::Pi)))
+}
+
+// The angle as an exact fraction of a full turn, if it is one, without
+// the whole turns. Special angles have small denominators.
+fn to_turns(angle: &Num, unit: AngleUnit) -> Option<(i64, i64)> {
+    let turns = match (unit, angle) {
+        (AngleUnit::Degree, angle) => angle.to_rational()? / BigInt::from(360),
+        (AngleUnit::Gradian, angle) => angle.to_rational()? / BigInt::from(400),
+        (AngleUnit::Radian, Num::Symbolic(symbolic)) if symbolic.constant() == Constant::Pi => {
+            symbolic.coefficient().to_rational()? / BigInt::from(2)
+        },
+        (AngleUnit::Radian, angle) if angle.is_exact() && angle.is_zero() => return Some((0, 1)),
+        _ => return None,
+    };
+    let numerator = turns.numer().mod_floor(turns.denom());
+    Some((numerator.to_i32()? as i64, turns.denom().to_i32()? as i64))
+}
+
+fn from_turns(numerator: i64, denominator: i64, unit: AngleUnit) -> Result<Num, ErrorKind> {
+    match unit {
+        AngleUnit::Degree => rational(numerator * 360, denominator),
+        AngleUnit::Gradian => rational(numerator * 400, denominator),
+        AngleUnit::Radian => pi().mul(&rational(numerator * 2, denominator)?),
+    }
+}
+
+fn to_radians(angle: &Num, unit: AngleUnit) -> Result<f64, ErrorKind> {
+    let angle = angle.to_f64()?;
+    Ok(match unit {
+        AngleUnit::Degree => angle.to_radians(),
+        AngleUnit::Radian => angle,
+        AngleUnit::Gradian => angle * std::f64::consts::PI / 200.0,
+    })
+}
+
+fn from_radians(radians: f64, unit: AngleUnit) -> Result<Num, ErrorKind> {
+    Num::from_f64(match unit {
+        AngleUnit::Degree => radians.to_degrees(),
+        AngleUnit::Radian => radians,
+        AngleUnit::Gradian => radians * 200.0 / std::f64::consts::PI,
+    })
+}
+
+fn half(numerator: i32) -> Result<Num, ErrorKind> {
+    Num::I32(numerator).div(&Num::I32(2))
+}
+
+fn half_root(radicand: i32) -> Result<Num, ErrorKind> {
+    Radical::sqrt(&Num::I32(radicand))?.div(&Num::I32(2))
+}
+
+// sin of multiples of 30° and 45°, which are the only rational turns with
+// values expressible as a single square root.
+fn exact_sin(numerator: i64, denominator: i64) -> Result<Option<Num>, ErrorKind> {
+    let numerator = numerator.rem_euclid(denominator);
+    let value = if numerator * 12 % denominator == 0 {
+        match numerator * 12 / denominator {
+            0 | 6 => Num::I32(0),
+            1 | 5 => half(1)?,
+            2 | 4 => half_root(3)?,
+            3 => Num::I32(1),
+            7 | 11 => half(-1)?,
+            8 | 10 => half_root(3)?.minus()?,
+            _ => Num::I32(-1),
+        }
+    } else if numerator * 8 % denominator == 0 {
+        match numerator * 8 / denominator {
+            1 | 3 => half_root(2)?,
+            _ => half_root(2)?.minus()?,
+        }
+    } else {
+        return Ok(None);
+    };
+    Ok(Some(value))
+}
+
+fn exact_cos(numerator: i64, denominator: i64) -> Result<Option<Num>, ErrorKind> {
+    exact_sin(numerator * 4 + denominator, denominator * 4)
+}
+
+fn exact_tan(numerator: i64, denominator: i64) -> Result<Option<Num>, ErrorKind> {
+    match (exact_sin(numerator, denominator)?, exact_cos(numerator, denominator)?) {
+        (Some(_), Some(cos)) if cos.is_zero() => Err(ErrorKind::TangentUndefined),
+        (Some(sin), Some(cos)) => sin.div(&cos).map(Some),
+        _ => Ok(None),
+    }
+}
+
+/// Converts `angle` given in degrees, as with `30°`, to `unit`.
+pub fn degree(angle: &Num, unit: AngleUnit) -> Result<Num, ErrorKind> {
+    match unit {
+        AngleUnit::Degree => Ok(angle.clone()),
+        AngleUnit::Radian => angle.mul(&pi())?.div(&Num::I32(180)),
+        AngleUnit::Gradian => angle.mul(&rational(10, 9)?),
+    }
+}
+
+pub fn sin(angle: &Num, unit: AngleUnit) -> Result<Num, ErrorKind> {
+    if let Some((numerator, denominator)) = to_turns(angle, unit) {
+        if let Some(value) = exact_sin(numerator, denominator)? {
+            return Ok(value);
+        }
+    }
+    Num::from_f64(to_radians(angle, unit)?.sin())
+}
+
+pub fn cos(angle: &Num, unit: AngleUnit) -> Result<Num, ErrorKind> {
+    if let Some((numerator, denominator)) = to_turns(angle, unit) {
+        if let Some(value) = exact_cos(numerator, denominator)? {
+            return Ok(value);
+        }
+    }
+    Num::from_f64(to_radians(angle, unit)?.cos())
+}
+
+pub fn tan(angle: &Num, unit: AngleUnit) -> Result<Num, ErrorKind> {
+    if let Some((numerator, denominator)) = to_turns(angle, unit) {
+        if let Some(value) = exact_tan(numerator, denominator)? {
+            return Ok(value);
+        }
+    }
+    Num::from_f64(to_radians(angle, unit)?.tan())
+}
+
+// The special angle whose value under `function` is `value`, if any.
+fn special_angle<F>(
+    value: &Num,
+    candidates: &[(i64, i64)],
+    function: F,
+    unit: AngleUnit,
+) -> Result<Option<Num>, ErrorKind>
+where
+    F: Fn(i64, i64) -> Result<Option<Num>, ErrorKind>,
+{
+    if !value.is_exact() {
+        return Ok(None);
+    }
+    for &(numerator, denominator) in candidates {
+        // tan is undefined at ±90°, which is never the answer of atan.
+        if let Ok(Some(candidate)) = function(numerator, denominator) {
+            if candidate == *value {
+                return from_turns(numerator, denominator, unit).map(Some);
+            }
+        }
+    }
+    Ok(None)
+}
+
+fn check_unit_range(value: &Num) -> Result<(), ErrorKind> {
+    if value.to_f64()?.abs() > 1.0 {
+        return Err(ErrorKind::OutsideUnitRange);
+    }
+    Ok(())
+}
+
+pub fn asin(value: &Num, unit: AngleUnit) -> Result<Num, ErrorKind> {
+    check_unit_range(value)?;
+    if let Some(angle) = special_angle(value, &SIGNED_SPECIAL_TURNS, exact_sin, unit)? {
+        return Ok(angle);
+    }
+    from_radians(value.to_f64()?.asin(), unit)
+}
+
+pub fn acos(value: &Num, unit: AngleUnit) -> Result<Num, ErrorKind> {
+    check_unit_range(value)?;
+    if let Some(angle) = special_angle(value, &HALF_TURN_SPECIAL_TURNS, exact_cos, unit)? {
+        return Ok(angle);
+    }
+    from_radians(value.to_f64()?.acos(), unit)
+}
+
+pub fn atan(value: &Num, unit: AngleUnit) -> Result<Num, ErrorKind> {
+    if let Some(angle) = special_angle(value, &SIGNED_SPECIAL_TURNS, exact_tan, unit)? {
+        return Ok(angle);
+    }
+    from_radians(value.to_f64()?.atan(), unit)
+}
+
+fn decimal_to_radians(angle: &Decimal, unit: AngleUnit) -> Result<Decimal, ErrorKind> {
+    match unit {
+        AngleUnit::Degree => Ok(angle.mul(&Decimal::pi(angle.scale()))?.div_integer(180)),
+        AngleUnit::Radian => Ok(angle.clone()),
+        AngleUnit::Gradian => Ok(angle.mul(&Decimal::pi(angle.scale()))?.div_integer(200)),
+    }
+}
+
+fn decimal_from_radians(radians: &Decimal, unit: AngleUnit) -> Result<Decimal, ErrorKind> {
+    match unit {
+        AngleUnit::Degree => radians.mul_integer(180).div(&Decimal::pi(radians.scale())),
+        AngleUnit::Radian => Ok(radians.clone()),
+        AngleUnit::Gradian => radians.mul_integer(200).div(&Decimal::pi(radians.scale())),
+    }
+}
+
+pub fn sin_precise(angle: &Decimal, unit: AngleUnit) -> Result<Decimal, ErrorKind> {
+    Ok(decimal_to_radians(angle, unit)?.sin())
+}
+
+pub fn cos_precise(angle: &Decimal, unit: AngleUnit) -> Result<Decimal, ErrorKind> {
+    Ok(decimal_to_radians(angle, unit)?.cos())
+}
+
+pub fn tan_precise(angle: &Decimal, unit: AngleUnit) -> Result<Decimal, ErrorKind> {
+    decimal_to_radians(angle, unit)?.tan()
+}
+
+pub fn asin_precise(value: &Decimal, unit: AngleUnit) -> Result<Decimal, ErrorKind> {
+    decimal_from_radians(&value.asin()?, unit)
+}
+
+pub fn acos_precise(value: &Decimal, unit: AngleUnit) -> Result<Decimal, ErrorKind> {
+    decimal_from_radians(&value.acos()?, unit)
+}
+
+pub fn atan_precise(value: &Decimal, unit: AngleUnit) -> Result<Decimal, ErrorKind> {
+    decimal_from_radians(&value.atan()?, unit)
+}
@@ -0,0 +1,21 @@
+use wasm_bindgen::prelude::*;
+use super_calculator_core::{Evaluation, ParseResult};
+
+fn json_to_js(json: Result<String, String>) -> Result<JsValue, JsValue> {
+    let json = json.map_err(|e| JsValue::from_str(&e))?;
+    js_sys::JSON::parse(&json)
+}
+
+/// Evaluates `input` and returns the normalised expression, exact result,
+/// decimal approximation, whether the result is rounded and value kind, or
+/// an error with its code and span.
+#[wasm_bindgen]
+pub fn evaluate(input: &str) -> Result<JsValue, JsValue> {
+    json_to_js(Evaluation::evaluate(input).to_json())
+}
+
+/// Parses `input` and returns its syntax tree.
+#[wasm_bindgen]
+pub fn parse(input: &str) -> Result<JsValue, JsValue> {
+    json_to_js(ParseResult::parse(input).to_json())
+}
@@ -3,26 +3,41 @@ mod document;
 mod element;
 mod html_element;
 mod input_element;
+mod text_area_element;
 mod closure;
 mod event;
+mod clipboard;
+mod future;
+mod storage;
+mod blob;
 
-pub use window::window;
+pub use window::{window, language};
 pub use document::document;
 pub use element::{
     get_element_by_id, create_element, element_append_child,
+    set_attribute,
 };
 pub use html_element::{
-    get_html_element_by_id, append_child, is_scrolled_to_bottom,
-    remove_class, set_class,
+    get_html_element_by_id, element_to_html_element, append_child,
+    is_scrolled_to_bottom, add_class, remove_class, set_class,
 };
 pub use input_element::{
     get_input_element_by_id, get_input_element_from_event,
+    element_to_input_element,
+    insert_text_at_caret, focus,
 };
+pub use text_area_element::element_to_text_area_element;
 pub use closure::{
     create_event_closure, EventClosure, add_event_listener_with_callback,
-    forget_event_closure,
+    remove_event_listener_with_callback,
 };
-pub use event::event_to_keboard_event;
+pub use event::{event_to_keboard_event, event_to_mouse_event};
+pub use clipboard::{write_text_to_clipboard, read_text_from_clipboard};
+pub use future::spawn_local;
+pub use storage::{
+    local_storage, storage_get_item, storage_set_item, storage_remove_item,
+};
+pub use blob::{create_blob_from_text, download_blob, blob_text};
 
 #[allow(unused_macros)]
 #[macro_export]
@@ -0,0 +1,37 @@
+use anyhow::{anyhow, Result};
+use js_sys::Array;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Blob, BlobPropertyBag, HtmlElement, Url};
+
+use super::create_element;
+
+pub fn create_blob_from_text(text: &str, mime_type: &str) -> Result<Blob> {
+    let parts = Array::of1(&JsValue::from_str(text));
+    let mut options = BlobPropertyBag::new();
+    options.type_(mime_type);
+    Blob::new_with_str_sequence_and_options(&parts, &options)
+        .map_err(|e| anyhow!("Error creating Blob: {:?}", e))
+}
+
+pub fn download_blob(blob: &Blob, file_name: &str) -> Result<()> {
+    let url = Url::create_object_url_with_blob(blob)
+        .map_err(|e| anyhow!("Error creating object URL: {:?}", e))?;
+    let anchor = create_element("a")?
+        .dyn_into::<HtmlElement>()
+        .map_err(|element| anyhow!("Error converting {:#?} to HtmlElement", element))?;
+    anchor.set_attribute("href", &url)
+        .and_then(|_| anchor.set_attribute("download", file_name))
+        .map_err(|e| anyhow!("Error setting download attributes: {:?}", e))?;
+    anchor.click();
+    Url::revoke_object_url(&url)
+        .map_err(|e| anyhow!("Error revoking object URL: {:?}", e))
+}
+
+pub async fn blob_text(blob: &Blob) -> Result<String> {
+    JsFuture::from(blob.text())
+        .await
+        .map_err(|e| anyhow!("Error reading Blob: {:?}", e))?
+        .as_string()
+        .ok_or_else(|| anyhow!("Error converting Blob to String"))
+}
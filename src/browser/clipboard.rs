@@ -0,0 +1,32 @@
+use anyhow::{anyhow, Result};
+use js_sys::Promise;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = ["navigator", "clipboard"], js_name = writeText, catch)]
+    fn navigator_clipboard_write_text(data: &str) -> Result<Promise, JsValue>;
+
+    #[wasm_bindgen(js_namespace = ["navigator", "clipboard"], js_name = readText, catch)]
+    fn navigator_clipboard_read_text() -> Result<Promise, JsValue>;
+}
+
+pub async fn write_text_to_clipboard(text: &str) -> Result<()> {
+    let promise = navigator_clipboard_write_text(text)
+        .map_err(|e| anyhow!("Error writing to clipboard: {:?}", e))?;
+    JsFuture::from(promise)
+        .await
+        .map_err(|e| anyhow!("Error writing to clipboard: {:?}", e))?;
+    Ok(())
+}
+
+pub async fn read_text_from_clipboard() -> Result<String> {
+    let promise = navigator_clipboard_read_text()
+        .map_err(|e| anyhow!("Error reading from clipboard: {:?}", e))?;
+    let text = JsFuture::from(promise)
+        .await
+        .map_err(|e| anyhow!("Error reading from clipboard: {:?}", e))?;
+    text.as_string()
+        .ok_or_else(|| anyhow!("Error reading from clipboard: {:?}", text))
+}
@@ -30,6 +30,14 @@ pub fn add_event_listener_with_callback(
         .map_err(|e| anyhow!("Error adding event listener: {:?}", e))
 }
 
-pub fn forget_event_closure(closure: EventClosure) {
-    closure.forget();
+pub fn remove_event_listener_with_callback(
+    element: &EventTarget,
+    event_name: &str,
+    callback: &EventClosure,
+) -> Result<()> {
+    element
+        .remove_event_listener_with_callback(
+            event_name,
+            callback.as_ref().unchecked_ref())
+        .map_err(|e| anyhow!("Error removing event listener: {:?}", e))
 }
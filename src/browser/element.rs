@@ -15,6 +15,12 @@ pub fn create_element(tag: &str) -> Result<Element> {
         .map_err(|_| anyhow!("Error creating element with tag {}", tag))
 }
 
+pub fn set_attribute(element: &Element, name: &str, value: &str) -> Result<()> {
+    element
+        .set_attribute(name, value)
+        .map_err(|_| anyhow!("Error setting attribute {}", name))
+}
+
 pub fn element_append_child(parent: &Element, child: &Node) -> Result<Node> {
     parent
         .append_child(child)
@@ -1,6 +1,6 @@
 use anyhow::{anyhow, Result};
 use wasm_bindgen::JsCast;
-use web_sys::{Event, KeyboardEvent};
+use web_sys::{Event, KeyboardEvent, MouseEvent};
 
 pub fn event_to_keboard_event(event: &Event) -> Result<KeyboardEvent> {
     event
@@ -8,3 +8,10 @@ pub fn event_to_keboard_event(event: &Event) -> Result<KeyboardEvent> {
         .ok_or_else(|| anyhow!("No KeyboardEvent found on event"))
         .map(|event| event.clone())
 }
+
+pub fn event_to_mouse_event(event: &Event) -> Result<MouseEvent> {
+    event
+        .dyn_ref::<MouseEvent>()
+        .ok_or_else(|| anyhow!("No MouseEvent found on event"))
+        .map(|event| event.clone())
+}
@@ -1,11 +1,15 @@
 use anyhow::{anyhow, Result};
 use wasm_bindgen::JsCast;
-use web_sys::{Node, HtmlElement};
+use web_sys::{Element, Node, HtmlElement};
 
 use super::get_element_by_id;
 
 pub fn get_html_element_by_id(id: &str) -> Result<HtmlElement> {
-    get_element_by_id(id)?
+    element_to_html_element(get_element_by_id(id)?)
+}
+
+pub fn element_to_html_element(element: Element) -> Result<HtmlElement> {
+    element
         .dyn_into::<HtmlElement>()
         .map_err(|element| anyhow!("Error converting {:#?} to HtmlElement", element))
 }
@@ -14,6 +18,12 @@ pub fn set_class(element: &HtmlElement, class: &str) {
     element.set_class_name(class)
 }
 
+pub fn add_class(element: &HtmlElement, class: &str) -> Result<()> {
+    element.class_list()
+        .add_1(class)
+        .map_err(|_| anyhow!("Error adding class {}", class))
+}
+
 pub fn remove_class(element: &HtmlElement, class: &str) -> Result<()> {
     element.class_list()
         .remove_1(class)
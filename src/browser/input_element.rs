@@ -1,11 +1,15 @@
 use anyhow::{anyhow, Result};
 use wasm_bindgen::JsCast;
-use web_sys::{HtmlInputElement, Event};
+use web_sys::{Element, HtmlInputElement, Event};
 
 use super::get_element_by_id;
 
 pub fn get_input_element_by_id(id: &str) -> Result<HtmlInputElement> {
-    get_element_by_id(id)?
+    element_to_input_element(get_element_by_id(id)?)
+}
+
+pub fn element_to_input_element(element: Element) -> Result<HtmlInputElement> {
+    element
         .dyn_into::<HtmlInputElement>()
         .map_err(|element| anyhow!("Error converting {:#?} to HtmlInputElement", element))
 }
@@ -17,3 +21,26 @@ pub fn get_input_element_from_event(event: &Event) -> Result<HtmlInputElement> {
         .dyn_into::<HtmlInputElement>()
         .map_err(|element| anyhow!("Error converting {:#?} to HtmlInputElement", element))
 }
+
+pub fn insert_text_at_caret(element: &HtmlInputElement, text: &str) -> Result<()> {
+    let length = element.value().encode_utf16().count() as u32;
+    let start = element.selection_start()
+        .map_err(|_| anyhow!("Error getting selection start"))?
+        .unwrap_or(length);
+    let end = element.selection_end()
+        .map_err(|_| anyhow!("Error getting selection end"))?
+        .unwrap_or(length);
+    element
+        .set_range_text_with_start_and_end(text, start, end)
+        .map_err(|_| anyhow!("Error inserting text into input"))?;
+    let caret = start + text.encode_utf16().count() as u32;
+    element
+        .set_selection_range(caret, caret)
+        .map_err(|_| anyhow!("Error setting selection range"))
+}
+
+pub fn focus(element: &HtmlInputElement) -> Result<()> {
+    element
+        .focus()
+        .map_err(|_| anyhow!("Error focusing input element"))
+}
@@ -0,0 +1,29 @@
+use anyhow::{anyhow, Result};
+use web_sys::Storage;
+
+use super::window;
+
+pub fn local_storage() -> Result<Storage> {
+    window()?
+        .local_storage()
+        .map_err(|e| anyhow!("Error getting localStorage: {:?}", e))?
+        .ok_or_else(|| anyhow!("No localStorage Found"))
+}
+
+pub fn storage_get_item(storage: &Storage, key: &str) -> Result<Option<String>> {
+    storage
+        .get_item(key)
+        .map_err(|e| anyhow!("Error getting item {} from storage: {:?}", key, e))
+}
+
+pub fn storage_set_item(storage: &Storage, key: &str, value: &str) -> Result<()> {
+    storage
+        .set_item(key, value)
+        .map_err(|e| anyhow!("Error setting item {} to storage: {:?}", key, e))
+}
+
+pub fn storage_remove_item(storage: &Storage, key: &str) -> Result<()> {
+    storage
+        .remove_item(key)
+        .map_err(|e| anyhow!("Error removing item {} from storage: {:?}", key, e))
+}
@@ -0,0 +1,9 @@
+use anyhow::{anyhow, Result};
+use wasm_bindgen::JsCast;
+use web_sys::{Element, HtmlTextAreaElement};
+
+pub fn element_to_text_area_element(element: Element) -> Result<HtmlTextAreaElement> {
+    element
+        .dyn_into::<HtmlTextAreaElement>()
+        .map_err(|element| anyhow!("Error converting {:#?} to HtmlTextAreaElement", element))
+}
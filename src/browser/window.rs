@@ -4,3 +4,7 @@ use web_sys::Window;
 pub fn window() -> Result<Window> {
     web_sys::window().ok_or_else(|| anyhow!("No Window Found"))
 }
+
+pub fn language() -> Option<String> {
+    web_sys::window()?.navigator().language()
+}
@@ -1,269 +1,113 @@
 mod setup;
 mod format;
 mod history;
-mod num;
-mod term;
-mod operator;
-mod expression;
-mod node;
-mod paren;
-mod fraction;
+mod persistence;
+mod layout;
+mod document;
+mod programmer;
 
-use anyhow::Result;
-use expression::Expression;
+use super_calculator_core::{Settings, History, Environment};
+use crate::engine::{Dom, WebDom};
 
 const INPUT_AREA: &str = "input-area";
 const FORMATTED_DISPLAY: &str = "formatted-display";
+const PROGRAMMER_DISPLAY: &str = "programmer-display";
+const PROGRAMMER_ROW: &str = "programmer-row";
+const BIT_FIELD_ROW: &str = "bit-field-row";
 const HISTORY_CONTAINER: &str = "history-container";
 const HELP_BUTTON: &str = "help-button";
 const HELP_POPUP_CONTAINER: &str = "help-popup-container";
+const HELP_CONTENT: &str = "help-content";
 const CLOSE_HELP: &str = "close-help";
 const HISTORY_ITEM: &str = "history-item";
+const HISTORY_TEXT: &str = "history-text";
+const COPY_BUTTON: &str = "copy-button material-symbols-outlined";
+const EXPORT_JSON: &str = "export-json";
+const EXPORT_CSV: &str = "export-csv";
+const EXPORT_MARKDOWN: &str = "export-markdown";
+const IMPORT_FILE: &str = "import-file";
+const IMPORT_LABEL: &str = "インポート";
+const PASTE_SCRIPT: &str = "paste-script";
+const PASTE_SCRIPT_LABEL: &str = "スクリプト貼り付け";
+const MODE_BUTTON: &str = "mode-button";
+const ANGLE_BUTTON: &str = "angle-button";
+const WIDTH_BUTTON: &str = "width-button";
+const NOTATION_BUTTON: &str = "notation-button";
+const LOCALE_BUTTON: &str = "locale-button";
+const LOCALE_SAMPLE: &str = "1234567";
+const EXACT_MODE_LABEL: &str = "EXACT";
+const CALCULATOR_VIEW: &str = "calculator-view";
+const DOCUMENT_VIEW: &str = "document-view";
+const DOCUMENT_AREA: &str = "document-area";
+const DOCUMENT_RESULTS: &str = "document-results";
+const DOCUMENT_LINE: &str = "document-line";
+const DOCUMENT_MODE_LABEL: &str = "ノート";
+const CALCULATOR_MODE_LABEL: &str = "電卓";
 const HIDDEN: &str = "hidden";
-
-pub struct Calculator {}
-
-impl Calculator {
-    fn calculate_and_format(input: &str) -> Result<String, String> {
-        let expression = Expression::parse(input)?;
-        let result = expression.calculate()?;
-        Ok(format!("{} → {}", expression, result))
-    }
+const CLEAR_COMMAND: &str = "clear";
+const HISTORY_STORAGE_KEY: &str = "super_calculator.history";
+const SETTINGS_STORAGE_SUFFIX: &str = "settings";
+const ENVIRONMENT_STORAGE_SUFFIX: &str = "variables";
+
+pub struct Calculator<D: Dom = WebDom> {
+    dom: D,
+    input: D::HtmlInputElement,
+    display: D::HtmlElement,
+    programmer_display: D::HtmlElement,
+    history_container: D::HtmlElement,
+    help_popup: D::HtmlElement,
+    mode_button: D::HtmlElement,
+    angle_button: D::HtmlElement,
+    width_button: D::HtmlElement,
+    notation_button: D::HtmlElement,
+    locale_button: D::HtmlElement,
+    calculator_view: D::HtmlElement,
+    document_view: D::HtmlElement,
+    document_area: D::HtmlTextAreaElement,
+    document_results: D::HtmlElement,
+    document_mode: bool,
+    root: Option<D::Element>,
+    listeners: std::cell::RefCell<Vec<D::EventListener>>,
+    history_listeners: std::cell::RefCell<Vec<D::EventListener>>,
+    settings: Settings,
+    history_storage_key: Option<String>,
+    history: History,
+    environment: Environment,
+    this: std::rc::Weak<std::cell::RefCell<Self>>,
 }
 
 #[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn parse_normal() {
-        let input = "1 + 2";
-        let expected = "1 + 2 → 3";
-        let actual = Calculator::calculate_and_format(input).unwrap();
-        assert_eq!(expected, actual);
-    }
-
-    #[test]
-    fn parse_invalid_operator() {
-        let input = "1 & 2";
-        let expected = "対応していない文字です";
-        match Calculator::calculate_and_format(input) {
-            Ok(_) => panic!("should be error"),
-            Err(e) => assert_eq!(expected, e),
-        }
-    }
-
-    #[test]
-    fn parse_missing_operator() {
-        let input = "1 2";
-        let expected = "数値が連続しています";
-        match Calculator::calculate_and_format(input) {
-            Ok(_) => panic!("should be error"),
-            Err(e) => assert_eq!(expected, e),
-        }
-    }
-
-    #[test]
-    fn parse_missing_right_operand() {
-        let input = "1 + ";
-        let expected = "構文解析に失敗しました";
-        match Calculator::calculate_and_format(input) {
-            Ok(_) => panic!("should be error"),
-            Err(e) => assert_eq!(expected, e),
-        }
-    }
-
-    #[test]
-    fn parse_many_spaces() {
-        let input = "  1  +  2  ";
-        let expected = "1 + 2 → 3";
-        let actual = Calculator::calculate_and_format(input).unwrap();
-        assert_eq!(expected, actual);
-    }
-
-    #[test]
-    fn parse_paren_normal() {
-        let input = "2 - (1 + 2) * 3";
-        let expected = "2 - ( 1 + 2 ) * 3 → -7";
-        let actual = Calculator::calculate_and_format(input).unwrap();
-        assert_eq!(expected, actual);
-    }
-
-    #[test]
-    fn parse_paren_unary() {
-        let input = "-(-2 + 1) * 3";
-        let expected = "- ( - 2 + 1 ) * 3 → 3";
-        let actual = Calculator::calculate_and_format(input).unwrap();
-        assert_eq!(expected, actual);
-    }
-
-    #[test]
-    fn parse_paren_num_to_paren() {
-        let input = "1 + 2(2 * 3)";
-        let expected = "1 + 2 * ( 2 * 3 ) → 13";
-        let actual = Calculator::calculate_and_format(input).unwrap();
-        assert_eq!(expected, actual);
-    }
-
-    #[test]
-    fn parse_paren_paren_to_num() {
-        let input = "(1 + 2)2 * 3";
-        let expected = "( 1 + 2 ) * 2 * 3 → 18";
-        let actual = Calculator::calculate_and_format(input).unwrap();
-        assert_eq!(expected, actual);
-    }
-
-    #[test]
-    fn parse_paren_missing_right_paren() {
-        let input = "1 + (2 * 3";
-        let expected = "括弧が閉じられていません";
-        match Calculator::calculate_and_format(input) {
-            Ok(_) => panic!("should be error"),
-            Err(e) => assert_eq!(expected, e),
-        }
-    }
-
-    #[test]
-    fn parse_paren_missing_left_paren() {
-        let input = "1 + 2) * 3";
-        let expected = "括弧の対応が取れていません";
-        match Calculator::calculate_and_format(input) {
-            Ok(_) => panic!("should be error"),
-            Err(e) => assert_eq!(expected, e),
-        }
-    }
-
-    #[test]
-    fn parse_paren_missing_element() {
-        let input = "1 + ()";
-        let expected = "括弧の中に要素がありませんでした";
-        match Calculator::calculate_and_format(input) {
-            Ok(_) => panic!("should be error"),
-            Err(e) => assert_eq!(expected, e),
-        }
-    }
-
-    #[test]
-    fn parse_fraction_normal() {
-        let input = "1/2 + 3/4";
-        let expected = "1 / 2 + 3 / 4 → 5 / 4";
-        let actual = Calculator::calculate_and_format(input).unwrap();
-        assert_eq!(expected, actual);
-    }
-
-    #[test]
-    fn parse_fraction_denominator_minus() {
-        let input = "1/2 + 3/(-4)";
-        let expected = "1 / 2 + 3 / ( - 4 ) → -1 / 4";
-        let actual = Calculator::calculate_and_format(input).unwrap();
-        assert_eq!(expected, actual);
-    }
-
-    #[test]
-    fn test_add_normal() {
-        let input = "1 + 2";
-        let expected = "1 + 2 → 3";
-        let actual = Calculator::calculate_and_format(input).unwrap();
-        assert_eq!(expected, actual);
-    }
-
-    #[test]
-    fn test_add_overflow() {
-        let input = "2147483647 + 1";
-        let expected = "int32の範囲を超える加算です";
-        match Calculator::calculate_and_format(input) {
-            Ok(_) => panic!("should be error"),
-            Err(e) => assert_eq!(expected, e),
-        }
-    }
-
-    #[test]
-    fn test_sub_normal() {
-        let input = "1 - 2";
-        let expected = "1 - 2 → -1";
-        let actual = Calculator::calculate_and_format(input).unwrap();
-        assert_eq!(expected, actual);
-    }
-
-    #[test]
-    fn test_sub_overflow() {
-        let input = "-2147483647 - 2";
-        let expected = "int32の範囲を超える減算です";
-        match Calculator::calculate_and_format(input) {
-            Ok(_) => panic!("should be error"),
-            Err(e) => assert_eq!(expected, e),
-        }
-    }
-
-    #[test]
-    fn test_mul_normal() {
-        let input = "2 * 3";
-        let expected = "2 * 3 → 6";
-        let actual = Calculator::calculate_and_format(input).unwrap();
-        assert_eq!(expected, actual);
-    }
-
-    #[test]
-    fn test_mul_overflow() {
-        let input = "2147483647 * 2";
-        let expected = "int32の範囲を超える乗算です";
-        match Calculator::calculate_and_format(input) {
-            Ok(_) => panic!("should be error"),
-            Err(e) => assert_eq!(expected, e),
-        }
-    }
-
-    #[test]
-    fn test_div_normal() {
-        let input = "6 / 3";
-        let expected = "6 / 3 → 2";
-        let actual = Calculator::calculate_and_format(input).unwrap();
-        assert_eq!(expected, actual);
-    }
-
-    #[test]
-    fn test_div_overflow() {
-        let input = "(-2147483647 - 1) / (-1)";
-        let expected = "int32の範囲を超える除算です";
-        match Calculator::calculate_and_format(input) {
-            Ok(_) => panic!("should be error"),
-            Err(e) => assert_eq!(expected, e),
-        }
-    }
-
-    #[test]
-    fn test_div_by_zero() {
-        let input = "1 / 0";
-        let expected = "0で割ることはできません";
-        match Calculator::calculate_and_format(input) {
-            Ok(_) => panic!("should be error"),
-            Err(e) => assert_eq!(expected, e),
-        }
-    }
-
-    #[test]
-    fn test_plus_normal() {
-        let input = "+1";
-        let expected = "+ 1 → 1";
-        let actual = Calculator::calculate_and_format(input).unwrap();
-        assert_eq!(expected, actual);
-    }
-
-    #[test]
-    fn test_minus_normal() {
-        let input = "-1";
-        let expected = "- 1 → -1";
-        let actual = Calculator::calculate_and_format(input).unwrap();
-        assert_eq!(expected, actual);
-    }
+fn calculate(input: &str) -> Result<super_calculator_core::Calculation, String> {
+    super_calculator_core::Calculation::calculate(
+        input, &mut Environment::default(), &Settings::default())
+}
 
-    #[test]
-    fn test_minus_not_supported() {
-        let input = "-2147483648";
-        let expected = "数値に変換できません";
-        match Calculator::calculate_and_format(input) {
-            Ok(_) => panic!("should be error"),
-            Err(e) => assert_eq!(expected, e),
-        }
-    }
+#[cfg(test)]
+fn fake_dom() -> crate::engine::FakeDom {
+    let dom = crate::engine::FakeDom::new();
+    dom.add_element("button", HELP_BUTTON);
+    let help_popup = dom.add_element("div", HELP_POPUP_CONTAINER);
+    crate::engine::DomHtmlElement::set_class(&help_popup, HIDDEN);
+    dom.add_element("div", HELP_CONTENT);
+    dom.add_element("button", CLOSE_HELP);
+    dom.add_element("button", EXPORT_JSON);
+    dom.add_element("button", EXPORT_CSV);
+    dom.add_element("button", EXPORT_MARKDOWN);
+    dom.add_element("input", IMPORT_FILE);
+    dom.add_element("button", PASTE_SCRIPT);
+    dom.add_element("div", HISTORY_CONTAINER);
+    dom.add_element("input", INPUT_AREA);
+    dom.add_element("div", FORMATTED_DISPLAY);
+    dom.add_element("div", PROGRAMMER_DISPLAY);
+    dom.add_element("button", MODE_BUTTON);
+    dom.add_element("button", ANGLE_BUTTON);
+    dom.add_element("button", WIDTH_BUTTON);
+    dom.add_element("button", NOTATION_BUTTON);
+    dom.add_element("button", LOCALE_BUTTON);
+    dom.add_element("div", CALCULATOR_VIEW);
+    let document_view = dom.add_element("div", DOCUMENT_VIEW);
+    crate::engine::DomHtmlElement::set_class(&document_view, HIDDEN);
+    dom.add_element("textarea", DOCUMENT_AREA);
+    dom.add_element("div", DOCUMENT_RESULTS);
+    dom
 }
@@ -0,0 +1,109 @@
+use anyhow::Result;
+use crate::engine::{Dom, DomElement, DomHtmlElement, DomHtmlTextAreaElement};
+use super_calculator_core::{Document, LineResult};
+use super::{
+    Calculator, HIDDEN, DOCUMENT_LINE, DOCUMENT_MODE_LABEL, CALCULATOR_MODE_LABEL,
+};
+
+impl<D: Dom> Calculator<D> {
+    pub(super) fn handle_mode_toggle(&mut self) -> Result<()> {
+        self.document_mode = !self.document_mode;
+        if self.document_mode {
+            self.calculator_view.add_class(HIDDEN)?;
+            self.document_view.remove_class(HIDDEN)?;
+            self.mode_button.set_inner_text(CALCULATOR_MODE_LABEL);
+        } else {
+            self.document_view.add_class(HIDDEN)?;
+            self.calculator_view.remove_class(HIDDEN)?;
+            self.mode_button.set_inner_text(DOCUMENT_MODE_LABEL);
+        }
+        Ok(())
+    }
+
+    pub(super) fn handle_document_input(&mut self) -> Result<()> {
+        let document = Document::evaluate(&self.document_area.get_value(), &self.settings);
+        self.render_document(&document)
+    }
+
+    // One result row per line, so that the rows line up with the text area.
+    fn render_document(&self, document: &Document) -> Result<()> {
+        self.document_results.set_inner_text("");
+        for line in document.lines() {
+            let row = self.dom.create_element("div")?;
+            row.set_class_name(DOCUMENT_LINE);
+            match &line.result {
+                LineResult::Comment => {}
+                LineResult::Value(calculation) if calculation.approximate => {
+                    let result = calculation.grouped_result(self.settings.locale);
+                    row.set_text_content(&format!("{} {}", calculation.relation(), result));
+                }
+                LineResult::Value(calculation) => {
+                    row.set_text_content(&calculation.grouped_result(self.settings.locale));
+                }
+                LineResult::Error(error) => {
+                    let error_node = self.dom.create_element("span")?;
+                    error_node.set_class_name("error-text");
+                    error_node.set_text_content(error);
+                    row.append_child(&error_node.into())?;
+                }
+            }
+            self.document_results.append_child(&row.into())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::{FakeDom, FakeElement};
+    use crate::calculator::{
+        fake_dom, Calculator, CALCULATOR_VIEW, DOCUMENT_VIEW, DOCUMENT_AREA,
+        DOCUMENT_RESULTS, DOCUMENT_LINE, MODE_BUTTON, HIDDEN, DOCUMENT_MODE_LABEL,
+        CALCULATOR_MODE_LABEL,
+    };
+
+    fn setup() -> FakeDom {
+        let dom = fake_dom();
+        let calculator = Calculator::setup_dom(dom.clone()).unwrap();
+        dom.keep_alive(calculator);
+        dom
+    }
+
+    fn rows(dom: &FakeDom) -> Vec<FakeElement> {
+        dom.get(DOCUMENT_RESULTS).find_by_class(DOCUMENT_LINE)
+    }
+
+    #[test]
+    fn mode_button_switches_views() {
+        let dom = setup();
+        let mode_button = dom.get(MODE_BUTTON);
+        mode_button.click();
+        assert!(dom.get(CALCULATOR_VIEW).has_class(HIDDEN));
+        assert!(!dom.get(DOCUMENT_VIEW).has_class(HIDDEN));
+        assert_eq!(CALCULATOR_MODE_LABEL, mode_button.text_content());
+
+        mode_button.click();
+        assert!(!dom.get(CALCULATOR_VIEW).has_class(HIDDEN));
+        assert!(dom.get(DOCUMENT_VIEW).has_class(HIDDEN));
+        assert_eq!(DOCUMENT_MODE_LABEL, mode_button.text_content());
+    }
+
+    #[test]
+    fn document_input_renders_line_results() {
+        let dom = setup();
+        dom.get(DOCUMENT_AREA).input("家賃\nrent = 1200\nrent / 0\nline2 / 2\nπ + 1");
+        let texts: Vec<String> = rows(&dom).iter().map(|row| row.text_content()).collect();
+        assert_eq!(vec!["", "1200", "0で割ることはできません", "600", "≈ 4.1415926536"], texts);
+        assert_eq!(1, rows(&dom)[2].find_by_class("error-text").len());
+    }
+
+    #[test]
+    fn document_input_replaces_previous_results() {
+        let dom = setup();
+        let document_area = dom.get(DOCUMENT_AREA);
+        document_area.input("1\n2\n3");
+        document_area.input("1 + 1");
+        assert_eq!(1, rows(&dom).len());
+        assert_eq!("2", rows(&dom)[0].text_content());
+    }
+}
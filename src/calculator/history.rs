@@ -1,46 +1,228 @@
 use anyhow::Result;
 use crate::engine::{
-    HtmlElement, Node, Element,
+    Dom, DomElement, DomHtmlElement, DomEvent, DomEventListener, DomMouseEvent,
+};
+use super_calculator_core::{Calculation, HistoryEntry};
+use super::{
+    Calculator, HISTORY_ITEM, HISTORY_TEXT, COPY_BUTTON,
 };
-use super::{Calculator, HISTORY_ITEM};
 
-impl Calculator {
-    fn add_history_bottom(history_container: &HtmlElement, new_entry: &Node) -> Result<()> {
-        let at_bottom = history_container.is_scrolled_to_bottom();
+impl<D: Dom> Calculator<D> {
+    fn add_history_bottom(&self, new_entry: &D::Node) -> Result<()> {
+        let at_bottom = self.history_container.is_scrolled_to_bottom();
 
-        history_container.append_child(&new_entry)?;
+        self.history_container.append_child(new_entry)?;
 
         if at_bottom {
-            history_container.scroll_to_bottom();
+            self.history_container.scroll_to_bottom();
         }
         Ok(())
     }
 
-    fn add_error_entry(new_entry: &Element, value: &str, error: &str) -> Result<()> {
-        let text_node = Element::new_from_tag("span")?;
+    fn add_error_entry(&self, new_entry: &D::Element, value: &str, error: &str) -> Result<()> {
+        let text_node = self.dom.create_element("span")?;
         text_node.set_text_content(value);
         new_entry.append_child(&text_node.into())?;
 
-        let error_node = Element::new_from_tag("span")?;
+        let error_node = self.dom.create_element("span")?;
         error_node.set_class_name("error-text");
         error_node.set_text_content(&format!(" {}", error));
         new_entry.append_child(&error_node.into())?;
         Ok(())
     }
 
-    pub(super) fn add_history_entry(value: &str, result: &Result<String, String>, history_container: &HtmlElement) -> Result<()> {
-        let new_entry = Element::new_from_tag("div")?;
-        match &result {
-            Ok(result) => {
-                new_entry.set_text_content(result);
+    fn add_result_entry(&self, new_entry: &D::Element, calculation: &Calculation) -> Result<()> {
+        let text_node = self.dom.create_element("span")?;
+        text_node.set_class_name(HISTORY_TEXT);
+        text_node.set_text_content(&calculation.to_grouped_string(self.settings.locale));
+        new_entry.append_child(&text_node.into())?;
+
+        let copy_button = self.dom.create_element("button")?;
+        copy_button.set_class_name(COPY_BUTTON);
+        copy_button.set_attribute("title", "結果をコピー")?;
+        copy_button.set_text_content("content_copy");
+        let result = calculation.result.clone();
+        self.add_history_listener(&copy_button, "click", move |calculator, e| {
+            calculator.handle_copy_click(e, &result)
+        })?;
+        new_entry.append_child(&copy_button.into())?;
+        Ok(())
+    }
+
+    fn add_history_click(&self, new_entry: &D::Element, text: String, modifier_text: String) -> Result<()> {
+        self.add_history_listener(new_entry, "click", move |calculator, e| {
+            calculator.handle_history_click(e, &text, &modifier_text)
+        })
+    }
+
+    pub(super) fn remove_history_listeners(&self) -> Result<()> {
+        for listener in self.history_listeners.take() {
+            listener.remove()?;
+        }
+        Ok(())
+    }
+
+    fn handle_history_click(&mut self, event: D::Event, text: &str, modifier_text: &str) -> Result<()> {
+        let event = match event.to_mouse_event() {
+            Ok(event) => event,
+            Err(_) => {
+                return Ok(());
+            }
+        };
+        if event.modifier_key() {
+            self.insert_into_input(modifier_text)
+        } else {
+            self.insert_into_input(text)
+        }
+    }
+
+    fn handle_copy_click(&mut self, event: D::Event, result: &str) -> Result<()> {
+        event.stop_propagation();
+        self.dom.write_clipboard_text(result);
+        Ok(())
+    }
+
+    pub(super) fn add_history_entry(&self, entry: &HistoryEntry) -> Result<()> {
+        let value = &entry.input;
+        let new_entry = self.dom.create_element("div")?;
+        match &entry.calculation() {
+            Ok(calculation) => {
+                self.add_result_entry(&new_entry, calculation)?;
+                self.add_history_click(
+                    &new_entry, calculation.value.clone(), value.clone())?;
             }
             Err(error) => {
-                Self::add_error_entry(&new_entry, value, error)?;
+                self.add_error_entry(&new_entry, value, error)?;
+                self.add_history_click(
+                    &new_entry, value.clone(), value.clone())?;
             }
         }
         new_entry.set_class_name(HISTORY_ITEM);
 
-        Self::add_history_bottom(&history_container, &new_entry.into())?;
+        self.add_history_bottom(&new_entry.into())?;
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+    use std::cell::RefCell;
+    use crate::engine::{FakeDom, FakeElement, FakeEvent, DomHtmlInputElement};
+    use crate::calculator::{fake_dom, calculate, HISTORY_CONTAINER, INPUT_AREA, FORMATTED_DISPLAY};
+    use super_calculator_core::{Environment, Notation, Settings};
+
+    fn setup() -> (FakeDom, Rc<RefCell<Calculator<FakeDom>>>) {
+        let dom = fake_dom();
+        let calculator = Calculator::setup_dom(dom.clone()).unwrap();
+        (dom, calculator)
+    }
+
+    fn render(dom: &FakeDom, calculator: &Rc<RefCell<Calculator<FakeDom>>>, input: &str) -> FakeElement {
+        let entry = HistoryEntry::new(input, &calculate(input));
+        calculator.borrow().add_history_entry(&entry).unwrap();
+        dom.get(HISTORY_CONTAINER).children().last().unwrap().clone()
+    }
+
+    #[test]
+    fn result_entry_has_text_and_copy_button() {
+        let (dom, calculator) = setup();
+        let entry = render(&dom, &calculator, "1/2 + 3/4");
+        assert!(entry.has_class(HISTORY_ITEM));
+        let text = entry.find_by_class(HISTORY_TEXT);
+        assert_eq!("1 / 2 + 3 / 4 → 5 / 4", text[0].text_content());
+        let copy_button = entry.find_by_class("copy-button");
+        assert_eq!("button", copy_button[0].tag());
+        assert_eq!(Some("結果をコピー".to_string()), copy_button[0].attribute("title"));
+    }
+
+    #[test]
+    fn approximate_entry_is_marked() {
+        let (dom, calculator) = setup();
+        let entry = render(&dom, &calculator, "π + 1");
+        let text = entry.find_by_class(HISTORY_TEXT);
+        assert_eq!("π + 1 ≈ 4.1415926536", text[0].text_content());
+    }
+
+    #[test]
+    fn result_digits_are_grouped_for_language() {
+        let dom = fake_dom();
+        dom.set_language("de-DE");
+        let calculator = Calculator::setup_dom(dom.clone()).unwrap();
+        let entry = render(&dom, &calculator, "1,000,000 * 3 / 2");
+        let text = entry.find_by_class(HISTORY_TEXT);
+        assert_eq!("1,000,000 * 3 / 2 → 1.500.000", text[0].text_content());
+        let input = dom.get(INPUT_AREA);
+        entry.click();
+        assert_eq!("1500000", input.value());
+    }
+
+    #[test]
+    fn error_entry_has_error_text() {
+        let (dom, calculator) = setup();
+        let entry = render(&dom, &calculator, "1 + ");
+        assert_eq!("1 +  構文解析に失敗しました", entry.text_content());
+        assert!(entry.find_by_class("copy-button").is_empty());
+    }
+
+    #[test]
+    fn click_inserts_result_at_caret() {
+        let (dom, calculator) = setup();
+        let entry = render(&dom, &calculator, "1/2 + 3/4");
+        let input = dom.get(INPUT_AREA);
+        input.set_value("2 * ");
+        entry.click();
+        assert_eq!("2 * 5 / 4", input.value());
+        assert_eq!("2 * 5 / 4", dom.get(FORMATTED_DISPLAY).text_content());
+        assert!(input.is_focused());
+    }
+
+    #[test]
+    fn click_inserts_standard_value_for_notation() {
+        let (dom, calculator) = setup();
+        let input = dom.get(INPUT_AREA);
+        for (notation, expression, expected) in [
+            (Notation::Kanji, "300 + 20", "320"),
+            (Notation::Si, "470n * 1000", "47 / 100000"),
+            (Notation::Si, "1 / 3", "1 / 3"),
+            (Notation::Myriad, "π * 10000", "10000π"),
+        ] {
+            let settings = Settings { notation, ..Settings::default() };
+            let result = Calculation::calculate(expression, &mut Environment::default(), &settings);
+            calculator.borrow().add_history_entry(&HistoryEntry::new(expression, &result)).unwrap();
+            let entry = dom.get(HISTORY_CONTAINER).children().last().unwrap().clone();
+            input.set_value("");
+            entry.click();
+            assert_eq!(expected, input.value(), "{}", expression);
+        }
+    }
+
+    #[test]
+    fn modifier_click_inserts_expression() {
+        let (dom, calculator) = setup();
+        let entry = render(&dom, &calculator, "1+2");
+        let input = dom.get(INPUT_AREA);
+        input.set_value("()");
+        input.set_caret(1);
+        entry.dispatch("click", FakeEvent::mouse(true));
+        assert_eq!("(1+2)", input.value());
+    }
+
+    #[test]
+    fn error_entry_click_inserts_input() {
+        let (dom, calculator) = setup();
+        let entry = render(&dom, &calculator, "1 2");
+        entry.click();
+        assert_eq!("1 2", dom.get(INPUT_AREA).value());
+    }
+
+    #[test]
+    fn copy_button_writes_clipboard() {
+        let (dom, calculator) = setup();
+        let entry = render(&dom, &calculator, "1/2 + 3/4");
+        entry.find_by_class("copy-button")[0].click();
+        assert_eq!(vec!["5 / 4".to_string()], dom.clipboard());
+        assert_eq!("", dom.get(INPUT_AREA).value());
+    }
+}
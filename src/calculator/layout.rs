@@ -0,0 +1,208 @@
+use anyhow::Result;
+use crate::engine::{Dom, DomElement};
+use super_calculator_core::ExportFormat;
+use super::{
+    INPUT_AREA, FORMATTED_DISPLAY, PROGRAMMER_DISPLAY, HISTORY_CONTAINER, HELP_BUTTON,
+    HELP_POPUP_CONTAINER, HELP_CONTENT, CLOSE_HELP, EXPORT_JSON, EXPORT_CSV, EXPORT_MARKDOWN,
+    IMPORT_FILE, IMPORT_LABEL, PASTE_SCRIPT, PASTE_SCRIPT_LABEL, HIDDEN, MODE_BUTTON, ANGLE_BUTTON,
+    WIDTH_BUTTON, NOTATION_BUTTON, LOCALE_BUTTON,
+    CALCULATOR_VIEW,
+    DOCUMENT_VIEW, DOCUMENT_AREA, DOCUMENT_RESULTS, DOCUMENT_MODE_LABEL,
+};
+
+const WIDGET_ROOT: &str = "super-calculator";
+const HISTORY_MENU: &str = "history-menu";
+const IMPORT_LABEL_CLASS: &str = "import-label";
+const HELP_POPUP: &str = "help-popup";
+const DEVICE: &str = "device";
+const INPUT_PLACEHOLDER: &str = "数式を入力してください";
+const DOCUMENT_PLACEHOLDER: &str = "1行に1つずつ式を書いてください";
+// The only copy of the help, rendered into both the page and generated
+// calculators.
+pub(super) const HELP_TEXT: &[(&str, &str)] = &[
+    ("h2", "操作方法"),
+    ("p", "Ctrl + Enter を押して入力を確定します。"),
+    ("p", "履歴をクリックすると結果を入力欄に挿入します。"),
+    ("p", "Ctrl (Mac では Cmd) を押しながらクリックすると元の式を挿入します。"),
+    ("p", "履歴はブラウザに保存されます。clear と入力して確定すると履歴を消去します。"),
+    ("p", "ans と入力すると直前の計算結果を使えます。"),
+    ("p", "x = 1 + 2 のように入力すると変数に代入できます。"),
+    ("p", "a = 3; b = 4; a * b のように ; で区切ると順に計算し、文ごとに履歴へ追加します。"),
+    ("p", "ノートボタンで複数行のメモに切り替えます。各行の結果が右側に表示され、line1 のように前の行を参照できます。"),
+    ("p", "左上のボタンで履歴を JSON / CSV / Markdown に書き出し、JSON から読み込めます。"),
+    ("p", "スクリプト貼り付けでクリップボードの .calc スクリプトを1行ずつ計算します。# 以降はコメントです。"),
+    ("h3", "機能"),
+    ("p", "・四則演算"),
+    ("p", "・括弧"),
+    ("p", "・定数 π (pi) と e (2π や π / 2 のまま計算します)"),
+    ("p", "・平方根 sqrt(x) と √x (√8 は 2√2 のように簡約します)"),
+    ("p", "・三角関数 sin, cos, tan と逆関数 asin, acos, atan (sin(30°) = 1 / 2 のように特別な角度は正確に計算します)"),
+    ("p", "・対数と指数 ln, log10, log2, log(底, x), exp (log2(1024) = 10 のように割り切れる場合は正確に計算します)"),
+    ("p", "・N(式, 桁数) で近似値を指定した有効桁数まで計算します (N(π, 100) など。桁数を省略すると50桁)"),
+    ("p", "・0x1F, 0b1010, 0o17 のように16進数・2進数・8進数で入力できます。255 in hex や to_bin(10) で変換し、整数の結果は入力欄の下に4つの基数で表示します"),
+    ("p", "・ビット演算 & (AND), | (OR), xor, ~ (NOT), <<, >> (優先順位は C と同じです。整数だけに使え、結果は i32 などの型に合わせて切り詰めます)"),
+    ("p", "・右上の EXACT ボタンでプログラマーモードに切り替え、i8 から u64 までの型を選びます。整数はその型として計算し、桁あふれは折り返し、割り算は切り捨てます。小数や π などの整数以外はエラーになります。結果のビットも表示します"),
+    ("p", "・1.5, 6.02e23, 1.5E-3 のような小数と指数表記、3k, 2.2M, 470n のような SI 接頭辞 (p, n, u, µ, m, k, M, G, T) で入力できます。割り切れる値は分数のまま計算します。変数 k があっても 2k は接頭辞として 2000 と読みます"),
+    ("p", "・3億2000万 や 1.5万 のように万・億・兆から極までの単位で入力できます"),
+    ("p", "・1,000,000 や 1_000_000 のように桁を区切って入力できます (関数の引数の中では , は引数の区切りになるので _ を使ってください)。結果の桁は右上の 1,234,567 ボタンで区切り方を切り替えます (最初はブラウザの言語に合わせます)"),
+    ("p", "・右上の STD ボタンで結果の表記を STD / ENG (470e-6 のように指数を3の倍数にします) / SI (470µ のように接頭辞を付けます) / 万 (3億2000万) / 漢 (三億二千万) で切り替えます"),
+    ("p", "・角度の単位は右上のボタンで RAD / DEG / GRAD を切り替えます。30° のように ° を付けた数値は度として扱います"),
+    ("p", "・正確に表せない結果は近似値になり、→ の代わりに ≈ で表示します"),
+    ("h3", "入力例"),
+    ("p", "1 + 2 * 3 / 2 - 1"),
+    ("p", "2(1 + 2) * 3"),
+];
+
+pub(super) struct Layout<D: Dom> {
+    pub root: Option<D::Element>,
+    pub input: D::Element,
+    pub display: D::Element,
+    pub programmer_display: D::Element,
+    pub history_container: D::Element,
+    pub help_button: D::Element,
+    pub help_popup: D::Element,
+    pub close_help: D::Element,
+    pub mode_button: D::Element,
+    pub angle_button: D::Element,
+    pub width_button: D::Element,
+    pub notation_button: D::Element,
+    pub locale_button: D::Element,
+    pub calculator_view: D::Element,
+    pub document_view: D::Element,
+    pub document_area: D::Element,
+    pub document_results: D::Element,
+    pub export_buttons: Vec<(D::Element, ExportFormat)>,
+    pub import_file: D::Element,
+    pub paste_script: D::Element,
+}
+
+impl<D: Dom> Layout<D> {
+    pub fn from_ids(dom: &D) -> Result<Self> {
+        Self::append_help_text(dom, &dom.element_by_id(HELP_CONTENT)?)?;
+        Ok(Self {
+            root: None,
+            input: dom.element_by_id(INPUT_AREA)?,
+            display: dom.element_by_id(FORMATTED_DISPLAY)?,
+            programmer_display: dom.element_by_id(PROGRAMMER_DISPLAY)?,
+            history_container: dom.element_by_id(HISTORY_CONTAINER)?,
+            help_button: dom.element_by_id(HELP_BUTTON)?,
+            help_popup: dom.element_by_id(HELP_POPUP_CONTAINER)?,
+            close_help: dom.element_by_id(CLOSE_HELP)?,
+            mode_button: dom.element_by_id(MODE_BUTTON)?,
+            angle_button: dom.element_by_id(ANGLE_BUTTON)?,
+            width_button: dom.element_by_id(WIDTH_BUTTON)?,
+            notation_button: dom.element_by_id(NOTATION_BUTTON)?,
+            locale_button: dom.element_by_id(LOCALE_BUTTON)?,
+            calculator_view: dom.element_by_id(CALCULATOR_VIEW)?,
+            document_view: dom.element_by_id(DOCUMENT_VIEW)?,
+            document_area: dom.element_by_id(DOCUMENT_AREA)?,
+            document_results: dom.element_by_id(DOCUMENT_RESULTS)?,
+            export_buttons: vec![
+                (dom.element_by_id(EXPORT_JSON)?, ExportFormat::Json),
+                (dom.element_by_id(EXPORT_CSV)?, ExportFormat::Csv),
+                (dom.element_by_id(EXPORT_MARKDOWN)?, ExportFormat::Markdown),
+            ],
+            import_file: dom.element_by_id(IMPORT_FILE)?,
+            paste_script: dom.element_by_id(PASTE_SCRIPT)?,
+        })
+    }
+
+    // Generated elements are identified by class instead of ID so that
+    // several calculators can live on one page.
+    pub fn build(dom: &D, container: &D::Element) -> Result<Self> {
+        let root = Self::create_child(dom, container, "div", WIDGET_ROOT)?;
+
+        let history_menu = Self::create_child(dom, &root, "div", HISTORY_MENU)?;
+        let mut export_buttons = Vec::new();
+        for (class, label, format) in [
+            (EXPORT_JSON, "JSON", ExportFormat::Json),
+            (EXPORT_CSV, "CSV", ExportFormat::Csv),
+            (EXPORT_MARKDOWN, "Markdown", ExportFormat::Markdown),
+        ] {
+            let button = Self::create_child(dom, &history_menu, "button", class)?;
+            button.set_text_content(label);
+            export_buttons.push((button, format));
+        }
+        let import_label = Self::create_child(dom, &history_menu, "label", IMPORT_LABEL_CLASS)?;
+        import_label.set_text_content(IMPORT_LABEL);
+        let import_file = Self::create_child(
+            dom, &import_label, "input", &format!("{} {}", IMPORT_FILE, HIDDEN))?;
+        import_file.set_attribute("type", "file")?;
+        import_file.set_attribute("accept", ".json,application/json")?;
+        let paste_script = Self::create_child(dom, &history_menu, "button", PASTE_SCRIPT)?;
+        paste_script.set_text_content(PASTE_SCRIPT_LABEL);
+
+        let help_button = Self::create_child(dom, &root, "button", HELP_BUTTON)?;
+        let help_icon = Self::create_child(dom, &help_button, "span", "material-symbols-outlined")?;
+        help_icon.set_text_content("help");
+
+        let mode_button = Self::create_child(dom, &root, "button", MODE_BUTTON)?;
+        mode_button.set_text_content(DOCUMENT_MODE_LABEL);
+        let angle_button = Self::create_child(dom, &root, "button", ANGLE_BUTTON)?;
+        let width_button = Self::create_child(dom, &root, "button", WIDTH_BUTTON)?;
+        let notation_button = Self::create_child(dom, &root, "button", NOTATION_BUTTON)?;
+        let locale_button = Self::create_child(dom, &root, "button", LOCALE_BUTTON)?;
+
+        let help_popup = Self::create_child(
+            dom, &root, "div", &format!("{} {}", HELP_POPUP_CONTAINER, HIDDEN))?;
+        let help_window = Self::create_child(dom, &help_popup, "div", HELP_POPUP)?;
+        let help_content = Self::create_child(dom, &help_window, "div", HELP_CONTENT)?;
+        let close_help = Self::create_child(dom, &help_content, "button", CLOSE_HELP)?;
+        close_help.set_text_content("×");
+        Self::append_help_text(dom, &help_content)?;
+
+        let device = Self::create_child(
+            dom, &root, "div", &format!("{} {}", DEVICE, CALCULATOR_VIEW))?;
+        let history_container = Self::create_child(dom, &device, "div", HISTORY_CONTAINER)?;
+        let input = Self::create_child(dom, &device, "input", INPUT_AREA)?;
+        input.set_attribute("type", "text")?;
+        input.set_attribute("placeholder", INPUT_PLACEHOLDER)?;
+        let display = Self::create_child(dom, &device, "div", FORMATTED_DISPLAY)?;
+        let programmer_display = Self::create_child(dom, &device, "div", PROGRAMMER_DISPLAY)?;
+
+        let document_view = Self::create_child(
+            dom, &root, "div", &format!("{} {}", DOCUMENT_VIEW, HIDDEN))?;
+        let document_area = Self::create_child(dom, &document_view, "textarea", DOCUMENT_AREA)?;
+        document_area.set_attribute("placeholder", DOCUMENT_PLACEHOLDER)?;
+        document_area.set_attribute("spellcheck", "false")?;
+        let document_results = Self::create_child(dom, &document_view, "div", DOCUMENT_RESULTS)?;
+
+        Ok(Self {
+            root: Some(root),
+            input,
+            display,
+            programmer_display,
+            history_container,
+            help_button,
+            help_popup,
+            close_help,
+            mode_button,
+            angle_button,
+            width_button,
+            notation_button,
+            locale_button,
+            calculator_view: device,
+            document_view,
+            document_area,
+            document_results,
+            export_buttons,
+            import_file,
+            paste_script,
+        })
+    }
+
+    fn append_help_text(dom: &D, help_content: &D::Element) -> Result<()> {
+        for (tag, text) in HELP_TEXT {
+            let line = Self::create_child(dom, help_content, tag, "")?;
+            line.set_text_content(text);
+        }
+        Ok(())
+    }
+
+    fn create_child(dom: &D, parent: &D::Element, tag: &str, class: &str) -> Result<D::Element> {
+        let element = dom.create_element(tag)?;
+        element.set_class_name(class);
+        parent.append_child(&element.clone().into())?;
+        Ok(element)
+    }
+}
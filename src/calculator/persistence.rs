@@ -0,0 +1,111 @@
+use anyhow::Result;
+use crate::engine::{Dom, DomHtmlElement, DomStorage};
+use super_calculator_core::{Environment, History, HistoryEntry, Settings};
+use super::{Calculator, SETTINGS_STORAGE_SUFFIX, ENVIRONMENT_STORAGE_SUFFIX};
+
+impl<D: Dom> Calculator<D> {
+    // Settings and variables are stored next to the history, under its key
+    // with a suffix, so that mounted calculators keep theirs apart.
+    fn storage_key(&self, suffix: &str) -> Option<String> {
+        self.history_storage_key.as_ref().map(|key| format!("{}.{}", key, suffix))
+    }
+
+    // Values that can no longer be read, e.g. after an incompatible
+    // update, are dropped instead of failing on every start.
+    fn load_item<T>(&self, key: &str, from_json: fn(&str) -> Result<T, String>) -> Result<Option<T>> {
+        let storage = self.dom.local_storage()?;
+        match storage.get_item(key)? {
+            Some(value) => match from_json(&value) {
+                Ok(item) => Ok(Some(item)),
+                Err(e) => {
+                    error!("{}", e);
+                    storage.remove_item(key)?;
+                    Ok(None)
+                }
+            },
+            None => Ok(None),
+        }
+    }
+
+    fn load_history(&self) -> Result<History> {
+        let key = match &self.history_storage_key {
+            Some(key) => key,
+            None => return Ok(History::default()),
+        };
+        Ok(self.load_item(key, History::from_json)?.unwrap_or_default())
+    }
+
+    fn save_history(&self) -> Result<()> {
+        let key = match &self.history_storage_key {
+            Some(key) => key,
+            None => return Ok(()),
+        };
+        let value = self.history.to_json()
+            .map_err(anyhow::Error::msg)?;
+        self.dom.local_storage()?.set_item(key, &value)
+    }
+
+    pub(super) fn store_history_entries(&mut self, entries: &[HistoryEntry]) -> Result<()> {
+        self.history.extend(entries);
+        self.history.cap(self.settings.max_stored_history);
+        self.save_history()
+    }
+
+    pub(super) fn restore_history(&mut self) -> Result<()> {
+        self.history = self.load_history()?;
+        for entry in self.history.entries() {
+            self.add_history_entry(entry)?;
+        }
+        Ok(())
+    }
+
+    pub(super) fn save_settings(&self) -> Result<()> {
+        let key = match self.storage_key(SETTINGS_STORAGE_SUFFIX) {
+            Some(key) => key,
+            None => return Ok(()),
+        };
+        let value = self.settings.to_json()
+            .map_err(anyhow::Error::msg)?;
+        self.dom.local_storage()?.set_item(&key, &value)
+    }
+
+    pub(super) fn save_environment(&self) -> Result<()> {
+        let key = match self.storage_key(ENVIRONMENT_STORAGE_SUFFIX) {
+            Some(key) => key,
+            None => return Ok(()),
+        };
+        let value = self.environment.to_json()
+            .map_err(anyhow::Error::msg)?;
+        self.dom.local_storage()?.set_item(&key, &value)
+    }
+
+    // Stored settings win over the language of the browser, since the
+    // user chose them.
+    pub(super) fn restore_settings(&mut self) -> Result<()> {
+        if let Some(key) = self.storage_key(SETTINGS_STORAGE_SUFFIX) {
+            if let Some(settings) = self.load_item(&key, Settings::from_json)? {
+                self.settings = settings;
+            }
+        }
+        Ok(())
+    }
+
+    pub(super) fn restore_environment(&mut self) -> Result<()> {
+        if let Some(key) = self.storage_key(ENVIRONMENT_STORAGE_SUFFIX) {
+            if let Some(environment) = self.load_item(&key, Environment::from_json)? {
+                self.environment = environment;
+            }
+        }
+        Ok(())
+    }
+
+    pub(super) fn clear_history(&mut self) -> Result<()> {
+        self.history.clear();
+        self.remove_history_listeners()?;
+        self.history_container.set_inner_text("");
+        match &self.history_storage_key {
+            Some(key) => self.dom.local_storage()?.remove_item(key),
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use anyhow::Result;
+use crate::engine::{Dom, DomElement, DomHtmlElement};
+use super_calculator_core::{BitWidth, Radix};
+use super::{Calculator, PROGRAMMER_ROW, BIT_FIELD_ROW, EXACT_MODE_LABEL};
+
+impl<D: Dom> Calculator<D> {
+    pub(super) fn width_label(&self) -> String {
+        match self.settings.programmer {
+            true => self.settings.bit_width.to_string(),
+            false => EXACT_MODE_LABEL.to_string(),
+        }
+    }
+
+    // Cycles from the exact mode through the machine types and back. Like
+    // the angle unit, only later calculations are affected.
+    pub(super) fn handle_width_toggle(&mut self) -> Result<()> {
+        let next = match self.settings.programmer {
+            true => BitWidth::ALL
+                .iter()
+                .skip_while(|width| **width != self.settings.bit_width)
+                .nth(1),
+            false => BitWidth::ALL.first(),
+        };
+        self.settings.programmer = next.is_some();
+        self.settings.bit_width = next.copied().unwrap_or_default();
+        self.width_button.set_inner_text(&self.width_label());
+        self.save_settings()?;
+        self.update_programmer_display()?;
+        if self.document_mode {
+            self.handle_document_input()?;
+        }
+        Ok(())
+    }
+
+    // The last result in every base, or nothing when it is not an integer.
+    // Programmer mode adds the bits of the machine type, a byte per row.
+    pub(super) fn update_programmer_display(&self) -> Result<()> {
+        self.programmer_display.set_inner_text("");
+        let Some(num) = self.environment.ans_integer() else {
+            return Ok(());
+        };
+        let width = self.settings.bit_width;
+        for radix in Radix::ALL {
+            let row = self.dom.create_element("div")?;
+            row.set_class_name(PROGRAMMER_ROW);
+            row.set_text_content(&format!("{} {}", radix.label(), radix.format(num, width)));
+            self.programmer_display.append_child(&row.into())?;
+        }
+        if !self.settings.programmer {
+            return Ok(());
+        }
+        for (high, bits) in width.bit_field(num) {
+            let row = self.dom.create_element("div")?;
+            row.set_class_name(BIT_FIELD_ROW);
+            row.set_text_content(&format!("{}:{} {}", high, high - 7, bits));
+            self.programmer_display.append_child(&row.into())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::{FakeDom, FakeEvent};
+    use crate::calculator::{
+        fake_dom, Calculator, INPUT_AREA, PROGRAMMER_DISPLAY, PROGRAMMER_ROW, BIT_FIELD_ROW,
+        WIDTH_BUTTON,
+    };
+
+    fn setup() -> FakeDom {
+        let dom = fake_dom();
+        let calculator = Calculator::setup_dom(dom.clone()).unwrap();
+        dom.keep_alive(calculator);
+        dom
+    }
+
+    fn submit(dom: &FakeDom, value: &str) {
+        let input = dom.get(INPUT_AREA);
+        input.input(value);
+        input.dispatch("keydown", FakeEvent::keyboard("Enter", true));
+    }
+
+    fn rows(dom: &FakeDom, class: &str) -> Vec<String> {
+        dom.get(PROGRAMMER_DISPLAY)
+            .find_by_class(class)
+            .iter()
+            .map(|row| row.text_content())
+            .collect()
+    }
+
+    #[test]
+    fn programmer_display_shows_all_bases() {
+        let dom = setup();
+        assert!(rows(&dom, PROGRAMMER_ROW).is_empty());
+        submit(&dom, "0x1F + 1");
+        assert_eq!(
+            vec!["HEX 0x20", "DEC 32", "OCT 0o40", "BIN 0b100000"],
+            rows(&dom, PROGRAMMER_ROW),
+        );
+        assert!(rows(&dom, BIT_FIELD_ROW).is_empty());
+    }
+
+    #[test]
+    fn programmer_display_clears_for_fractions() {
+        let dom = setup();
+        submit(&dom, "255");
+        assert_eq!(4, rows(&dom, PROGRAMMER_ROW).len());
+        submit(&dom, "1 / 2");
+        assert!(rows(&dom, PROGRAMMER_ROW).is_empty());
+    }
+
+    #[test]
+    fn width_button_cycles_machine_types() {
+        let dom = setup();
+        let width_button = dom.get(WIDTH_BUTTON);
+        assert_eq!("EXACT", width_button.text_content());
+        let mut labels = Vec::new();
+        for _ in 0..9 {
+            width_button.click();
+            labels.push(width_button.text_content());
+        }
+        assert_eq!(
+            vec!["i8", "i16", "i32", "i64", "u8", "u16", "u32", "u64", "EXACT"],
+            labels,
+        );
+    }
+
+    #[test]
+    fn programmer_mode_wraps_and_shows_bits() {
+        let dom = setup();
+        dom.get(WIDTH_BUTTON).click();
+        submit(&dom, "100 + 100");
+        assert_eq!(
+            vec!["HEX 0xC8", "DEC -56", "OCT 0o310", "BIN 0b11001000"],
+            rows(&dom, PROGRAMMER_ROW),
+        );
+        assert_eq!(vec!["7:0 1100 1000"], rows(&dom, BIT_FIELD_ROW));
+        dom.get(WIDTH_BUTTON).click();
+        assert_eq!(vec!["15:8 1111 1111", "7:0 1100 1000"], rows(&dom, BIT_FIELD_ROW));
+    }
+}
@@ -1,102 +1,799 @@
+use std::rc::Rc;
+use std::cell::RefCell;
 use anyhow::Result;
-use crate::browser::{
-    create_event_closure,
-    forget_event_closure,
-};
 use crate::engine::{
-    HtmlElement, Event, KeyboardEvent, HtmlInputElement,
-    Element,
+    Dom, DomElement, DomHtmlElement, DomHtmlInputElement, DomEvent,
+    DomKeyboardEvent, DomEventListener, Element, WebDom,
+};
+use super_calculator_core::{
+    Calculation, Environment, ExportFormat, History, HistoryEntry, Locale, Script, Settings,
 };
+use super::layout::Layout;
 use super::{
-    Calculator, INPUT_AREA, FORMATTED_DISPLAY, HISTORY_CONTAINER,
-    HELP_POPUP_CONTAINER, HIDDEN, HELP_BUTTON, CLOSE_HELP,
+    Calculator, INPUT_AREA, HIDDEN, CLEAR_COMMAND, IMPORT_LABEL,
+    HISTORY_STORAGE_KEY, LOCALE_SAMPLE,
 };
 
 impl Calculator {
     pub fn setup() -> Result<()> {
-        let input = Element::new_from_id(INPUT_AREA)?;
-        let closure = create_event_closure(move |e: web_sys::Event| {
-            match Self::handle_input(e) {
-                Ok(_) => {}
-                Err(e) => error!("{}", e),
-            }
-        });
-        input.add_event_listener_with_callback("input", &closure)?;
-        forget_event_closure(closure);
+        let dom = WebDom::new();
+        // Pages that only embed widgets do not have the standalone markup.
+        if dom.element_by_id(INPUT_AREA).is_err() {
+            return Ok(());
+        }
+        let calculator = Self::setup_dom(dom)?;
+        // The standalone calculator lives as long as the page.
+        std::mem::forget(calculator);
+        Ok(())
+    }
 
-        let closure = create_event_closure(move |e: web_sys::Event| {
-            match Self::handle_keydown(e) {
-                Ok(_) => {}
-                Err(e) => error!("{}", e),
-            }
-        });
-        input.add_event_listener_with_callback("keydown", &closure)?;
-        forget_event_closure(closure);
+    pub(crate) fn mount(container: Element, storage_key: Option<String>) -> Result<Rc<RefCell<Self>>> {
+        Self::mount_dom(WebDom::new(), &container, storage_key)
+    }
+}
+
+impl<D: Dom> Calculator<D> {
+    fn new(dom: D, layout: &Layout<D>, history_storage_key: Option<String>) -> Result<Self> {
+        Ok(Self {
+            input: dom.to_input_element(&layout.input)?,
+            display: dom.to_html_element(&layout.display)?,
+            programmer_display: dom.to_html_element(&layout.programmer_display)?,
+            history_container: dom.to_html_element(&layout.history_container)?,
+            help_popup: dom.to_html_element(&layout.help_popup)?,
+            mode_button: dom.to_html_element(&layout.mode_button)?,
+            angle_button: dom.to_html_element(&layout.angle_button)?,
+            width_button: dom.to_html_element(&layout.width_button)?,
+            notation_button: dom.to_html_element(&layout.notation_button)?,
+            locale_button: dom.to_html_element(&layout.locale_button)?,
+            calculator_view: dom.to_html_element(&layout.calculator_view)?,
+            document_view: dom.to_html_element(&layout.document_view)?,
+            document_area: dom.to_text_area_element(&layout.document_area)?,
+            document_results: dom.to_html_element(&layout.document_results)?,
+            document_mode: false,
+            root: layout.root.clone(),
+            listeners: RefCell::new(Vec::new()),
+            history_listeners: RefCell::new(Vec::new()),
+            // Without a known language, results are grouped like 1,234,567.
+            settings: Settings {
+                locale: dom.language().map(|language| Locale::parse(&language).unwrap_or(Locale::English)),
+                ..Settings::default()
+            },
+            history_storage_key,
+            history: History::default(),
+            environment: Environment::default(),
+            this: std::rc::Weak::new(),
+            dom,
+        })
+    }
+
+    pub(super) fn setup_dom(dom: D) -> Result<Rc<RefCell<Self>>> {
+        let layout = Layout::from_ids(&dom)?;
+        Self::start(dom, layout, Some(HISTORY_STORAGE_KEY.to_string()))
+    }
+
+    pub(super) fn mount_dom(
+        dom: D,
+        container: &D::Element,
+        history_storage_key: Option<String>,
+    ) -> Result<Rc<RefCell<Self>>> {
+        let layout = Layout::build(&dom, container)?;
+        Self::start(dom, layout, history_storage_key)
+    }
 
-        let help_button = Element::new_from_id(HELP_BUTTON)?;
-        let closure = create_event_closure(move |_: web_sys::Event| {
-            match Self::handle_help_popup() {
+    fn start(dom: D, layout: Layout<D>, history_storage_key: Option<String>) -> Result<Rc<RefCell<Self>>> {
+        let calculator = Rc::new(RefCell::new(Self::new(dom, &layout, history_storage_key)?));
+        let mut inner = calculator.borrow_mut();
+        inner.this = Rc::downgrade(&calculator);
+        for restored in [inner.restore_settings(), inner.restore_environment()] {
+            match restored {
                 Ok(_) => {}
                 Err(e) => error!("{}", e),
             }
-        });
+        }
+        inner.angle_button.set_inner_text(&inner.settings.angle_unit.to_string());
+        inner.width_button.set_inner_text(&inner.width_label());
+        inner.notation_button.set_inner_text(&inner.settings.notation.to_string());
+        inner.locale_button.set_inner_text(&inner.locale_label());
+        match inner.restore_history() {
+            Ok(_) => {}
+            Err(e) => error!("{}", e),
+        }
+        inner.update_programmer_display()?;
+        inner.add_listeners(&layout)?;
+        drop(inner);
+        Ok(calculator)
+    }
+
+    pub(crate) fn destroy(&mut self) -> Result<()> {
+        for listener in self.listeners.take() {
+            listener.remove()?;
+        }
+        self.remove_history_listeners()?;
+        if let Some(root) = self.root.take() {
+            root.remove();
+        }
+        Ok(())
+    }
+
+    fn add_listeners(&self, layout: &Layout<D>) -> Result<()> {
+        self.add_listener(&layout.input, "input", Self::handle_input)?;
+        self.add_listener(&layout.input, "keydown", Self::handle_keydown)?;
+
+        self.add_listener(&layout.help_button, "click", |calculator, _| {
+            calculator.handle_help_popup()
+        })?;
+
+        self.add_listener(&layout.close_help, "click", |calculator, _| {
+            calculator.handle_popup_close()
+        })?;
+
+        for (export_button, format) in &layout.export_buttons {
+            let format = *format;
+            self.add_listener(export_button, "click", move |calculator, _| {
+                calculator.handle_export(format)
+            })?;
+        }
+
+        self.add_listener(&layout.import_file, "change", Self::handle_import)?;
+
+        self.add_listener(&layout.paste_script, "click", |calculator, _| {
+            calculator.handle_paste_script()
+        })?;
+
+        self.add_listener(&layout.mode_button, "click", |calculator, _| {
+            calculator.handle_mode_toggle()
+        })?;
+        self.add_listener(&layout.angle_button, "click", |calculator, _| {
+            calculator.handle_angle_toggle()
+        })?;
+        self.add_listener(&layout.width_button, "click", |calculator, _| {
+            calculator.handle_width_toggle()
+        })?;
+        self.add_listener(&layout.notation_button, "click", |calculator, _| {
+            calculator.handle_notation_toggle()
+        })?;
+        self.add_listener(&layout.locale_button, "click", |calculator, _| {
+            calculator.handle_locale_toggle()
+        })?;
+        self.add_listener(&layout.document_area, "input", |calculator, _| {
+            calculator.handle_document_input()
+        })?;
+        Ok(())
+    }
+
+    pub(super) fn add_listener<F>(&self, element: &D::Element, event: &str, handler: F) -> Result<()>
+    where
+        F: Fn(&mut Self, D::Event) -> Result<()> + 'static,
+    {
+        let listener = self.create_listener(element, event, handler)?;
+        self.listeners.borrow_mut().push(listener);
+        Ok(())
+    }
 
-        help_button.add_event_listener_with_callback("click", &closure)?;
-        forget_event_closure(closure);
+    // Listeners of history entries are kept apart so that clearing the
+    // history can remove them without touching the fixed controls.
+    pub(super) fn add_history_listener<F>(&self, element: &D::Element, event: &str, handler: F) -> Result<()>
+    where
+        F: Fn(&mut Self, D::Event) -> Result<()> + 'static,
+    {
+        let listener = self.create_listener(element, event, handler)?;
+        self.history_listeners.borrow_mut().push(listener);
+        Ok(())
+    }
 
-        let close_help = Element::new_from_id(CLOSE_HELP)?;
-        let closure = create_event_closure(move |_: web_sys::Event| {
-            match Self::handle_popup_close() {
+    fn create_listener<F>(&self, element: &D::Element, event: &str, handler: F) -> Result<D::EventListener>
+    where
+        F: Fn(&mut Self, D::Event) -> Result<()> + 'static,
+    {
+        let this = self.this.clone();
+        let listener = element.add_event_listener(event, move |e: D::Event| {
+            let calculator = match this.upgrade() {
+                Some(calculator) => calculator,
+                None => return,
+            };
+            let result = handler(&mut calculator.borrow_mut(), e);
+            match result {
                 Ok(_) => {}
                 Err(e) => error!("{}", e),
             }
-        });
-        close_help.add_event_listener_with_callback("click", &closure)?;
-        forget_event_closure(closure);
+        })?;
+        Ok(listener)
+    }
+
+    fn handle_input(&mut self, _: D::Event) -> Result<()> {
+        let value = self.input.get_value();
+        self.update_formatted_display(&value);
         Ok(())
     }
 
-    fn handle_input(event: web_sys::Event) -> Result<()> {
-        let event = Event::new(event);
-        let value = event
-            .get_target_input_element()?
-            .get_value();
-        let formatted_value = Self::format_input(&value);
-        let display = HtmlElement::new_from_id(FORMATTED_DISPLAY)?;
-        display.set_inner_text(&formatted_value);
+    fn update_formatted_display(&self, value: &str) {
+        let formatted_value = Calculator::format_input(value);
+        self.display.set_inner_text(&formatted_value);
+    }
+
+    pub(super) fn insert_into_input(&self, text: &str) -> Result<()> {
+        self.input.insert_at_caret(text)?;
+        self.input.focus()?;
+        self.update_formatted_display(&self.input.get_value());
         Ok(())
     }
 
-    fn handle_keydown(event: web_sys::Event) -> Result<()> {
-        let event = Event::new(event);
-        let event: KeyboardEvent = match event.try_into() {
+    fn handle_keydown(&mut self, event: D::Event) -> Result<()> {
+        let event = match event.to_keyboard_event() {
             Ok(event) => event,
             Err(_) => {
                 return Ok(());
             }
         };
         if event.ctrl_enter() {
-            let input = HtmlInputElement::new_from_id(INPUT_AREA)?;
-            let display = HtmlElement::new_from_id(FORMATTED_DISPLAY)?;
-            let history_container = HtmlElement::new_from_id(HISTORY_CONTAINER)?;
-            let value = display.get_inner_text();
-            let result = Self::calculate_and_format(&value);
-            Self::add_history_entry(&value, &result, &history_container)?;
-            display.set_inner_text("");
-            input.set_value("");
+            let value = self.display.get_inner_text();
+            if value.trim() == CLEAR_COMMAND {
+                self.clear_history()?;
+            } else {
+                let results = Calculation::calculate_statements(
+                    &value, &mut self.environment, &self.settings);
+                let entries: Vec<HistoryEntry> = results
+                    .iter()
+                    .map(|(statement, result)| HistoryEntry::new(statement, result))
+                    .collect();
+                for entry in &entries {
+                    self.add_history_entry(entry)?;
+                }
+                for stored in [self.store_history_entries(&entries), self.save_environment()] {
+                    match stored {
+                        Ok(_) => {}
+                        Err(e) => error!("{}", e),
+                    }
+                }
+                self.update_programmer_display()?;
+            }
+            self.display.set_inner_text("");
+            self.input.set_value("");
+        }
+        Ok(())
+    }
+
+    // Only later calculations use the new unit; the history keeps its results.
+    fn handle_angle_toggle(&mut self) -> Result<()> {
+        self.settings.angle_unit = self.settings.angle_unit.next();
+        self.angle_button.set_inner_text(&self.settings.angle_unit.to_string());
+        self.save_settings()?;
+        if self.document_mode {
+            self.handle_document_input()?;
+        }
+        Ok(())
+    }
+
+    fn handle_notation_toggle(&mut self) -> Result<()> {
+        self.settings.notation = self.settings.notation.next();
+        self.notation_button.set_inner_text(&self.settings.notation.to_string());
+        self.save_settings()?;
+        if self.document_mode {
+            self.handle_document_input()?;
+        }
+        Ok(())
+    }
+
+    // A large number grouped the current way, so the label shows what
+    // the button does.
+    fn locale_label(&self) -> String {
+        match self.settings.locale {
+            Some(locale) => locale.group(LOCALE_SAMPLE),
+            None => LOCALE_SAMPLE.to_string(),
+        }
+    }
+
+    // Cycles through the conventions and no grouping at all. Results
+    // already in the history keep their grouping.
+    fn handle_locale_toggle(&mut self) -> Result<()> {
+        let next = match self.settings.locale {
+            Some(locale) => Locale::ALL
+                .iter()
+                .skip_while(|other| **other != locale)
+                .nth(1),
+            None => Locale::ALL.first(),
+        };
+        self.settings.locale = next.copied();
+        self.locale_button.set_inner_text(&self.locale_label());
+        self.save_settings()?;
+        if self.document_mode {
+            self.handle_document_input()?;
         }
         Ok(())
     }
 
-    fn handle_help_popup() -> Result<()> {
-        let help_popup = HtmlElement::new_from_id(HELP_POPUP_CONTAINER)?;
-        help_popup.remove_class(HIDDEN)?;
+    fn handle_help_popup(&mut self) -> Result<()> {
+        self.help_popup.remove_class(HIDDEN)?;
         Ok(())
     }
 
-    fn handle_popup_close() -> Result<()> {
-        let help_popup = HtmlElement::new_from_id(HELP_POPUP_CONTAINER)?;
-        help_popup.set_class(HIDDEN);
+    fn handle_popup_close(&mut self) -> Result<()> {
+        self.help_popup.add_class(HIDDEN)
+    }
+
+    fn handle_export(&mut self, format: ExportFormat) -> Result<()> {
+        let content = self.history.export(format)
+            .map_err(anyhow::Error::msg)?;
+        self.dom.download_text(format.file_name(), format.mime_type(), &content)
+    }
+
+    fn handle_import(&mut self, event: D::Event) -> Result<()> {
+        let input = event.get_target_input_element()?;
+        let this = self.this.clone();
+        self.dom.read_file_text(&input, move |value| {
+            let calculator = match this.upgrade() {
+                Some(calculator) => calculator,
+                None => return,
+            };
+            let result = calculator.borrow_mut().import_history(value);
+            match result {
+                Ok(_) => {}
+                Err(e) => error!("{}", e),
+            }
+        })?;
+        input.set_value("");
         Ok(())
     }
+
+    fn handle_paste_script(&mut self) -> Result<()> {
+        let this = self.this.clone();
+        self.dom.read_clipboard_text(move |text| {
+            let calculator = match this.upgrade() {
+                Some(calculator) => calculator,
+                None => return,
+            };
+            let result = calculator.borrow_mut().run_script(text);
+            match result {
+                Ok(_) => {}
+                Err(e) => error!("{}", e),
+            }
+        });
+        Ok(())
+    }
+
+    // Statements share the calculator's variables, so values defined by the
+    // script remain usable afterwards.
+    fn run_script(&mut self, text: Result<String>) -> Result<()> {
+        let script = Script::run(&text?, &mut self.environment, &self.settings);
+        let entries: Vec<HistoryEntry> = script.statements()
+            .map(|statement| {
+                let result = statement.result.clone().map_err(|e| e.to_string());
+                HistoryEntry::new(&statement.input, &result)
+            })
+            .collect();
+        for entry in &entries {
+            self.add_history_entry(entry)?;
+        }
+        self.update_programmer_display()?;
+        self.save_environment()?;
+        self.store_history_entries(&entries)
+    }
+
+    fn import_history(&mut self, value: Result<String>) -> Result<()> {
+        let value = value?;
+        match History::import_json(&value) {
+            Ok(entries) => {
+                for entry in &entries {
+                    self.add_history_entry(entry)?;
+                }
+                self.store_history_entries(&entries)
+            }
+            Err(error) => {
+                let entry = HistoryEntry::new(IMPORT_LABEL, &Err(error));
+                self.add_history_entry(&entry)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{FakeDom, FakeElement, FakeEvent, DomStorage};
+    use crate::calculator::layout::HELP_TEXT;
+    use crate::calculator::{
+        fake_dom, FORMATTED_DISPLAY, HISTORY_CONTAINER, HELP_POPUP_CONTAINER,
+        HELP_BUTTON, HELP_CONTENT, CLOSE_HELP, HISTORY_ITEM, HISTORY_STORAGE_KEY, EXPORT_CSV,
+        EXPORT_JSON, IMPORT_FILE, PASTE_SCRIPT, ANGLE_BUTTON, NOTATION_BUTTON, WIDTH_BUTTON,
+        LOCALE_BUTTON,
+    };
+
+    fn setup() -> FakeDom {
+        let dom = fake_dom();
+        let calculator = Calculator::setup_dom(dom.clone()).unwrap();
+        dom.keep_alive(calculator);
+        dom
+    }
+
+    fn submit(dom: &FakeDom, value: &str) {
+        let input = dom.get(INPUT_AREA);
+        input.input(value);
+        input.dispatch("keydown", FakeEvent::keyboard("Enter", true));
+    }
+
+    fn history_texts(dom: &FakeDom) -> Vec<String> {
+        dom.get(HISTORY_CONTAINER)
+            .find_by_class(HISTORY_ITEM)
+            .iter()
+            .map(|entry| entry.text_content())
+            .collect()
+    }
+
+    fn mount(dom: &FakeDom, id: &str) -> (FakeElement, Rc<RefCell<Calculator<FakeDom>>>) {
+        let container = dom.add_element("div", id);
+        let calculator = Calculator::mount_dom(dom.clone(), &container, None).unwrap();
+        (container, calculator)
+    }
+
+    fn find(container: &FakeElement, class: &str) -> FakeElement {
+        container.find_by_class(class)[0].clone()
+    }
+
+    fn submit_to(container: &FakeElement, value: &str) {
+        let input = find(container, INPUT_AREA);
+        input.input(value);
+        input.dispatch("keydown", FakeEvent::keyboard("Enter", true));
+    }
+
+    #[test]
+    fn input_updates_formatted_display() {
+        let dom = setup();
+        dom.get(INPUT_AREA).input("1 + 2");
+        assert_eq!("1 + 2", dom.get(FORMATTED_DISPLAY).text_content());
+    }
+
+    #[test]
+    fn ctrl_enter_adds_history_entry() {
+        let dom = setup();
+        submit(&dom, "1 + 2");
+        assert_eq!(vec!["1 + 2 → 3content_copy"], history_texts(&dom));
+        assert_eq!("", dom.get(INPUT_AREA).value());
+        assert_eq!("", dom.get(FORMATTED_DISPLAY).text_content());
+    }
+
+    #[test]
+    fn ans_refers_to_previous_result() {
+        let dom = setup();
+        submit(&dom, "1 + 2");
+        submit(&dom, "2ans");
+        assert_eq!(
+            vec!["1 + 2 → 3content_copy", "2 * ans → 6content_copy"],
+            history_texts(&dom),
+        );
+    }
+
+    #[test]
+    fn semicolons_add_one_entry_per_statement() {
+        let dom = setup();
+        submit(&dom, "a = 3; b = 4; a*a + b*b");
+        assert_eq!(
+            vec![
+                "a = 3 → 3content_copy",
+                "b = 4 → 4content_copy",
+                "a * a + b * b → 25content_copy",
+            ],
+            history_texts(&dom),
+        );
+    }
+
+    #[test]
+    fn semicolon_error_names_failing_statement() {
+        let dom = setup();
+        submit(&dom, "a = 3; a / 0; a + 1");
+        let texts = history_texts(&dom);
+        assert_eq!(2, texts.len());
+        assert_eq!("a / 0 2番目の文: 0で割ることはできません", texts[1]);
+    }
+
+    #[test]
+    fn enter_without_ctrl_does_nothing() {
+        let dom = setup();
+        let input = dom.get(INPUT_AREA);
+        input.input("1 + 2");
+        input.dispatch("keydown", FakeEvent::keyboard("Enter", false));
+        assert!(history_texts(&dom).is_empty());
+        assert_eq!("1 + 2", input.value());
+    }
+
+    #[test]
+    fn ctrl_enter_adds_error_entry() {
+        let dom = setup();
+        submit(&dom, "1 / 0");
+        let entries = dom.get(HISTORY_CONTAINER).find_by_class(HISTORY_ITEM);
+        assert_eq!(1, entries.len());
+        let error = entries[0].find_by_class("error-text");
+        assert_eq!(" 0で割ることはできません", error[0].text_content());
+    }
+
+    #[test]
+    fn history_is_stored_and_restored() {
+        let dom = setup();
+        submit(&dom, "1 + 2");
+        submit(&dom, "1 2");
+        assert!(dom.storage().get_item(HISTORY_STORAGE_KEY).unwrap().is_some());
+
+        let restored = fake_dom();
+        restored.storage().set_item(
+            HISTORY_STORAGE_KEY,
+            &dom.storage().get_item(HISTORY_STORAGE_KEY).unwrap().unwrap(),
+        ).unwrap();
+        restored.keep_alive(Calculator::setup_dom(restored.clone()).unwrap());
+        assert_eq!(history_texts(&dom), history_texts(&restored));
+    }
+
+    #[test]
+    fn clear_command_clears_history() {
+        let dom = setup();
+        submit(&dom, "1 + 2");
+        submit(&dom, "clear");
+        assert!(history_texts(&dom).is_empty());
+        assert_eq!(None, dom.storage().get_item(HISTORY_STORAGE_KEY).unwrap());
+    }
+
+    #[test]
+    fn clear_command_removes_entry_listeners() {
+        let dom = setup();
+        submit(&dom, "1 + 2");
+        submit(&dom, "1 / 0");
+        let entries = dom.get(HISTORY_CONTAINER).find_by_class(HISTORY_ITEM);
+        assert_eq!(3, entries.iter().map(FakeElement::listener_count).sum::<usize>());
+        submit(&dom, "clear");
+        assert_eq!(0, entries.iter().map(FakeElement::listener_count).sum::<usize>());
+        assert!(dom.get(INPUT_AREA).listener_count() > 0);
+    }
+
+    #[test]
+    fn angle_button_switches_unit() {
+        let dom = setup();
+        let angle_button = dom.get(ANGLE_BUTTON);
+        assert_eq!("RAD", angle_button.text_content());
+        submit(&dom, "sin(π/6)");
+        angle_button.click();
+        assert_eq!("DEG", angle_button.text_content());
+        submit(&dom, "sin(30)");
+        assert_eq!(
+            vec!["sin ( π / 6 ) → 1 / 2content_copy", "sin ( 30 ) → 1 / 2content_copy"],
+            history_texts(&dom),
+        );
+    }
+
+    #[test]
+    fn notation_button_switches_notation() {
+        let dom = setup();
+        let notation_button = dom.get(NOTATION_BUTTON);
+        assert_eq!("STD", notation_button.text_content());
+        submit(&dom, "4.7k");
+        notation_button.click();
+        notation_button.click();
+        assert_eq!("SI", notation_button.text_content());
+        submit(&dom, "4.7k");
+        notation_button.click();
+        assert_eq!("万", notation_button.text_content());
+        submit(&dom, "4.7万");
+        notation_button.click();
+        notation_button.click();
+        assert_eq!("STD", notation_button.text_content());
+        assert_eq!(
+            vec![
+                "4.7k → 4700content_copy",
+                "4.7k → 4.7kcontent_copy",
+                "4.7万 → 4万7000content_copy",
+            ],
+            history_texts(&dom),
+        );
+    }
+
+    #[test]
+    fn locale_button_switches_grouping() {
+        let dom = setup();
+        let locale_button = dom.get(LOCALE_BUTTON);
+        assert_eq!("1234567", locale_button.text_content());
+        submit(&dom, "1234 * 1000");
+        locale_button.click();
+        assert_eq!("1,234,567", locale_button.text_content());
+        submit(&dom, "1234 * 1000");
+        locale_button.click();
+        assert_eq!("1.234.567", locale_button.text_content());
+        submit(&dom, "1234 * 1000");
+        for _ in 0..3 {
+            locale_button.click();
+        }
+        assert_eq!("1234567", locale_button.text_content());
+        assert_eq!(
+            vec![
+                "1234 * 1000 → 1234000content_copy",
+                "1234 * 1000 → 1,234,000content_copy",
+                "1234 * 1000 → 1.234.000content_copy",
+            ],
+            history_texts(&dom),
+        );
+    }
+
+    // A page reload: the same storage with a new calculator.
+    fn reload(dom: &FakeDom, keys: &[&str]) -> FakeDom {
+        let restored = fake_dom();
+        for key in keys {
+            if let Some(value) = dom.storage().get_item(key).unwrap() {
+                restored.storage().set_item(key, &value).unwrap();
+            }
+        }
+        restored.keep_alive(Calculator::setup_dom(restored.clone()).unwrap());
+        restored
+    }
+
+    #[test]
+    fn settings_are_stored_and_restored() {
+        let dom = setup();
+        dom.get(ANGLE_BUTTON).click();
+        dom.get(WIDTH_BUTTON).click();
+        dom.get(NOTATION_BUTTON).click();
+        dom.get(LOCALE_BUTTON).click();
+
+        let restored = reload(&dom, &["super_calculator.history.settings"]);
+        assert_eq!("DEG", restored.get(ANGLE_BUTTON).text_content());
+        assert_eq!("i8", restored.get(WIDTH_BUTTON).text_content());
+        assert_eq!("ENG", restored.get(NOTATION_BUTTON).text_content());
+        assert_eq!("1,234,567", restored.get(LOCALE_BUTTON).text_content());
+        submit(&restored, "7 * 40");
+        assert_eq!(vec!["7 * 40 → 24content_copy"], history_texts(&restored));
+    }
+
+    #[test]
+    fn stored_locale_wins_over_language() {
+        let dom = fake_dom();
+        dom.storage().set_item("super_calculator.history.settings", r#"{"locale":"German"}"#).unwrap();
+        dom.set_language("en-US");
+        dom.keep_alive(Calculator::setup_dom(dom.clone()).unwrap());
+        submit(&dom, "1234 * 1000");
+        assert_eq!(vec!["1234 * 1000 → 1.234.000content_copy"], history_texts(&dom));
+    }
+
+    #[test]
+    fn variables_are_stored_and_restored() {
+        let dom = setup();
+        submit(&dom, "rate = 1 / 3; 0xFF");
+
+        let restored = reload(&dom, &["super_calculator.history.variables"]);
+        submit(&restored, "rate * 3 + ans");
+        assert_eq!(vec!["rate * 3 + ans → 256content_copy"], history_texts(&restored));
+    }
+
+    #[test]
+    fn help_popup_open_and_close() {
+        let dom = setup();
+        let help_popup = dom.get(HELP_POPUP_CONTAINER);
+        dom.get(HELP_BUTTON).click();
+        assert!(!help_popup.has_class(HIDDEN));
+        dom.get(CLOSE_HELP).click();
+        assert!(help_popup.has_class(HIDDEN));
+    }
+
+    fn help_lines(help_content: &FakeElement) -> Vec<(String, String)> {
+        help_content.children()
+            .iter()
+            .filter(|line| line.tag() != "button")
+            .map(|line| (line.tag(), line.text_content()))
+            .collect()
+    }
+
+    #[test]
+    fn help_text_is_rendered_from_one_source() {
+        let expected: Vec<_> = HELP_TEXT.iter()
+            .map(|(tag, text)| (tag.to_string(), text.to_string()))
+            .collect();
+        let dom = setup();
+        assert_eq!(expected, help_lines(&dom.get(HELP_CONTENT)));
+        let (container, _calculator) = mount(&FakeDom::new(), "dashboard");
+        assert_eq!(expected, help_lines(&find(&container, HELP_CONTENT)));
+
+        let page = include_str!("../../static/index.html");
+        let start = page.find(&format!("<div id=\"{}\">", HELP_CONTENT)).unwrap();
+        let end = start + page[start..].find("</div>").unwrap();
+        let help_content: Vec<_> = page[start..end].lines()
+            .skip(1)
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect();
+        assert_eq!(vec![format!("<button id=\"{}\">×</button>", CLOSE_HELP)], help_content);
+    }
+
+    #[test]
+    fn export_downloads_history() {
+        let dom = setup();
+        submit(&dom, "1 + 2");
+        dom.get(EXPORT_CSV).click();
+        let downloads = dom.downloads();
+        assert_eq!(1, downloads.len());
+        assert_eq!("history.csv", downloads[0].file_name);
+        assert_eq!(
+            "input,expression,result,decimal,approximate,error\r\n1 + 2,1 + 2,3,3,false,\r\n",
+            downloads[0].content,
+        );
+    }
+
+    #[test]
+    fn import_adds_history() {
+        let dom = setup();
+        submit(&dom, "1 + 2");
+        dom.get(EXPORT_JSON).click();
+        let content = dom.downloads()[0].content.clone();
+
+        let other = setup();
+        let import_file = other.get(IMPORT_FILE);
+        import_file.set_file(&content);
+        import_file.dispatch("change", FakeEvent::new());
+        other.run_pending();
+        assert_eq!(history_texts(&dom), history_texts(&other));
+        assert_eq!(
+            dom.storage().get_item(HISTORY_STORAGE_KEY).unwrap(),
+            other.storage().get_item(HISTORY_STORAGE_KEY).unwrap(),
+        );
+    }
+
+    #[test]
+    fn paste_script_adds_history_per_statement() {
+        let dom = setup();
+        dom.write_clipboard_text("# 予算\nrent = 1200\n\nrent / 0\nrent * 2  # 2か月\n");
+        dom.get(PASTE_SCRIPT).click();
+        dom.run_pending();
+        let texts = history_texts(&dom);
+        assert_eq!(3, texts.len());
+        assert_eq!("rent = 1200 → 1200content_copy", texts[0]);
+        assert_eq!("rent / 0 4:6: 0で割ることはできません", texts[1]);
+        assert_eq!("rent * 2 → 2400content_copy", texts[2]);
+
+        submit(&dom, "rent + 1");
+        assert_eq!("rent + 1 → 1201content_copy", history_texts(&dom)[3]);
+    }
+
+    #[test]
+    fn mount_generates_calculator_in_container() {
+        let dom = FakeDom::new();
+        let (container, _calculator) = mount(&dom, "dashboard");
+        assert_eq!(1, container.children().len());
+        let input = find(&container, INPUT_AREA);
+        assert_eq!("input", input.tag());
+        input.input("1 + 2");
+        assert_eq!("1 + 2", find(&container, FORMATTED_DISPLAY).text_content());
+
+        let help_popup = find(&container, HELP_POPUP_CONTAINER);
+        assert!(help_popup.has_class(HIDDEN));
+        find(&container, HELP_BUTTON).click();
+        assert!(!help_popup.has_class(HIDDEN));
+        find(&container, CLOSE_HELP).click();
+        assert!(help_popup.has_class(HIDDEN));
+        assert!(help_popup.has_class(HELP_POPUP_CONTAINER));
+    }
+
+    #[test]
+    fn mounted_calculators_are_independent() {
+        let dom = FakeDom::new();
+        let (first, _first_calculator) = mount(&dom, "first");
+        let (second, _second_calculator) = mount(&dom, "second");
+        submit_to(&first, "1 + 2");
+        submit_to(&second, "ans");
+
+        let first_history = find(&first, HISTORY_CONTAINER).find_by_class(HISTORY_ITEM);
+        assert_eq!("1 + 2 → 3content_copy", first_history[0].text_content());
+        let second_history = find(&second, HISTORY_CONTAINER).find_by_class(HISTORY_ITEM);
+        let error = second_history[0].find_by_class("error-text");
+        assert_eq!(" ansはまだ計算結果を持っていません", error[0].text_content());
+        assert_eq!(None, dom.storage().get_item(HISTORY_STORAGE_KEY).unwrap());
+    }
+
+    #[test]
+    fn destroy_removes_listeners_and_elements() {
+        let dom = FakeDom::new();
+        let (container, calculator) = mount(&dom, "dashboard");
+        submit_to(&container, "1 + 2");
+        let root = container.children()[0].clone();
+        assert!(root.listener_count() > 0);
+
+        calculator.borrow_mut().destroy().unwrap();
+        assert!(container.children().is_empty());
+        assert_eq!(0, root.listener_count());
+        let input = find(&root, INPUT_AREA);
+        input.input("1 + 2");
+        assert_eq!("", find(&root, FORMATTED_DISPLAY).text_content());
+    }
 }
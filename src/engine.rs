@@ -1,13 +1,36 @@
 mod element;
 mod html_element;
 mod html_input_element;
+mod html_text_area_element;
 mod event;
 mod keyboard_event;
 mod node;
+mod mouse_event;
+mod clipboard;
+mod storage;
+mod blob;
+mod event_listener;
+mod dom;
+mod web_dom;
+#[cfg(test)]
+mod fake_dom;
 
 pub use element::Element;
 pub use html_input_element::HtmlInputElement;
+pub use html_text_area_element::HtmlTextAreaElement;
 pub use html_element::HtmlElement;
 pub use event::Event;
 pub use keyboard_event::KeyboardEvent;
 pub use node::Node;
+pub use mouse_event::MouseEvent;
+pub use clipboard::Clipboard;
+pub use storage::Storage;
+pub use blob::Blob;
+pub use event_listener::EventListener;
+pub use dom::{
+    Dom, DomNode, DomElement, DomHtmlElement, DomHtmlInputElement,
+    DomHtmlTextAreaElement, DomEvent, DomKeyboardEvent, DomMouseEvent, DomStorage, DomEventListener,
+};
+pub use web_dom::WebDom;
+#[cfg(test)]
+pub use fake_dom::{FakeDom, FakeElement, FakeEvent};
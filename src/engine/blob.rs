@@ -0,0 +1,27 @@
+use anyhow::Result;
+use crate::browser::{
+    create_blob_from_text, download_blob, blob_text,
+};
+
+#[derive(Debug, Clone)]
+pub struct Blob {
+    inner: web_sys::Blob,
+}
+
+impl Blob {
+    pub fn new(inner: web_sys::Blob) -> Self {
+        Self { inner }
+    }
+
+    pub fn new_from_text(text: &str, mime_type: &str) -> Result<Self> {
+        Ok(Self::new(create_blob_from_text(text, mime_type)?))
+    }
+
+    pub fn download(&self, file_name: &str) -> Result<()> {
+        download_blob(&self.inner, file_name)
+    }
+
+    pub async fn text(&self) -> Result<String> {
+        blob_text(&self.inner).await
+    }
+}
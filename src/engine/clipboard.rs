@@ -0,0 +1,15 @@
+use anyhow::Result;
+use crate::browser::{write_text_to_clipboard, read_text_from_clipboard};
+
+#[derive(Debug, Clone)]
+pub struct Clipboard {}
+
+impl Clipboard {
+    pub async fn write_text(text: &str) -> Result<()> {
+        write_text_to_clipboard(text).await
+    }
+
+    pub async fn read_text() -> Result<String> {
+        read_text_from_clipboard().await
+    }
+}
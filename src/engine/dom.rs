@@ -0,0 +1,139 @@
+use anyhow::Result;
+
+pub trait Dom: Clone + 'static {
+    type Node: DomNode;
+    type Element: DomElement<Self> + Into<Self::Node>;
+    type HtmlElement: DomHtmlElement<Self>;
+    type HtmlInputElement: DomHtmlInputElement;
+    type HtmlTextAreaElement: DomHtmlTextAreaElement;
+    type Event: DomEvent<Self>;
+    type KeyboardEvent: DomKeyboardEvent;
+    type MouseEvent: DomMouseEvent;
+    type Storage: DomStorage;
+    type EventListener: DomEventListener;
+
+    fn element_by_id(&self, id: &str) -> Result<Self::Element>;
+
+    fn html_element_by_id(&self, id: &str) -> Result<Self::HtmlElement>;
+
+    fn input_element_by_id(&self, id: &str) -> Result<Self::HtmlInputElement>;
+
+    fn create_element(&self, tag: &str) -> Result<Self::Element>;
+
+    fn to_html_element(&self, element: &Self::Element) -> Result<Self::HtmlElement>;
+
+    fn to_input_element(&self, element: &Self::Element) -> Result<Self::HtmlInputElement>;
+
+    fn to_text_area_element(&self, element: &Self::Element) -> Result<Self::HtmlTextAreaElement>;
+
+    fn local_storage(&self) -> Result<Self::Storage>;
+
+    /// The preferred language of the user, like `ja` or `en-US`.
+    fn language(&self) -> Option<String>;
+
+    fn write_clipboard_text(&self, text: &str);
+
+    fn read_clipboard_text<F>(&self, callback: F)
+    where
+        F: FnOnce(Result<String>) + 'static;
+
+    fn download_text(&self, file_name: &str, mime_type: &str, content: &str) -> Result<()>;
+
+    fn read_file_text<F>(&self, input: &Self::HtmlInputElement, callback: F) -> Result<()>
+    where
+        F: FnOnce(Result<String>) + 'static;
+}
+
+pub trait DomNode: Clone {}
+
+pub trait DomElement<D: Dom>: Clone {
+    fn set_text_content(&self, value: &str);
+
+    fn set_class_name(&self, value: &str);
+
+    fn set_attribute(&self, name: &str, value: &str) -> Result<()>;
+
+    fn append_child(&self, child: &D::Node) -> Result<D::Node>;
+
+    fn remove(&self);
+
+    fn add_event_listener<F>(&self, event: &str, listener: F) -> Result<D::EventListener>
+    where
+        F: FnMut(D::Event) + 'static;
+}
+
+pub trait DomHtmlElement<D: Dom>: Clone {
+    fn get_inner_text(&self) -> String;
+
+    fn set_inner_text(&self, value: &str);
+
+    fn set_class(&self, class: &str);
+
+    fn add_class(&self, class: &str) -> Result<()>;
+
+    fn remove_class(&self, class: &str) -> Result<()>;
+
+    fn append_child(&self, child: &D::Node) -> Result<D::Node>;
+
+    fn is_scrolled_to_bottom(&self) -> bool;
+
+    fn scroll_to_bottom(&self);
+}
+
+pub trait DomHtmlInputElement: Clone {
+    fn get_value(&self) -> String;
+
+    fn set_value(&self, value: &str);
+
+    fn insert_at_caret(&self, text: &str) -> Result<()>;
+
+    fn focus(&self) -> Result<()>;
+}
+
+pub trait DomHtmlTextAreaElement: Clone {
+    fn get_value(&self) -> String;
+}
+
+pub trait DomEvent<D: Dom> {
+    fn get_target_input_element(&self) -> Result<D::HtmlInputElement>;
+
+    fn stop_propagation(&self);
+
+    fn to_keyboard_event(&self) -> Result<D::KeyboardEvent>;
+
+    fn to_mouse_event(&self) -> Result<D::MouseEvent>;
+}
+
+pub trait DomKeyboardEvent {
+    fn key(&self) -> String;
+
+    fn ctrl_key(&self) -> bool;
+
+    fn ctrl_enter(&self) -> bool {
+        self.ctrl_key() && self.key() == "Enter"
+    }
+}
+
+pub trait DomMouseEvent {
+    fn ctrl_key(&self) -> bool;
+
+    fn meta_key(&self) -> bool;
+
+    fn alt_key(&self) -> bool;
+
+    fn modifier_key(&self) -> bool {
+        self.ctrl_key() || self.meta_key() || self.alt_key()
+    }
+}
+
+pub trait DomStorage {
+    fn get_item(&self, key: &str) -> Result<Option<String>>;
+
+    fn set_item(&self, key: &str, value: &str) -> Result<()>;
+
+    fn remove_item(&self, key: &str) -> Result<()>;
+}
+
+pub trait DomEventListener {
+    fn remove(&self) -> Result<()>;
+}
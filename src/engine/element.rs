@@ -2,10 +2,11 @@ use anyhow::Result;
 use crate::browser::{
     get_element_by_id, create_element,
     add_event_listener_with_callback,
-    element_append_child,
-    EventClosure,
+    element_append_child, set_attribute,
+    create_event_closure, EventClosure,
 };
-use super::Node;
+use super::dom::DomElement;
+use super::{Node, Event, EventListener, WebDom};
 
 #[derive(Debug, Clone)]
 pub struct Element {
@@ -25,6 +26,10 @@ impl Element {
         Ok(Self::new(create_element(tag)?))
     }
 
+    pub(crate) fn inner(&self) -> &web_sys::Element {
+        &self.inner
+    }
+
     pub fn set_text_content(&self, value: &str) {
         self.inner.set_text_content(Some(value))
     }
@@ -33,11 +38,19 @@ impl Element {
         self.inner.set_class_name(value)
     }
 
+    pub fn set_attribute(&self, name: &str, value: &str) -> Result<()> {
+        set_attribute(&self.inner, name, value)
+    }
+
     pub fn append_child(&self, child: &Node) -> Result<Node> {
         Ok(Node::new(element_append_child(
             &self.inner, child.inner())?))
     }
 
+    pub fn remove(&self) {
+        self.inner.remove()
+    }
+
     pub fn add_event_listener_with_callback(
         &self,
         event: &str,
@@ -51,8 +64,41 @@ impl Element {
     }
 }
 
-impl Into<Node> for Element {
-    fn into(self) -> Node {
-        Node::new(self.inner.into())
+impl From<Element> for Node {
+    fn from(element: Element) -> Node {
+        Node::new(element.inner.into())
+    }
+}
+
+impl DomElement<WebDom> for Element {
+    fn set_text_content(&self, value: &str) {
+        self.set_text_content(value)
+    }
+
+    fn set_class_name(&self, value: &str) {
+        self.set_class_name(value)
+    }
+
+    fn set_attribute(&self, name: &str, value: &str) -> Result<()> {
+        self.set_attribute(name, value)
+    }
+
+    fn append_child(&self, child: &Node) -> Result<Node> {
+        self.append_child(child)
+    }
+
+    fn remove(&self) {
+        self.remove()
+    }
+
+    fn add_event_listener<F>(&self, event: &str, mut listener: F) -> Result<EventListener>
+    where
+        F: FnMut(Event) + 'static,
+    {
+        let closure = create_event_closure(move |e: web_sys::Event| {
+            listener(Event::new(e))
+        });
+        self.add_event_listener_with_callback(event, &closure)?;
+        Ok(EventListener::new(self.inner.clone().into(), event, closure))
     }
 }
@@ -1,8 +1,10 @@
 use anyhow::Result;
 use crate::browser::{
-    event_to_keboard_event, get_input_element_from_event,
+    event_to_keboard_event, event_to_mouse_event,
+    get_input_element_from_event,
 };
-use super::{HtmlInputElement, KeyboardEvent};
+use super::dom::DomEvent;
+use super::{HtmlInputElement, KeyboardEvent, MouseEvent, WebDom};
 
 #[derive(Debug, Clone)]
 pub struct Event {
@@ -21,6 +23,10 @@ impl Event {
     pub fn get_target_input_element(&self) -> Result<HtmlInputElement> {
         Ok(HtmlInputElement::new(get_input_element_from_event(&self.inner)?))
     }
+
+    pub fn stop_propagation(&self) {
+        self.inner.stop_propagation()
+    }
 }
 
 impl TryInto<KeyboardEvent> for Event {
@@ -30,3 +36,29 @@ impl TryInto<KeyboardEvent> for Event {
         Ok(KeyboardEvent::new(event_to_keboard_event(&self.inner)?))
     }
 }
+
+impl TryInto<MouseEvent> for Event {
+    type Error = anyhow::Error;
+
+    fn try_into(self) -> Result<MouseEvent> {
+        Ok(MouseEvent::new(event_to_mouse_event(&self.inner)?))
+    }
+}
+
+impl DomEvent<WebDom> for Event {
+    fn get_target_input_element(&self) -> Result<HtmlInputElement> {
+        self.get_target_input_element()
+    }
+
+    fn stop_propagation(&self) {
+        self.stop_propagation()
+    }
+
+    fn to_keyboard_event(&self) -> Result<KeyboardEvent> {
+        self.clone().try_into()
+    }
+
+    fn to_mouse_event(&self) -> Result<MouseEvent> {
+        self.clone().try_into()
+    }
+}
@@ -0,0 +1,33 @@
+use anyhow::Result;
+use crate::browser::{remove_event_listener_with_callback, EventClosure};
+use super::dom::DomEventListener;
+
+pub struct EventListener {
+    target: web_sys::EventTarget,
+    event: String,
+    closure: EventClosure,
+}
+
+impl EventListener {
+    pub fn new(target: web_sys::EventTarget, event: &str, closure: EventClosure) -> Self {
+        Self {
+            target,
+            event: event.to_string(),
+            closure,
+        }
+    }
+
+    pub fn remove(&self) -> Result<()> {
+        remove_event_listener_with_callback(
+            &self.target,
+            &self.event,
+            &self.closure,
+        )
+    }
+}
+
+impl DomEventListener for EventListener {
+    fn remove(&self) -> Result<()> {
+        self.remove()
+    }
+}
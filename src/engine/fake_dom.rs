@@ -0,0 +1,562 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::{Rc, Weak};
+use anyhow::{anyhow, Result};
+use super::dom::{
+    Dom, DomNode, DomElement, DomHtmlElement, DomHtmlInputElement,
+    DomHtmlTextAreaElement, DomEvent, DomKeyboardEvent, DomMouseEvent, DomStorage, DomEventListener,
+};
+
+type FakeListener = Rc<RefCell<dyn FnMut(FakeEvent)>>;
+type FakeTask = Box<dyn FnOnce()>;
+
+#[derive(Default)]
+struct FakeElementInner {
+    tag: String,
+    id: String,
+    text: String,
+    class_name: String,
+    attributes: HashMap<String, String>,
+    value: String,
+    caret: Option<usize>,
+    focused: bool,
+    file: Option<String>,
+    children: Vec<FakeElement>,
+    parent: Weak<RefCell<FakeElementInner>>,
+    listeners: Vec<(String, FakeListener)>,
+}
+
+#[derive(Clone, Default)]
+pub struct FakeElement {
+    inner: Rc<RefCell<FakeElementInner>>,
+}
+
+impl FakeElement {
+    pub fn new(tag: &str) -> Self {
+        let element = Self::default();
+        element.inner.borrow_mut().tag = tag.to_string();
+        element
+    }
+
+    pub fn new_with_id(tag: &str, id: &str) -> Self {
+        let element = Self::new(tag);
+        element.inner.borrow_mut().id = id.to_string();
+        element
+    }
+
+    pub fn tag(&self) -> String {
+        self.inner.borrow().tag.clone()
+    }
+
+    pub fn has_class(&self, class: &str) -> bool {
+        self.inner.borrow().class_name
+            .split_whitespace()
+            .any(|name| name == class)
+    }
+
+    pub fn attribute(&self, name: &str) -> Option<String> {
+        self.inner.borrow().attributes.get(name).cloned()
+    }
+
+    pub fn text_content(&self) -> String {
+        let inner = self.inner.borrow();
+        let mut text = inner.text.clone();
+        for child in &inner.children {
+            text.push_str(&child.text_content());
+        }
+        text
+    }
+
+    pub fn children(&self) -> Vec<FakeElement> {
+        self.inner.borrow().children.clone()
+    }
+
+    pub fn listener_count(&self) -> usize {
+        let mut count = self.inner.borrow().listeners.len();
+        for child in self.children() {
+            count += child.listener_count();
+        }
+        count
+    }
+
+    pub fn value(&self) -> String {
+        self.inner.borrow().value.clone()
+    }
+
+    pub fn set_caret(&self, caret: usize) {
+        self.inner.borrow_mut().caret = Some(caret);
+    }
+
+    pub fn is_focused(&self) -> bool {
+        self.inner.borrow().focused
+    }
+
+    pub fn set_file(&self, content: &str) {
+        self.inner.borrow_mut().file = Some(content.to_string());
+    }
+
+    pub fn append(&self, child: &FakeElement) {
+        child.inner.borrow_mut().parent = Rc::downgrade(&self.inner);
+        self.inner.borrow_mut().children.push(child.clone());
+    }
+
+    pub fn find_by_id(&self, id: &str) -> Option<FakeElement> {
+        if self.inner.borrow().id == id {
+            return Some(self.clone());
+        }
+        self.children()
+            .iter()
+            .find_map(|child| child.find_by_id(id))
+    }
+
+    pub fn find_by_class(&self, class: &str) -> Vec<FakeElement> {
+        let mut result = Vec::new();
+        if self.has_class(class) {
+            result.push(self.clone());
+        }
+        for child in self.children() {
+            result.extend(child.find_by_class(class));
+        }
+        result
+    }
+
+    pub fn dispatch(&self, event_name: &str, mut event: FakeEvent) {
+        if event.target.is_none() {
+            event.target = Some(self.clone());
+        }
+        let mut current = Some(self.clone());
+        while let Some(element) = current {
+            let listeners: Vec<FakeListener> = element.inner.borrow().listeners
+                .iter()
+                .filter(|(name, _)| name == event_name)
+                .map(|(_, listener)| listener.clone())
+                .collect();
+            for listener in listeners {
+                (listener.borrow_mut())(event.clone());
+            }
+            if event.is_propagation_stopped() {
+                break;
+            }
+            current = element.inner.borrow().parent
+                .upgrade()
+                .map(|inner| FakeElement { inner });
+        }
+    }
+
+    pub fn input(&self, value: &str) {
+        self.inner.borrow_mut().value = value.to_string();
+        self.dispatch("input", FakeEvent::new());
+    }
+
+    pub fn click(&self) {
+        self.dispatch("click", FakeEvent::mouse(false));
+    }
+
+    fn clear_children(&self) {
+        let mut inner = self.inner.borrow_mut();
+        for child in &inner.children {
+            child.inner.borrow_mut().parent = Weak::new();
+        }
+        inner.children.clear();
+    }
+}
+
+impl DomNode for FakeElement {}
+
+impl DomElement<FakeDom> for FakeElement {
+    fn set_text_content(&self, value: &str) {
+        self.clear_children();
+        self.inner.borrow_mut().text = value.to_string();
+    }
+
+    fn set_class_name(&self, value: &str) {
+        self.inner.borrow_mut().class_name = value.to_string();
+    }
+
+    fn set_attribute(&self, name: &str, value: &str) -> Result<()> {
+        self.inner.borrow_mut().attributes.insert(name.to_string(), value.to_string());
+        Ok(())
+    }
+
+    fn append_child(&self, child: &FakeElement) -> Result<FakeElement> {
+        self.append(child);
+        Ok(child.clone())
+    }
+
+    fn remove(&self) {
+        let parent = self.inner.borrow().parent.upgrade();
+        if let Some(parent) = parent {
+            parent.borrow_mut().children
+                .retain(|child| !Rc::ptr_eq(&child.inner, &self.inner));
+        }
+        self.inner.borrow_mut().parent = Weak::new();
+    }
+
+    fn add_event_listener<F>(&self, event: &str, listener: F) -> Result<FakeEventListener>
+    where
+        F: FnMut(FakeEvent) + 'static,
+    {
+        let listener: FakeListener = Rc::new(RefCell::new(listener));
+        self.inner.borrow_mut().listeners
+            .push((event.to_string(), listener.clone()));
+        Ok(FakeEventListener {
+            element: self.clone(),
+            listener,
+        })
+    }
+}
+
+impl DomHtmlElement<FakeDom> for FakeElement {
+    fn get_inner_text(&self) -> String {
+        self.text_content()
+    }
+
+    fn set_inner_text(&self, value: &str) {
+        DomElement::set_text_content(self, value)
+    }
+
+    fn set_class(&self, class: &str) {
+        DomElement::set_class_name(self, class)
+    }
+
+    fn add_class(&self, class: &str) -> Result<()> {
+        if !self.has_class(class) {
+            let mut inner = self.inner.borrow_mut();
+            let class_name = format!("{} {}", inner.class_name, class);
+            inner.class_name = class_name.trim().to_string();
+        }
+        Ok(())
+    }
+
+    fn remove_class(&self, class: &str) -> Result<()> {
+        let class_name = self.inner.borrow().class_name
+            .split_whitespace()
+            .filter(|name| *name != class)
+            .collect::<Vec<_>>()
+            .join(" ");
+        self.inner.borrow_mut().class_name = class_name;
+        Ok(())
+    }
+
+    fn append_child(&self, child: &FakeElement) -> Result<FakeElement> {
+        self.append(child);
+        Ok(child.clone())
+    }
+
+    fn is_scrolled_to_bottom(&self) -> bool {
+        true
+    }
+
+    fn scroll_to_bottom(&self) {}
+}
+
+impl DomHtmlInputElement for FakeElement {
+    fn get_value(&self) -> String {
+        self.value()
+    }
+
+    fn set_value(&self, value: &str) {
+        let mut inner = self.inner.borrow_mut();
+        inner.value = value.to_string();
+        inner.caret = None;
+    }
+
+    fn insert_at_caret(&self, text: &str) -> Result<()> {
+        let mut inner = self.inner.borrow_mut();
+        let length = inner.value.chars().count();
+        let caret = inner.caret.unwrap_or(length).min(length);
+        let mut value: String = inner.value.chars().take(caret).collect();
+        value.push_str(text);
+        value.extend(inner.value.chars().skip(caret));
+        inner.value = value;
+        inner.caret = Some(caret + text.chars().count());
+        Ok(())
+    }
+
+    fn focus(&self) -> Result<()> {
+        self.inner.borrow_mut().focused = true;
+        Ok(())
+    }
+}
+
+pub struct FakeEventListener {
+    element: FakeElement,
+    listener: FakeListener,
+}
+
+impl DomEventListener for FakeEventListener {
+    fn remove(&self) -> Result<()> {
+        let listener = Rc::as_ptr(&self.listener) as *const ();
+        self.element.inner.borrow_mut().listeners
+            .retain(|(_, other)| Rc::as_ptr(other) as *const () != listener);
+        Ok(())
+    }
+}
+
+impl DomHtmlTextAreaElement for FakeElement {
+    fn get_value(&self) -> String {
+        self.value()
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct FakeEvent {
+    target: Option<FakeElement>,
+    key: Option<String>,
+    mouse: bool,
+    ctrl_key: bool,
+    meta_key: bool,
+    alt_key: bool,
+    propagation_stopped: Rc<Cell<bool>>,
+}
+
+impl FakeEvent {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn keyboard(key: &str, ctrl_key: bool) -> Self {
+        Self {
+            key: Some(key.to_string()),
+            ctrl_key,
+            ..Self::default()
+        }
+    }
+
+    pub fn mouse(ctrl_key: bool) -> Self {
+        Self {
+            mouse: true,
+            ctrl_key,
+            ..Self::default()
+        }
+    }
+
+    pub fn is_propagation_stopped(&self) -> bool {
+        self.propagation_stopped.get()
+    }
+}
+
+impl DomEvent<FakeDom> for FakeEvent {
+    fn get_target_input_element(&self) -> Result<FakeElement> {
+        self.target
+            .clone()
+            .ok_or_else(|| anyhow!("No target found on event"))
+    }
+
+    fn stop_propagation(&self) {
+        self.propagation_stopped.set(true);
+    }
+
+    fn to_keyboard_event(&self) -> Result<FakeEvent> {
+        match self.key {
+            Some(_) => Ok(self.clone()),
+            None => Err(anyhow!("No KeyboardEvent found on event")),
+        }
+    }
+
+    fn to_mouse_event(&self) -> Result<FakeEvent> {
+        if self.mouse {
+            Ok(self.clone())
+        } else {
+            Err(anyhow!("No MouseEvent found on event"))
+        }
+    }
+}
+
+impl DomKeyboardEvent for FakeEvent {
+    fn key(&self) -> String {
+        self.key.clone().unwrap_or_default()
+    }
+
+    fn ctrl_key(&self) -> bool {
+        self.ctrl_key
+    }
+}
+
+impl DomMouseEvent for FakeEvent {
+    fn ctrl_key(&self) -> bool {
+        self.ctrl_key
+    }
+
+    fn meta_key(&self) -> bool {
+        self.meta_key
+    }
+
+    fn alt_key(&self) -> bool {
+        self.alt_key
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct FakeStorage {
+    items: Rc<RefCell<HashMap<String, String>>>,
+}
+
+impl DomStorage for FakeStorage {
+    fn get_item(&self, key: &str) -> Result<Option<String>> {
+        Ok(self.items.borrow().get(key).cloned())
+    }
+
+    fn set_item(&self, key: &str, value: &str) -> Result<()> {
+        self.items.borrow_mut().insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+
+    fn remove_item(&self, key: &str) -> Result<()> {
+        self.items.borrow_mut().remove(key);
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FakeDownload {
+    pub file_name: String,
+    pub mime_type: String,
+    pub content: String,
+}
+
+#[derive(Clone, Default)]
+pub struct FakeDom {
+    document: FakeElement,
+    storage: FakeStorage,
+    language: Rc<RefCell<Option<String>>>,
+    clipboard: Rc<RefCell<Vec<String>>>,
+    downloads: Rc<RefCell<Vec<FakeDownload>>>,
+    pending: Rc<RefCell<Vec<FakeTask>>>,
+    kept_alive: Rc<RefCell<Vec<Rc<dyn std::any::Any>>>>,
+}
+
+impl FakeDom {
+    pub fn new() -> Self {
+        Self {
+            document: FakeElement::new("body"),
+            ..Self::default()
+        }
+    }
+
+    pub fn add_element(&self, tag: &str, id: &str) -> FakeElement {
+        let element = FakeElement::new_with_id(tag, id);
+        self.document.append(&element);
+        element
+    }
+
+    pub fn get(&self, id: &str) -> FakeElement {
+        self.document
+            .find_by_id(id)
+            .unwrap_or_else(|| panic!("No Element found with ID {}", id))
+    }
+
+    pub fn storage(&self) -> FakeStorage {
+        self.storage.clone()
+    }
+
+    pub fn set_language(&self, language: &str) {
+        self.language.replace(Some(language.to_string()));
+    }
+
+    pub fn clipboard(&self) -> Vec<String> {
+        self.clipboard.borrow().clone()
+    }
+
+    pub fn downloads(&self) -> Vec<FakeDownload> {
+        self.downloads.borrow().clone()
+    }
+
+    pub fn keep_alive<T: 'static>(&self, value: Rc<T>) {
+        self.kept_alive.borrow_mut().push(value);
+    }
+
+    pub fn run_pending(&self) {
+        let tasks = self.pending.replace(Vec::new());
+        for task in tasks {
+            task();
+        }
+    }
+}
+
+impl Dom for FakeDom {
+    type Node = FakeElement;
+    type Element = FakeElement;
+    type HtmlElement = FakeElement;
+    type HtmlInputElement = FakeElement;
+    type HtmlTextAreaElement = FakeElement;
+    type Event = FakeEvent;
+    type KeyboardEvent = FakeEvent;
+    type MouseEvent = FakeEvent;
+    type Storage = FakeStorage;
+    type EventListener = FakeEventListener;
+
+    fn element_by_id(&self, id: &str) -> Result<FakeElement> {
+        self.document
+            .find_by_id(id)
+            .ok_or_else(|| anyhow!("No Element found with ID {}", id))
+    }
+
+    fn html_element_by_id(&self, id: &str) -> Result<FakeElement> {
+        self.element_by_id(id)
+    }
+
+    fn input_element_by_id(&self, id: &str) -> Result<FakeElement> {
+        self.element_by_id(id)
+    }
+
+    fn create_element(&self, tag: &str) -> Result<FakeElement> {
+        Ok(FakeElement::new(tag))
+    }
+
+    fn to_html_element(&self, element: &FakeElement) -> Result<FakeElement> {
+        Ok(element.clone())
+    }
+
+    fn to_input_element(&self, element: &FakeElement) -> Result<FakeElement> {
+        Ok(element.clone())
+    }
+
+    fn to_text_area_element(&self, element: &FakeElement) -> Result<FakeElement> {
+        Ok(element.clone())
+    }
+
+    fn local_storage(&self) -> Result<FakeStorage> {
+        Ok(self.storage.clone())
+    }
+
+    fn language(&self) -> Option<String> {
+        self.language.borrow().clone()
+    }
+
+    fn write_clipboard_text(&self, text: &str) {
+        self.clipboard.borrow_mut().push(text.to_string());
+    }
+
+    fn read_clipboard_text<F>(&self, callback: F)
+    where
+        F: FnOnce(Result<String>) + 'static,
+    {
+        let text = self.clipboard.borrow().last().cloned().unwrap_or_default();
+        self.pending
+            .borrow_mut()
+            .push(Box::new(move || callback(Ok(text))));
+    }
+
+    fn download_text(&self, file_name: &str, mime_type: &str, content: &str) -> Result<()> {
+        self.downloads.borrow_mut().push(FakeDownload {
+            file_name: file_name.to_string(),
+            mime_type: mime_type.to_string(),
+            content: content.to_string(),
+        });
+        Ok(())
+    }
+
+    fn read_file_text<F>(&self, input: &FakeElement, callback: F) -> Result<()>
+    where
+        F: FnOnce(Result<String>) + 'static,
+    {
+        let file = input.inner.borrow_mut().file.take();
+        if let Some(content) = file {
+            self.pending
+                .borrow_mut()
+                .push(Box::new(move || callback(Ok(content))));
+        }
+        Ok(())
+    }
+}
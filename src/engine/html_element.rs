@@ -1,9 +1,10 @@
 use anyhow::Result;
 use crate::browser::{
-    get_html_element_by_id, is_scrolled_to_bottom, append_child,
-    remove_class, set_class,
+    get_html_element_by_id, element_to_html_element, is_scrolled_to_bottom,
+    append_child, add_class, remove_class, set_class,
 };
-use super::Node;
+use super::dom::DomHtmlElement;
+use super::{Element, Node, WebDom};
 
 #[derive(Debug, Clone)]
 pub struct HtmlElement {
@@ -19,6 +20,10 @@ impl HtmlElement {
         Ok(Self::new(get_html_element_by_id(id)?))
     }
 
+    pub fn new_from_element(element: &Element) -> Result<Self> {
+        Ok(Self::new(element_to_html_element(element.inner().clone())?))
+    }
+
     pub fn get_inner_text(&self) -> String {
         self.inner.inner_text()
     }
@@ -31,6 +36,10 @@ impl HtmlElement {
         set_class(&self.inner, class)
     }
 
+    pub fn add_class(&self, class: &str) -> Result<()> {
+        add_class(&self.inner, class)
+    }
+
     pub fn remove_class(&self, class: &str) -> Result<()> {
         remove_class(&self.inner, class)
     }
@@ -48,3 +57,37 @@ impl HtmlElement {
         self.inner.set_scroll_top(self.inner.scroll_height())
     }
 }
+
+impl DomHtmlElement<WebDom> for HtmlElement {
+    fn get_inner_text(&self) -> String {
+        self.get_inner_text()
+    }
+
+    fn set_inner_text(&self, value: &str) {
+        self.set_inner_text(value)
+    }
+
+    fn set_class(&self, class: &str) {
+        self.set_class(class)
+    }
+
+    fn add_class(&self, class: &str) -> Result<()> {
+        self.add_class(class)
+    }
+
+    fn remove_class(&self, class: &str) -> Result<()> {
+        self.remove_class(class)
+    }
+
+    fn append_child(&self, child: &Node) -> Result<Node> {
+        self.append_child(child)
+    }
+
+    fn is_scrolled_to_bottom(&self) -> bool {
+        self.is_scrolled_to_bottom()
+    }
+
+    fn scroll_to_bottom(&self) {
+        self.scroll_to_bottom()
+    }
+}
@@ -1,5 +1,10 @@
 use anyhow::Result;
-use crate::browser::get_input_element_by_id;
+use crate::browser::{
+    get_input_element_by_id, element_to_input_element,
+    insert_text_at_caret, focus,
+};
+use super::dom::DomHtmlInputElement;
+use super::{Blob, Element};
 
 #[derive(Debug, Clone)]
 pub struct HtmlInputElement {
@@ -15,6 +20,10 @@ impl HtmlInputElement {
         Ok(Self::new(get_input_element_by_id(id)?))
     }
 
+    pub fn new_from_element(element: &Element) -> Result<Self> {
+        Ok(Self::new(element_to_input_element(element.inner().clone())?))
+    }
+
     pub fn get_value(&self) -> String {
         self.inner.value()
     }
@@ -22,4 +31,37 @@ impl HtmlInputElement {
     pub fn set_value(&self, value: &str) {
         self.inner.set_value(value)
     }
+
+    pub fn first_file(&self) -> Option<Blob> {
+        self.inner
+            .files()
+            .and_then(|files| files.get(0))
+            .map(|file| Blob::new(file.into()))
+    }
+
+    pub fn insert_at_caret(&self, text: &str) -> Result<()> {
+        insert_text_at_caret(&self.inner, text)
+    }
+
+    pub fn focus(&self) -> Result<()> {
+        focus(&self.inner)
+    }
+}
+
+impl DomHtmlInputElement for HtmlInputElement {
+    fn get_value(&self) -> String {
+        self.get_value()
+    }
+
+    fn set_value(&self, value: &str) {
+        self.set_value(value)
+    }
+
+    fn insert_at_caret(&self, text: &str) -> Result<()> {
+        self.insert_at_caret(text)
+    }
+
+    fn focus(&self) -> Result<()> {
+        self.focus()
+    }
 }
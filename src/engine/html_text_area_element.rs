@@ -0,0 +1,29 @@
+use anyhow::Result;
+use crate::browser::element_to_text_area_element;
+use super::dom::DomHtmlTextAreaElement;
+use super::Element;
+
+#[derive(Debug, Clone)]
+pub struct HtmlTextAreaElement {
+    inner: web_sys::HtmlTextAreaElement,
+}
+
+impl HtmlTextAreaElement {
+    pub fn new(inner: web_sys::HtmlTextAreaElement) -> Self {
+        Self { inner }
+    }
+
+    pub fn new_from_element(element: &Element) -> Result<Self> {
+        Ok(Self::new(element_to_text_area_element(element.inner().clone())?))
+    }
+
+    pub fn get_value(&self) -> String {
+        self.inner.value()
+    }
+}
+
+impl DomHtmlTextAreaElement for HtmlTextAreaElement {
+    fn get_value(&self) -> String {
+        self.get_value()
+    }
+}
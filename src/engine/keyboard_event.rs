@@ -1,3 +1,5 @@
+use super::dom::DomKeyboardEvent;
+
 #[derive(Debug, Clone)]
 pub struct KeyboardEvent {
     inner: web_sys::KeyboardEvent,
@@ -15,8 +17,14 @@ impl KeyboardEvent {
     pub fn ctrl_key(&self) -> bool {
         self.inner.ctrl_key()
     }
+}
+
+impl DomKeyboardEvent for KeyboardEvent {
+    fn key(&self) -> String {
+        self.key()
+    }
 
-    pub fn ctrl_enter(&self) -> bool {
-        self.ctrl_key() && self.key() == "Enter"
+    fn ctrl_key(&self) -> bool {
+        self.ctrl_key()
     }
 }
@@ -0,0 +1,38 @@
+use super::dom::DomMouseEvent;
+
+#[derive(Debug, Clone)]
+pub struct MouseEvent {
+    inner: web_sys::MouseEvent,
+}
+
+impl MouseEvent {
+    pub fn new(inner: web_sys::MouseEvent) -> Self {
+        Self { inner }
+    }
+
+    pub fn ctrl_key(&self) -> bool {
+        self.inner.ctrl_key()
+    }
+
+    pub fn meta_key(&self) -> bool {
+        self.inner.meta_key()
+    }
+
+    pub fn alt_key(&self) -> bool {
+        self.inner.alt_key()
+    }
+}
+
+impl DomMouseEvent for MouseEvent {
+    fn ctrl_key(&self) -> bool {
+        self.ctrl_key()
+    }
+
+    fn meta_key(&self) -> bool {
+        self.meta_key()
+    }
+
+    fn alt_key(&self) -> bool {
+        self.alt_key()
+    }
+}
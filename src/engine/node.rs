@@ -1,3 +1,5 @@
+use super::dom::DomNode;
+
 #[derive(Debug, Clone)]
 pub struct Node {
     inner: web_sys::Node,
@@ -12,3 +14,5 @@ impl Node {
         &self.inner
     }
 }
+
+impl DomNode for Node {}
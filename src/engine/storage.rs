@@ -0,0 +1,46 @@
+use anyhow::Result;
+use crate::browser::{
+    local_storage, storage_get_item, storage_set_item, storage_remove_item,
+};
+use super::dom::DomStorage;
+
+#[derive(Debug, Clone)]
+pub struct Storage {
+    inner: web_sys::Storage,
+}
+
+impl Storage {
+    pub fn new(inner: web_sys::Storage) -> Self {
+        Self { inner }
+    }
+
+    pub fn new_local() -> Result<Self> {
+        Ok(Self::new(local_storage()?))
+    }
+
+    pub fn get_item(&self, key: &str) -> Result<Option<String>> {
+        storage_get_item(&self.inner, key)
+    }
+
+    pub fn set_item(&self, key: &str, value: &str) -> Result<()> {
+        storage_set_item(&self.inner, key, value)
+    }
+
+    pub fn remove_item(&self, key: &str) -> Result<()> {
+        storage_remove_item(&self.inner, key)
+    }
+}
+
+impl DomStorage for Storage {
+    fn get_item(&self, key: &str) -> Result<Option<String>> {
+        self.get_item(key)
+    }
+
+    fn set_item(&self, key: &str, value: &str) -> Result<()> {
+        self.set_item(key, value)
+    }
+
+    fn remove_item(&self, key: &str) -> Result<()> {
+        self.remove_item(key)
+    }
+}
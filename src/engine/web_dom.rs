@@ -0,0 +1,102 @@
+use anyhow::Result;
+use crate::browser::{spawn_local, language};
+use super::dom::Dom;
+use super::{
+    Node, Element, HtmlElement, HtmlInputElement, HtmlTextAreaElement, Event, KeyboardEvent,
+    MouseEvent, Storage, EventListener, Clipboard, Blob,
+};
+
+#[derive(Debug, Clone, Default)]
+pub struct WebDom {}
+
+impl WebDom {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Dom for WebDom {
+    type Node = Node;
+    type Element = Element;
+    type HtmlElement = HtmlElement;
+    type HtmlInputElement = HtmlInputElement;
+    type HtmlTextAreaElement = HtmlTextAreaElement;
+    type Event = Event;
+    type KeyboardEvent = KeyboardEvent;
+    type MouseEvent = MouseEvent;
+    type Storage = Storage;
+    type EventListener = EventListener;
+
+    fn element_by_id(&self, id: &str) -> Result<Element> {
+        Element::new_from_id(id)
+    }
+
+    fn html_element_by_id(&self, id: &str) -> Result<HtmlElement> {
+        HtmlElement::new_from_id(id)
+    }
+
+    fn input_element_by_id(&self, id: &str) -> Result<HtmlInputElement> {
+        HtmlInputElement::new_from_id(id)
+    }
+
+    fn create_element(&self, tag: &str) -> Result<Element> {
+        Element::new_from_tag(tag)
+    }
+
+    fn to_html_element(&self, element: &Element) -> Result<HtmlElement> {
+        HtmlElement::new_from_element(element)
+    }
+
+    fn to_input_element(&self, element: &Element) -> Result<HtmlInputElement> {
+        HtmlInputElement::new_from_element(element)
+    }
+
+    fn to_text_area_element(&self, element: &Element) -> Result<HtmlTextAreaElement> {
+        HtmlTextAreaElement::new_from_element(element)
+    }
+
+    fn local_storage(&self) -> Result<Storage> {
+        Storage::new_local()
+    }
+
+    fn language(&self) -> Option<String> {
+        language()
+    }
+
+    fn write_clipboard_text(&self, text: &str) {
+        let text = text.to_string();
+        spawn_local(async move {
+            match Clipboard::write_text(&text).await {
+                Ok(_) => {}
+                Err(e) => error!("{}", e),
+            }
+        });
+    }
+
+    fn read_clipboard_text<F>(&self, callback: F)
+    where
+        F: FnOnce(Result<String>) + 'static,
+    {
+        spawn_local(async move {
+            callback(Clipboard::read_text().await);
+        });
+    }
+
+    fn download_text(&self, file_name: &str, mime_type: &str, content: &str) -> Result<()> {
+        Blob::new_from_text(content, mime_type)?.download(file_name)
+    }
+
+    fn read_file_text<F>(&self, input: &HtmlInputElement, callback: F) -> Result<()>
+    where
+        F: FnOnce(Result<String>) + 'static,
+    {
+        let file = match input.first_file() {
+            Some(file) => file,
+            None => return Ok(()),
+        };
+        spawn_local(async move {
+            callback(file.text().await);
+        });
+        Ok(())
+    }
+}
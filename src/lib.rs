@@ -2,11 +2,15 @@
 mod browser;
 mod engine;
 mod calculator;
+mod widget;
+mod api;
 
 use anyhow::Result;
 use wasm_bindgen::prelude::*;
 
-use calculator::Calculator;
+pub use calculator::Calculator;
+pub use widget::CalculatorWidget;
+pub use api::{evaluate, parse};
 
 #[wasm_bindgen(start)]
 pub fn main_js() -> Result<(), JsValue> {
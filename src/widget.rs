@@ -0,0 +1,32 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+use wasm_bindgen::prelude::*;
+use crate::engine::Element;
+use crate::calculator::Calculator;
+
+#[wasm_bindgen]
+pub struct CalculatorWidget {
+    calculator: Rc<RefCell<Calculator>>,
+}
+
+#[wasm_bindgen]
+impl CalculatorWidget {
+    /// Mounts a new calculator into `container`.
+    /// History, settings and variables are persisted in localStorage only
+    /// when `storage_key` is given, the latter two under `<storage_key>.settings`
+    /// and `<storage_key>.variables`.
+    #[wasm_bindgen(constructor)]
+    pub fn new(container: web_sys::HtmlElement, storage_key: Option<String>) -> Result<CalculatorWidget, JsValue> {
+        let calculator = Calculator::mount(Element::new(container.into()), storage_key)
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+        Ok(Self { calculator })
+    }
+
+    /// Removes the event listeners and the generated elements.
+    pub fn destroy(self) -> Result<(), JsValue> {
+        self.calculator
+            .borrow_mut()
+            .destroy()
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+}
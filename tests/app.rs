@@ -1,17 +1,238 @@
+use wasm_bindgen::JsCast;
 use wasm_bindgen_test::{wasm_bindgen_test_configure, wasm_bindgen_test};
+use web_sys::{
+    Document, Element, Event, EventInit, HtmlElement, HtmlInputElement,
+    HtmlTextAreaElement, KeyboardEvent, KeyboardEventInit,
+};
+
+use super_calculator::{Calculator, CalculatorWidget, evaluate, parse};
 
 wasm_bindgen_test_configure!(run_in_browser);
 
+const INDEX_HTML: &str = include_str!("../static/index.html");
+
+fn document() -> Document {
+    web_sys::window().unwrap().document().unwrap()
+}
+
+fn body_html() -> &'static str {
+    let start = INDEX_HTML.find("<body>").unwrap() + "<body>".len();
+    let end = INDEX_HTML.find("</body>").unwrap();
+    &INDEX_HTML[start..end]
+}
+
+fn setup() {
+    document().body().unwrap().set_inner_html(body_html());
+    web_sys::window().unwrap()
+        .local_storage().unwrap().unwrap()
+        .clear().unwrap();
+    Calculator::setup().unwrap();
+}
+
+fn get(id: &str) -> HtmlElement {
+    document()
+        .get_element_by_id(id)
+        .unwrap()
+        .dyn_into::<HtmlElement>()
+        .unwrap()
+}
+
+fn input_area() -> HtmlInputElement {
+    get("input-area").dyn_into::<HtmlInputElement>().unwrap()
+}
+
+fn history_items() -> Vec<Element> {
+    let items = get("history-container").get_elements_by_class_name("history-item");
+    (0..items.length()).map(|i| items.item(i).unwrap()).collect()
+}
+
+fn type_input(value: &str) {
+    type_into(&input_area(), value);
+}
+
+fn type_into(input: &HtmlInputElement, value: &str) {
+    input.set_value(value);
+    let mut init = EventInit::new();
+    init.bubbles(true);
+    let event = Event::new_with_event_init_dict("input", &init).unwrap();
+    input.dispatch_event(&event).unwrap();
+}
+
+fn press_key(key: &str, ctrl_key: bool) {
+    press_key_on(&input_area(), key, ctrl_key);
+}
+
+fn press_key_on(input: &HtmlInputElement, key: &str, ctrl_key: bool) {
+    let mut init = KeyboardEventInit::new();
+    init.key(key).ctrl_key(ctrl_key).bubbles(true);
+    let event = KeyboardEvent::new_with_keyboard_event_init_dict("keydown", &init).unwrap();
+    input.dispatch_event(&event).unwrap();
+}
+
+fn submit(value: &str) {
+    type_input(value);
+    press_key("Enter", true);
+}
+
+fn mount_container(id: &str) -> HtmlElement {
+    let container = document().create_element("div").unwrap();
+    container.set_id(id);
+    document().body().unwrap().append_child(&container).unwrap();
+    container.dyn_into::<HtmlElement>().unwrap()
+}
+
+fn find(container: &HtmlElement, class: &str) -> HtmlElement {
+    container
+        .get_elements_by_class_name(class)
+        .item(0)
+        .unwrap()
+        .dyn_into::<HtmlElement>()
+        .unwrap()
+}
+
+#[wasm_bindgen_test]
+fn input_is_echoed_to_formatted_display() {
+    setup();
+    type_input("1 + 2");
+    assert_eq!("1 + 2", get("formatted-display").inner_text());
+}
+
+#[wasm_bindgen_test]
+fn ctrl_enter_adds_history_entry() {
+    setup();
+    submit("1/2 + 3/4");
+    let items = history_items();
+    assert_eq!(1, items.len());
+    let text = items[0].get_elements_by_class_name("history-text").item(0).unwrap();
+    assert_eq!(Some("1 / 2 + 3 / 4 → 5 / 4".to_string()), text.text_content());
+    assert_eq!("", input_area().value());
+    assert_eq!("", get("formatted-display").inner_text());
+}
+
+#[wasm_bindgen_test]
+fn enter_without_ctrl_keeps_input() {
+    setup();
+    type_input("1 + 2");
+    press_key("Enter", false);
+    assert!(history_items().is_empty());
+    assert_eq!("1 + 2", input_area().value());
+}
+
+#[wasm_bindgen_test]
+fn error_entry_shows_error_span() {
+    setup();
+    submit("1 $ 2");
+    let items = history_items();
+    assert_eq!(1, items.len());
+    let error = items[0].get_elements_by_class_name("error-text").item(0).unwrap();
+    assert_eq!(Some(" 対応していない文字です".to_string()), error.text_content());
+}
+
+#[wasm_bindgen_test]
+fn history_survives_setup() {
+    setup();
+    submit("1 + 2");
+    submit("2 * 3");
+    document().body().unwrap().set_inner_html(body_html());
+    Calculator::setup().unwrap();
+    assert_eq!(2, history_items().len());
+}
+
+#[wasm_bindgen_test]
+fn clear_command_removes_history() {
+    setup();
+    submit("1 + 2");
+    submit("clear");
+    assert!(history_items().is_empty());
+}
+
+#[wasm_bindgen_test]
+fn history_click_inserts_result() {
+    setup();
+    submit("1/2 + 3/4");
+    type_input("2 * ");
+    history_items()[0].dyn_ref::<HtmlElement>().unwrap().click();
+    assert_eq!("2 * 5 / 4", input_area().value());
+    assert_eq!("2 * 5 / 4", get("formatted-display").inner_text());
+}
+
+#[wasm_bindgen_test]
+fn help_popup_toggles_hidden_class() {
+    setup();
+    let popup = get("help-popup-container");
+    assert!(popup.class_list().contains("hidden"));
+    get("help-button").click();
+    assert!(!popup.class_list().contains("hidden"));
+    get("close-help").click();
+    assert!(popup.class_list().contains("hidden"));
+}
+
+#[wasm_bindgen_test]
+fn document_mode_evaluates_each_line() {
+    setup();
+    get("mode-button").click();
+    assert!(get("calculator-view").class_list().contains("hidden"));
+    assert!(!get("document-view").class_list().contains("hidden"));
+
+    let document_area = get("document-area").dyn_into::<HtmlTextAreaElement>().unwrap();
+    document_area.set_value("メモ\nx = 1 + 2\nline2 * 2");
+    let mut init = EventInit::new();
+    init.bubbles(true);
+    let event = Event::new_with_event_init_dict("input", &init).unwrap();
+    document_area.dispatch_event(&event).unwrap();
+
+    let rows = get("document-results").get_elements_by_class_name("document-line");
+    let texts: Vec<Option<String>> = (0..rows.length())
+        .map(|i| rows.item(i).unwrap().text_content())
+        .collect();
+    assert_eq!(vec![Some("".to_string()), Some("3".to_string()), Some("6".to_string())], texts);
+}
+
+#[wasm_bindgen_test]
+fn widgets_mount_independently_and_destroy() {
+    document().body().unwrap().set_inner_html("");
+    let first = mount_container("first");
+    let second = mount_container("second");
+    let first_widget = CalculatorWidget::new(first.clone(), None).unwrap();
+    let second_widget = CalculatorWidget::new(second.clone(), None).unwrap();
+
+    let input = find(&first, "input-area").dyn_into::<HtmlInputElement>().unwrap();
+    type_into(&input, "1 + 2");
+    press_key_on(&input, "Enter", true);
+    assert_eq!(1, first.get_elements_by_class_name("history-item").length());
+    assert_eq!(0, second.get_elements_by_class_name("history-item").length());
+
+    first_widget.destroy().unwrap();
+    assert_eq!(0, first.child_element_count());
+    assert_eq!(1, second.child_element_count());
+    second_widget.destroy().unwrap();
+}
 
-// This runs a unit test in native Rust, so it can only use Rust APIs.
-#[test]
-fn rust_test() {
-    assert_eq!(1, 1);
+fn get_field(value: &wasm_bindgen::JsValue, path: &[&str]) -> wasm_bindgen::JsValue {
+    path.iter().fold(value.clone(), |value, key| {
+        js_sys::Reflect::get(&value, &(*key).into()).unwrap()
+    })
 }
 
+#[wasm_bindgen_test]
+fn evaluate_returns_structured_result() {
+    let evaluation = evaluate("1/2 + 3/4").unwrap();
+    assert_eq!(Some("5 / 4".to_string()), get_field(&evaluation, &["result"]).as_string());
+    assert_eq!(Some("fraction".to_string()), get_field(&evaluation, &["kind"]).as_string());
+    assert_eq!(Some(false), get_field(&evaluation, &["approximate"]).as_bool());
+    assert!(get_field(&evaluation, &["error"]).is_null());
+
+    let evaluation = evaluate("1 $ 2").unwrap();
+    assert_eq!(
+        Some("unsupported_character".to_string()),
+        get_field(&evaluation, &["error", "code"]).as_string(),
+    );
+    assert_eq!(Some(2.0), get_field(&evaluation, &["error", "span", "start"]).as_f64());
+}
 
-// This runs a unit test in the browser, so it can use browser APIs.
 #[wasm_bindgen_test]
-fn web_test() {
-    assert_eq!(1, 1);
+fn parse_returns_ast() {
+    let result = parse("1 + 2").unwrap();
+    assert_eq!(Some("binary".to_string()), get_field(&result, &["ast", "type"]).as_string());
+    assert_eq!(Some("+".to_string()), get_field(&result, &["ast", "operator"]).as_string());
 }